@@ -115,6 +115,10 @@ use crate::fs::{cwd, RenameFlags, ResolveFlags, Statx, StatxFlags};
     target_os = "wasi",
 )))]
 use crate::fs::{Dev, FileType};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::fs::{DnotifyFlags, FileOwner, LeaseType, MemfdSecretFlags, SyncFileRangeFlags};
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+use crate::fs::{FileLock, FileLockConflict, FileLockKind, FileLockWhence};
 use crate::fs::{Mode, OFlags, Stat, Timestamps};
 #[cfg(not(any(
     target_os = "haiku",
@@ -125,6 +129,8 @@ use crate::fs::{Mode, OFlags, Stat, Timestamps};
 )))]
 use crate::fs::{StatVfs, StatVfsMountFlags};
 use crate::io::{self, SeekFrom};
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+use crate::process::Pid;
 #[cfg(not(target_os = "wasi"))]
 use crate::process::{Gid, Uid};
 #[cfg(not(all(
@@ -150,6 +156,14 @@ use core::ptr::null;
     target_os = "macos",
 ))]
 use core::ptr::null_mut;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use linux_raw_sys::{
+    general::{file_clone_range, file_dedupe_range, fsxattr},
+    ioctl::{
+        FICLONE, FICLONERANGE, FIDEDUPERANGE, FS_IOC_FIEMAP, FS_IOC_FSGETXATTR, FS_IOC_FSSETXATTR,
+        FS_IOC_GETFLAGS, FS_IOC_SETFLAGS,
+    },
+};
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 use {
     super::super::conv::nonnegative_ret,
@@ -383,6 +397,54 @@ pub(crate) fn renameat2(
     renameat(old_dirfd, old_path, new_dirfd, new_path)
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn name_to_handle_at(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    handle: *mut c::c_void,
+    mount_id: &mut c::c_int,
+    flags: AtFlags,
+) -> io::Result<()> {
+    weak_or_syscall! {
+        fn name_to_handle_at(
+            dir_fd: c::c_int,
+            path: *const c::c_char,
+            handle: *mut c::c_void,
+            mount_id: *mut c::c_int,
+            flags: c::c_int
+        ) via SYS_name_to_handle_at -> c::c_int
+    }
+
+    ret(name_to_handle_at(
+        borrowed_fd(dirfd),
+        c_str(path),
+        handle,
+        mount_id,
+        flags.bits() as c::c_int,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn open_by_handle_at(
+    mount_fd: BorrowedFd<'_>,
+    handle: *const c::c_void,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    weak_or_syscall! {
+        fn open_by_handle_at(
+            mount_fd: c::c_int,
+            handle: *const c::c_void,
+            flags: c::c_int
+        ) via SYS_open_by_handle_at -> c::c_int
+    }
+
+    ret_owned_fd(open_by_handle_at(
+        borrowed_fd(mount_fd),
+        handle,
+        flags.bits(),
+    ))
+}
+
 #[cfg(not(target_os = "redox"))]
 pub(crate) fn symlinkat(
     old_path: &CStr,
@@ -868,6 +930,178 @@ pub(crate) fn fcntl_setfl(fd: BorrowedFd<'_>, flags: OFlags) -> io::Result<()> {
     unsafe { ret(c::fcntl(borrowed_fd(fd), c::F_SETFL, flags.bits())) }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_getlease(fd: BorrowedFd<'_>) -> io::Result<LeaseType> {
+    match unsafe { ret_c_int(c::fcntl(borrowed_fd(fd), c::F_GETLEASE))? } {
+        c::F_RDLCK => Ok(LeaseType::Read),
+        c::F_WRLCK => Ok(LeaseType::Write),
+        c::F_UNLCK => Ok(LeaseType::Unlock),
+        _ => Err(io::Errno::INVAL),
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_setlease(fd: BorrowedFd<'_>, lease: LeaseType) -> io::Result<()> {
+    let lease = match lease {
+        LeaseType::Read => c::F_RDLCK,
+        LeaseType::Write => c::F_WRLCK,
+        LeaseType::Unlock => c::F_UNLCK,
+    };
+    unsafe { ret(c::fcntl(borrowed_fd(fd), c::F_SETLEASE, lease as c::c_int)) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_getown_ex(fd: BorrowedFd<'_>) -> io::Result<Option<FileOwner>> {
+    use linux_raw_sys::general::{f_owner_ex, F_GETOWN_EX, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID};
+
+    let mut owner = MaybeUninit::<f_owner_ex>::uninit();
+    unsafe {
+        ret(c::fcntl(
+            borrowed_fd(fd),
+            F_GETOWN_EX as c::c_int,
+            owner.as_mut_ptr(),
+        ))?
+    };
+
+    let owner = unsafe { owner.assume_init() };
+    let pid = match unsafe { Pid::from_raw(owner.pid) } {
+        Some(pid) => pid,
+        None => return Ok(None),
+    };
+    match owner.type_ as u32 {
+        F_OWNER_TID => Ok(Some(FileOwner::Thread(pid))),
+        F_OWNER_PID => Ok(Some(FileOwner::Process(pid))),
+        F_OWNER_PGRP => Ok(Some(FileOwner::ProcessGroup(pid))),
+        _ => Err(io::Errno::INVAL),
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_setown_ex(fd: BorrowedFd<'_>, owner: Option<FileOwner>) -> io::Result<()> {
+    use linux_raw_sys::general::{f_owner_ex, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID, F_SETOWN_EX};
+
+    let (type_, pid) = match owner {
+        None => (F_OWNER_PID, None),
+        Some(FileOwner::Thread(pid)) => (F_OWNER_TID, Some(pid)),
+        Some(FileOwner::Process(pid)) => (F_OWNER_PID, Some(pid)),
+        Some(FileOwner::ProcessGroup(pid)) => (F_OWNER_PGRP, Some(pid)),
+    };
+    let owner = f_owner_ex {
+        type_: type_ as _,
+        pid: Pid::as_raw(pid),
+    };
+    unsafe {
+        ret(c::fcntl(
+            borrowed_fd(fd),
+            F_SETOWN_EX as c::c_int,
+            &owner as *const f_owner_ex,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_getsig(fd: BorrowedFd<'_>) -> io::Result<c::c_int> {
+    use linux_raw_sys::general::F_GETSIG;
+
+    unsafe { ret_c_int(c::fcntl(borrowed_fd(fd), F_GETSIG as c::c_int)) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_setsig(fd: BorrowedFd<'_>, sig: c::c_int) -> io::Result<()> {
+    use linux_raw_sys::general::F_SETSIG;
+
+    unsafe { ret(c::fcntl(borrowed_fd(fd), F_SETSIG as c::c_int, sig)) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fcntl_notify(fd: BorrowedFd<'_>, flags: DnotifyFlags) -> io::Result<()> {
+    unsafe {
+        ret(c::fcntl(
+            borrowed_fd(fd),
+            c::F_NOTIFY,
+            flags.bits() as c::c_int,
+        ))
+    }
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+pub(crate) fn fcntl_lock(
+    fd: BorrowedFd<'_>,
+    lock: &FileLock,
+    ofd: bool,
+    wait: bool,
+) -> io::Result<()> {
+    let cmd = match (ofd, wait) {
+        (false, false) => c::F_SETLK,
+        (false, true) => c::F_SETLKW,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        (true, false) => c::F_OFD_SETLK,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        (true, true) => c::F_OFD_SETLKW,
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        (true, _) => return Err(io::Errno::INVAL),
+    };
+    let raw = flock_from_file_lock(lock)?;
+    unsafe { ret(c::fcntl(borrowed_fd(fd), cmd, &raw)) }
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+pub(crate) fn fcntl_getlk(
+    fd: BorrowedFd<'_>,
+    lock: &FileLock,
+    ofd: bool,
+) -> io::Result<Option<FileLockConflict>> {
+    let cmd = match ofd {
+        false => c::F_GETLK,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        true => c::F_OFD_GETLK,
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        true => return Err(io::Errno::INVAL),
+    };
+    let mut raw = flock_from_file_lock(lock)?;
+    unsafe { ret(c::fcntl(borrowed_fd(fd), cmd, &mut raw))? };
+
+    let kind = match raw.l_type as c::c_int {
+        c::F_UNLCK => return Ok(None),
+        c::F_RDLCK => FileLockKind::Read,
+        c::F_WRLCK => FileLockKind::Write,
+        _ => return Err(io::Errno::INVAL),
+    };
+    Ok(Some(FileLockConflict {
+        lock: FileLock {
+            kind,
+            whence: FileLockWhence::Start,
+            start: raw.l_start.into(),
+            len: raw.l_len.into(),
+        },
+        // Open file description locks have an `l_pid` of -1.
+        pid: if raw.l_pid > 0 {
+            unsafe { Pid::from_raw(raw.l_pid) }
+        } else {
+            None
+        },
+    }))
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+fn flock_from_file_lock(lock: &FileLock) -> io::Result<c::flock> {
+    // Use `zeroed` to initialize any platform-specific fields.
+    let mut raw: c::flock = unsafe { core::mem::zeroed() };
+    raw.l_type = match lock.kind {
+        FileLockKind::Read => c::F_RDLCK,
+        FileLockKind::Write => c::F_WRLCK,
+        FileLockKind::Unlock => c::F_UNLCK,
+    } as _;
+    raw.l_whence = match lock.whence {
+        FileLockWhence::Start => c::SEEK_SET,
+        FileLockWhence::Current => c::SEEK_CUR,
+        FileLockWhence::End => c::SEEK_END,
+    } as _;
+    raw.l_start = lock.start.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+    raw.l_len = lock.len.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+    Ok(raw)
+}
+
 #[cfg(any(
     target_os = "android",
     target_os = "freebsd",
@@ -877,7 +1111,7 @@ pub(crate) fn fcntl_setfl(fd: BorrowedFd<'_>, flags: OFlags) -> io::Result<()> {
 pub(crate) fn fcntl_get_seals(fd: BorrowedFd<'_>) -> io::Result<SealFlags> {
     unsafe {
         ret_c_int(c::fcntl(borrowed_fd(fd), c::F_GET_SEALS))
-            .map(|flags| SealFlags::from_bits_retain(flags))
+            .map(|flags| SealFlags::from_bits_unchecked(flags))
     }
 }
 
@@ -910,6 +1144,46 @@ pub(crate) fn tell(fd: BorrowedFd<'_>) -> io::Result<u64> {
     Ok(offset as u64)
 }
 
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+pub(crate) fn seek_data(fd: BorrowedFd<'_>, offset: u64) -> io::Result<u64> {
+    // Silently cast; we'll get `EINVAL` if the value is negative.
+    let offset = unsafe {
+        ret_off_t(libc_lseek(
+            borrowed_fd(fd),
+            offset as libc_off_t,
+            c::SEEK_DATA,
+        ))?
+    };
+    Ok(offset as u64)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+pub(crate) fn seek_hole(fd: BorrowedFd<'_>, offset: u64) -> io::Result<u64> {
+    // Silently cast; we'll get `EINVAL` if the value is negative.
+    let offset = unsafe {
+        ret_off_t(libc_lseek(
+            borrowed_fd(fd),
+            offset as libc_off_t,
+            c::SEEK_HOLE,
+        ))?
+    };
+    Ok(offset as u64)
+}
+
 #[cfg(not(any(target_os = "android", target_os = "linux", target_os = "wasi")))]
 pub(crate) fn fchmod(fd: BorrowedFd<'_>, mode: Mode) -> io::Result<()> {
     unsafe { ret(c::fchmod(borrowed_fd(fd), mode.bits())) }
@@ -1044,7 +1318,7 @@ fn libc_statvfs_to_statvfs(from: libc_statvfs) -> StatVfs {
         f_ffree: from.f_ffree as u64,
         f_favail: from.f_ffree as u64,
         f_fsid: from.f_fsid as u64,
-        f_flag: unsafe { StatVfsMountFlags::from_bits_retain(from.f_flag as u64) },
+        f_flag: unsafe { StatVfsMountFlags::from_bits_unchecked(from.f_flag as u64) },
         f_namemax: from.f_namemax as u64,
     }
 }
@@ -1234,6 +1508,66 @@ pub(crate) fn fdatasync(fd: BorrowedFd<'_>) -> io::Result<()> {
     unsafe { ret(c::fdatasync(borrowed_fd(fd))) }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn syncfs(fd: BorrowedFd<'_>) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    use c::syncfs;
+    #[cfg(target_os = "android")]
+    weak_or_syscall! {
+        fn syncfs(fd: c::c_int) via SYS_syncfs -> c::c_int
+    }
+
+    unsafe { ret(syncfs(borrowed_fd(fd))) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn sync() {
+    unsafe { c::sync() }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn sync_file_range(
+    fd: BorrowedFd<'_>,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    use c::sync_file_range;
+    // `libc` doesn't declare `sync_file_range` for Android.
+    #[cfg(target_os = "android")]
+    weakcall! {
+        fn sync_file_range(
+            fd: c::c_int,
+            offset: i64,
+            nbytes: i64,
+            flags: c::c_uint
+        ) -> c::c_int
+    }
+
+    unsafe {
+        ret(sync_file_range(
+            borrowed_fd(fd),
+            offset as i64,
+            nbytes as i64,
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn readahead(fd: BorrowedFd<'_>, offset: u64, count: usize) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    use c::readahead;
+    // `libc` doesn't declare `readahead` for Android.
+    #[cfg(target_os = "android")]
+    weakcall! {
+        fn readahead(fd: c::c_int, offset: i64, count: usize) -> isize
+    }
+
+    unsafe { ret_ssize_t(readahead(borrowed_fd(fd), offset as i64, count)).map(drop) }
+}
+
 pub(crate) fn ftruncate(fd: BorrowedFd<'_>, length: u64) -> io::Result<()> {
     let length = length.try_into().map_err(|_overflow_err| io::Errno::FBIG)?;
     unsafe { ret(libc_ftruncate(borrowed_fd(fd), length)) }
@@ -1260,6 +1594,19 @@ pub(crate) fn memfd_create(path: &CStr, flags: MemfdFlags) -> io::Result<OwnedFd
     unsafe { ret_owned_fd(memfd_create(c_str(path), flags.bits())) }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn memfd_secret(flags: MemfdSecretFlags) -> io::Result<OwnedFd> {
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+    unsafe {
+        syscall_ret_owned_fd(c::syscall(c::SYS_memfd_secret, flags.bits() as c::c_uint))
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")))]
+    {
+        let _ = flags;
+        Err(io::Errno::NOSYS)
+    }
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub(crate) fn openat2(
     dirfd: BorrowedFd<'_>,
@@ -1812,3 +2159,83 @@ pub(crate) fn mount(
         ))
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_ficlone(fd: BorrowedFd<'_>, src_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe { ret(c::ioctl(borrowed_fd(fd), FICLONE as _, borrowed_fd(src_fd))) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_ficlonerange(fd: BorrowedFd<'_>, range: &file_clone_range) -> io::Result<()> {
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FICLONERANGE as _,
+            range as *const file_clone_range,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_fideduperange(
+    fd: BorrowedFd<'_>,
+    range: *mut file_dedupe_range,
+) -> io::Result<()> {
+    ret(c::ioctl(borrowed_fd(fd), FIDEDUPERANGE as _, range))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_getflags(fd: BorrowedFd<'_>) -> io::Result<u32> {
+    // `FS_IOC_GETFLAGS` is declared with a `long` argument, but the kernel
+    // reads and writes an `int`.
+    let mut flags = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_GETFLAGS as _,
+            flags.as_mut_ptr(),
+        ))?;
+        Ok(flags.assume_init())
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_setflags(fd: BorrowedFd<'_>, flags: u32) -> io::Result<()> {
+    let flags: c::c_uint = flags;
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_SETFLAGS as _,
+            &flags as *const c::c_uint,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_fsgetxattr(fd: BorrowedFd<'_>) -> io::Result<fsxattr> {
+    let mut attr = MaybeUninit::<fsxattr>::uninit();
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_FSGETXATTR as _,
+            attr.as_mut_ptr(),
+        ))?;
+        Ok(attr.assume_init())
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_fssetxattr(fd: BorrowedFd<'_>, attr: &fsxattr) -> io::Result<()> {
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_FSSETXATTR as _,
+            attr as *const fsxattr,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_fiemap(fd: BorrowedFd<'_>, fiemap: *mut c::c_void) -> io::Result<()> {
+    ret(c::ioctl(borrowed_fd(fd), FS_IOC_FIEMAP as _, fiemap))
+}
@@ -3,7 +3,11 @@
 use super::super::c;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 use super::super::conv::syscall_ret_owned_fd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use super::super::conv::syscall_ret_ssize_t;
 use super::super::conv::{borrowed_fd, no_fd, ret};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use super::super::conv::{syscall_ret, syscall_ret_u32};
 use super::super::offset::libc_mmap;
 #[cfg(not(target_os = "redox"))]
 use super::types::Advice;
@@ -11,11 +15,51 @@ use super::types::Advice;
 use super::types::MremapFlags;
 use super::types::{MapFlags, MprotectFlags, MsyncFlags, ProtFlags};
 #[cfg(any(target_os = "android", target_os = "linux"))]
-use super::types::{MlockFlags, UserfaultfdFlags};
+use super::types::{MlockFlags, MlockallFlags, UserfaultfdFlags};
 use crate::fd::BorrowedFd;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 use crate::fd::OwnedFd;
 use crate::io;
+#[cfg(target_os = "linux")]
+use crate::io::IoSlice;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::process::Pid;
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    not(all(
+        target_env = "gnu",
+        any(
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "s390x",
+            target_arch = "sparc",
+            target_arch = "sparc64",
+        )
+    ))
+))]
+use c::{SYS_pkey_alloc, SYS_pkey_free, SYS_pkey_mprotect};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use linux_raw_sys::{
+    general::{
+        uffdio_api, uffdio_continue, uffdio_copy, uffdio_range, uffdio_register,
+        uffdio_writeprotect, uffdio_zeropage,
+    },
+    ioctl::{
+        UFFDIO_API, UFFDIO_CONTINUE, UFFDIO_COPY, UFFDIO_REGISTER, UFFDIO_UNREGISTER, UFFDIO_WAKE,
+        UFFDIO_WRITEPROTECT, UFFDIO_ZEROPAGE,
+    },
+};
+#[cfg(all(
+    target_env = "gnu",
+    any(
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "s390x",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    )
+))]
+use pkey_nr::{SYS_pkey_alloc, SYS_pkey_free, SYS_pkey_mprotect};
 
 #[cfg(not(target_os = "redox"))]
 pub(crate) fn madvise(addr: *mut c::c_void, len: usize, advice: Advice) -> io::Result<()> {
@@ -212,7 +256,321 @@ pub(crate) unsafe fn munlock(addr: *mut c::c_void, length: usize) -> io::Result<
     ret(c::munlock(addr, length))
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn mlockall(flags: MlockallFlags) -> io::Result<()> {
+    unsafe { ret(c::mlockall(flags.bits())) }
+}
+
+/// # Safety
+///
+/// `munlockall` unlocks memory locked by unrelated code in the process.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn munlockall() -> io::Result<()> {
+    ret(c::munlockall())
+}
+
+/// # Safety
+///
+/// `vec` must have room for one byte for each page in `addr..addr + len`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn mincore(addr: *mut c::c_void, len: usize, vec: *mut u8) -> io::Result<()> {
+    ret(c::mincore(addr, len, vec.cast()))
+}
+
+/// # Safety
+///
+/// If `pidfd` refers to the current process, `process_madvise` has the same
+/// hazards as `madvise`.
+#[cfg(target_os = "linux")]
+pub(crate) unsafe fn process_madvise(
+    pidfd: BorrowedFd<'_>,
+    ranges: &[IoSlice<'_>],
+    advice: Advice,
+) -> io::Result<usize> {
+    // `Advice::LinuxDontNeed` doesn't have its real value; see `madvise`.
+    let advice = if let Advice::LinuxDontNeed = advice {
+        c::MADV_DONTNEED
+    } else {
+        advice as c::c_int
+    };
+    syscall_ret_ssize_t(c::syscall(
+        c::SYS_process_madvise,
+        borrowed_fd(pidfd),
+        ranges.as_ptr().cast::<c::iovec>(),
+        ranges.len(),
+        advice,
+        0,
+    ))
+    .map(|nread| nread as usize)
+}
+
+// The libc bindings don't have the `pkey_*` syscall numbers for these
+// targets, so take them from linux-raw-sys.
+#[cfg(all(
+    target_env = "gnu",
+    any(
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "s390x",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    )
+))]
+#[allow(non_upper_case_globals)]
+mod pkey_nr {
+    use super::c;
+    use linux_raw_sys::general::{__NR_pkey_alloc, __NR_pkey_free, __NR_pkey_mprotect};
+
+    pub(super) const SYS_pkey_alloc: c::c_long = __NR_pkey_alloc as c::c_long;
+    pub(super) const SYS_pkey_free: c::c_long = __NR_pkey_free as c::c_long;
+    pub(super) const SYS_pkey_mprotect: c::c_long = __NR_pkey_mprotect as c::c_long;
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn pkey_alloc(access_rights: u32) -> io::Result<u32> {
+    unsafe { syscall_ret_u32(c::syscall(SYS_pkey_alloc, 0, access_rights as c::c_uint)) }
+}
+
+/// # Safety
+///
+/// Memory may still be tagged with `pkey`, and will be governed by its new
+/// access rights if it's allocated again.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn pkey_free(pkey: u32) -> io::Result<()> {
+    syscall_ret(c::syscall(SYS_pkey_free, pkey as c::c_int))
+}
+
+/// # Safety
+///
+/// `pkey_mprotect` operates on raw pointers and may round out to the nearest
+/// page boundaries.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn pkey_mprotect(
+    ptr: *mut c::c_void,
+    len: usize,
+    flags: MprotectFlags,
+    pkey: u32,
+) -> io::Result<()> {
+    syscall_ret(c::syscall(
+        SYS_pkey_mprotect,
+        ptr,
+        len,
+        flags.bits(),
+        pkey as c::c_int,
+    ))
+}
+
+/// The `maxnode` argument for a node mask, which Linux expects to be one
+/// more than the number of bits in it.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+fn maxnode(nodemask: &[usize]) -> usize {
+    nodemask.len() * usize::MAX.count_ones() as usize + 1
+}
+
+/// # Safety
+///
+/// `mbind` operates on raw pointers and may round out to the nearest page
+/// boundaries.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn mbind(
+    addr: *mut c::c_void,
+    len: usize,
+    mode: u32,
+    nodemask: &[usize],
+    flags: u32,
+) -> io::Result<()> {
+    syscall_ret(c::syscall(
+        c::SYS_mbind,
+        addr,
+        len,
+        mode as c::c_ulong,
+        nodemask.as_ptr(),
+        maxnode(nodemask),
+        flags as c::c_uint,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_mempolicy(mode: u32, nodemask: &[usize]) -> io::Result<()> {
+    unsafe {
+        syscall_ret(c::syscall(
+            c::SYS_set_mempolicy,
+            mode as c::c_int,
+            nodemask.as_ptr(),
+            maxnode(nodemask),
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_mempolicy(
+    mode: &mut c::c_int,
+    nodemask: &mut [usize],
+    addr: *mut c::c_void,
+    flags: u32,
+) -> io::Result<()> {
+    let maxnode = maxnode(nodemask);
+    unsafe {
+        syscall_ret(c::syscall(
+            c::SYS_get_mempolicy,
+            mode as *mut c::c_int,
+            nodemask.as_mut_ptr(),
+            maxnode,
+            addr,
+            flags as c::c_ulong,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn migrate_pages(
+    pid: Option<Pid>,
+    old_nodes: &[usize],
+    new_nodes: &[usize],
+) -> io::Result<usize> {
+    #[cfg(not(target_arch = "arm"))]
+    use c::SYS_migrate_pages;
+    // The libc bindings don't have `SYS_migrate_pages` for arm, so take it
+    // from linux-raw-sys there.
+    #[cfg(target_arch = "arm")]
+    #[allow(non_upper_case_globals)]
+    const SYS_migrate_pages: c::c_long = linux_raw_sys::general::__NR_migrate_pages as c::c_long;
+
+    debug_assert_eq!(old_nodes.len(), new_nodes.len());
+    unsafe {
+        syscall_ret_ssize_t(c::syscall(
+            SYS_migrate_pages,
+            Pid::as_raw(pid) as c::c_int,
+            maxnode(old_nodes),
+            old_nodes.as_ptr(),
+            new_nodes.as_ptr(),
+        ))
+        .map(|n| n as usize)
+    }
+}
+
+/// # Safety
+///
+/// `pages` and `status` must be valid for `count` elements, and `nodes` must
+/// be null or valid for `count` elements.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn move_pages(
+    pid: Option<Pid>,
+    count: usize,
+    pages: *const *mut c::c_void,
+    nodes: *const c::c_int,
+    status: *mut c::c_int,
+    flags: u32,
+) -> io::Result<usize> {
+    syscall_ret_ssize_t(c::syscall(
+        c::SYS_move_pages,
+        Pid::as_raw(pid) as c::c_int,
+        count,
+        pages,
+        nodes,
+        status,
+        flags as c::c_int,
+    ))
+    .map(|n| n as usize)
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub(crate) unsafe fn userfaultfd(flags: UserfaultfdFlags) -> io::Result<OwnedFd> {
     syscall_ret_owned_fd(c::syscall(c::SYS_userfaultfd, flags.bits()))
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn ioctl_uffdio_api(fd: BorrowedFd<'_>, api: &mut uffdio_api) -> io::Result<()> {
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            UFFDIO_API as _,
+            api as *mut uffdio_api,
+        ))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_register(
+    fd: BorrowedFd<'_>,
+    register: &mut uffdio_register,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_REGISTER as _,
+        register as *mut uffdio_register,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_unregister(
+    fd: BorrowedFd<'_>,
+    range: &uffdio_range,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_UNREGISTER as _,
+        range as *const uffdio_range,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_wake(fd: BorrowedFd<'_>, range: &uffdio_range) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_WAKE as _,
+        range as *const uffdio_range,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_copy(
+    fd: BorrowedFd<'_>,
+    copy: &mut uffdio_copy,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_COPY as _,
+        copy as *mut uffdio_copy,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_zeropage(
+    fd: BorrowedFd<'_>,
+    zeropage: &mut uffdio_zeropage,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_ZEROPAGE as _,
+        zeropage as *mut uffdio_zeropage,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_writeprotect(
+    fd: BorrowedFd<'_>,
+    writeprotect: &uffdio_writeprotect,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_WRITEPROTECT as _,
+        writeprotect as *const uffdio_writeprotect,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) unsafe fn ioctl_uffdio_continue(
+    fd: BorrowedFd<'_>,
+    cont: &mut uffdio_continue,
+) -> io::Result<()> {
+    ret(c::ioctl(
+        borrowed_fd(fd),
+        UFFDIO_CONTINUE as _,
+        cont as *mut uffdio_continue,
+    ))
+}
@@ -284,6 +284,22 @@ bitflags! {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+bitflags! {
+    /// `MCL_*` flags for use with [`mlockall`].
+    ///
+    /// [`mlockall`]: crate::mm::mlockall
+    pub struct MlockallFlags: i32 {
+        /// `MCL_CURRENT`—Lock all pages which are currently mapped.
+        const CURRENT = c::MCL_CURRENT;
+        /// `MCL_FUTURE`—Lock all pages which become mapped in the future.
+        const FUTURE = c::MCL_FUTURE;
+        /// `MCL_ONFAULT` (since Linux 4.4)—Lock pages when they're faulted
+        /// in, rather than populating them immediately.
+        const ONFAULT = c::MCL_ONFAULT;
+    }
+}
+
 /// `POSIX_MADV_*` constants for use with [`madvise`].
 ///
 /// [`madvise`]: crate::mm::madvise
@@ -70,6 +70,7 @@ pub(crate) mod mm;
 pub(crate) mod net;
 #[cfg(not(windows))]
 #[cfg(any(
+    feature = "mm",
     feature = "param",
     feature = "runtime",
     feature = "time",
@@ -15,6 +15,8 @@ use super::super::conv::{syscall_ret, syscall_ret_u32};
     target_os = "linux",
 ))]
 use super::types::RawCpuSet;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use super::types::RawSysinfo;
 #[cfg(not(any(target_os = "wasi", target_os = "fuchsia")))]
 use crate::fd::BorrowedFd;
 use crate::ffi::CStr;
@@ -62,7 +64,7 @@ pub(crate) fn membarrier_query() -> MembarrierQuery {
     const MEMBARRIER_CMD_QUERY: u32 = 0;
     unsafe {
         match syscall_ret_u32(c::syscall(c::SYS_membarrier, MEMBARRIER_CMD_QUERY, 0)) {
-            Ok(query) => MembarrierQuery::from_bits_retain(query),
+            Ok(query) => MembarrierQuery::from_bits_unchecked(query),
             Err(_) => MembarrierQuery::empty(),
         }
     }
@@ -219,6 +221,41 @@ pub(crate) fn uname() -> RawUname {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn sysinfo() -> RawSysinfo {
+    let mut info = MaybeUninit::<RawSysinfo>::uninit();
+    unsafe {
+        ret_infallible(c::sysinfo(info.as_mut_ptr()));
+        info.assume_init()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn sethostname(name: &[u8]) -> io::Result<()> {
+    unsafe { ret(c::sethostname(name.as_ptr().cast(), name.len())) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn setdomainname(name: &[u8]) -> io::Result<()> {
+    syscall! {
+        fn setdomainname(
+            name: *const c::c_char,
+            len: c::size_t
+        ) via SYS_setdomainname -> c::c_int
+    }
+
+    unsafe { ret(setdomainname(name.as_ptr().cast(), name.len())) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn personality(persona: u32) -> io::Result<u32> {
+    unsafe { ret_c_int(c::personality(persona as _)).map(|prev| prev as u32) }
+}
+
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
 #[inline]
 pub(crate) fn nice(inc: i32) -> io::Result<i32> {
@@ -408,6 +445,83 @@ pub(crate) fn exit_group(code: c::c_int) -> ! {
     }
 }
 
+#[cfg(not(target_os = "wasi"))]
+#[inline]
+pub(crate) fn setuid(uid: Uid) -> io::Result<()> {
+    unsafe { ret(c::setuid(uid.as_raw())) }
+}
+
+#[cfg(not(target_os = "wasi"))]
+#[inline]
+pub(crate) fn setgid(gid: Gid) -> io::Result<()> {
+    unsafe { ret(c::setgid(gid.as_raw())) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn setresuid(ruid: u32, euid: u32, suid: u32) -> io::Result<()> {
+    unsafe { ret(c::setresuid(ruid, euid, suid)) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn setresgid(rgid: u32, egid: u32, sgid: u32) -> io::Result<()> {
+    unsafe { ret(c::setresgid(rgid, egid, sgid)) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn getresuid() -> (Uid, Uid, Uid) {
+    let mut ruid = MaybeUninit::<c::uid_t>::uninit();
+    let mut euid = MaybeUninit::<c::uid_t>::uninit();
+    let mut suid = MaybeUninit::<c::uid_t>::uninit();
+    unsafe {
+        ret_infallible(c::getresuid(
+            ruid.as_mut_ptr(),
+            euid.as_mut_ptr(),
+            suid.as_mut_ptr(),
+        ));
+        (
+            Uid::from_raw(ruid.assume_init()),
+            Uid::from_raw(euid.assume_init()),
+            Uid::from_raw(suid.assume_init()),
+        )
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn getresgid() -> (Gid, Gid, Gid) {
+    let mut rgid = MaybeUninit::<c::gid_t>::uninit();
+    let mut egid = MaybeUninit::<c::gid_t>::uninit();
+    let mut sgid = MaybeUninit::<c::gid_t>::uninit();
+    unsafe {
+        ret_infallible(c::getresgid(
+            rgid.as_mut_ptr(),
+            egid.as_mut_ptr(),
+            sgid.as_mut_ptr(),
+        ));
+        (
+            Gid::from_raw(rgid.assume_init()),
+            Gid::from_raw(egid.assume_init()),
+            Gid::from_raw(sgid.assume_init()),
+        )
+    }
+}
+
+#[cfg(not(target_os = "wasi"))]
+#[inline]
+pub(crate) fn getgroups(buf: &mut [Gid]) -> io::Result<usize> {
+    let len = buf.len().min(c::c_int::MAX as usize) as c::c_int;
+    unsafe { ret_c_int(c::getgroups(len, buf.as_mut_ptr().cast())).map(|n| n as usize) }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn setgroups(groups: &[Gid]) -> io::Result<()> {
+    unsafe { ret(c::setgroups(groups.len() as _, groups.as_ptr().cast())) }
+}
+
 #[cfg(not(target_os = "wasi"))]
 #[inline]
 pub(crate) fn setsid() -> io::Result<Pid> {
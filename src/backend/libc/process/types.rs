@@ -387,6 +387,9 @@ pub type RawId = c::id_t;
 #[cfg(not(target_os = "wasi"))]
 pub(crate) type RawUname = c::utsname;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) type RawSysinfo = c::sysinfo;
+
 #[cfg(any(
     target_os = "android",
     target_os = "dragonfly",
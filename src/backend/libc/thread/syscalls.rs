@@ -3,7 +3,7 @@
 use super::super::c;
 use super::super::conv::ret;
 #[cfg(any(target_os = "android", target_os = "linux"))]
-use super::super::conv::{borrowed_fd, ret_c_int};
+use super::super::conv::{borrowed_fd, ret_c_int, syscall_ret};
 use super::super::time::types::LibcTimespec;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 use crate::fd::BorrowedFd;
@@ -305,3 +305,76 @@ pub(crate) fn setns(fd: BorrowedFd, nstype: c::c_int) -> io::Result<c::c_int> {
 pub(crate) fn unshare(flags: crate::thread::UnshareFlags) -> io::Result<()> {
     unsafe { ret(c::unshare(flags.bits() as i32)) }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn capget(
+    header: *mut crate::thread::CapUserHeader,
+    data: *mut crate::thread::CapUserData,
+) -> io::Result<()> {
+    syscall! {
+        fn capget(
+            hdrp: *mut crate::thread::CapUserHeader,
+            data: *mut crate::thread::CapUserData
+        ) via SYS_capget -> c::c_int
+    }
+
+    ret(capget(header, data))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn capset(
+    header: *mut crate::thread::CapUserHeader,
+    data: *const crate::thread::CapUserData,
+) -> io::Result<()> {
+    syscall! {
+        fn capset(
+            hdrp: *mut crate::thread::CapUserHeader,
+            data: *const crate::thread::CapUserData
+        ) via SYS_capset -> c::c_int
+    }
+
+    ret(capset(header, data))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn rseq(rseq: &crate::thread::Rseq, flags: u32, sig: u32) -> io::Result<()> {
+    #[cfg(any(
+        target_env = "gnu",
+        all(
+            target_env = "musl",
+            target_pointer_width = "64",
+            not(target_arch = "mips64")
+        )
+    ))]
+    use c::SYS_rseq;
+    // The libc bindings don't have `SYS_rseq` for Android or most 32-bit
+    // musl targets, so take it from linux-raw-sys there.
+    #[cfg(not(any(
+        target_env = "gnu",
+        all(
+            target_env = "musl",
+            target_pointer_width = "64",
+            not(target_arch = "mips64")
+        )
+    )))]
+    #[allow(non_upper_case_globals)]
+    const SYS_rseq: c::c_long = linux_raw_sys::general::__NR_rseq as c::c_long;
+
+    syscall_ret(c::syscall(
+        SYS_rseq,
+        rseq as *const crate::thread::Rseq,
+        core::mem::size_of::<crate::thread::Rseq>() as c::c_uint,
+        flags as c::c_int,
+        sig,
+    ))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) fn sched_getcpu() -> io::Result<usize> {
+    let cpu = ret_c_int(unsafe { c::sched_getcpu() })?;
+    Ok(cpu as usize)
+}
@@ -266,3 +266,41 @@ pub(in crate::backend) unsafe fn syscall6_readonly(
     );
     FromAsm::from_asm(r0)
 }
+
+/// `clone(flags, stack, 0, 0, 0)`, calling `entry(arg)` on `stack` in the
+/// child.
+///
+/// The child never returns from this function; `entry` must not return.
+#[cfg(feature = "runtime")]
+#[inline]
+pub(in crate::backend) unsafe fn clone_with_entry(
+    nr: SyscallNumber<'_>,
+    flags: ArgReg<'_, A0>,
+    stack: ArgReg<'_, A1>,
+    entry: unsafe extern "C" fn(*mut core::ffi::c_void) -> !,
+    arg: *mut core::ffi::c_void,
+) -> RetReg<R0> {
+    let r0;
+    asm!(
+        "svc 0",
+        "cbnz x0, 2f",
+        // In the child, on the new stack. Clear the frame pointer and link
+        // register to mark the outermost frame, and call `entry(arg)`.
+        "mov x29, xzr",
+        "mov x30, xzr",
+        "mov x0, x5",
+        "blr x6",
+        "brk #0x1",
+        "2:",
+        in("x8") nr.to_asm(),
+        inlateout("x0") flags.to_asm() => r0,
+        in("x1") stack.to_asm(),
+        in("x2") 0_usize,
+        in("x3") 0_usize,
+        in("x4") 0_usize,
+        in("x5") arg,
+        in("x6") entry,
+        options(nostack)
+    );
+    FromAsm::from_asm(r0)
+}
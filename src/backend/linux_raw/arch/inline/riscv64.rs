@@ -263,3 +263,41 @@ pub(in crate::backend) unsafe fn syscall6_readonly(
     );
     FromAsm::from_asm(r0)
 }
+
+/// `clone(flags, stack, 0, 0, 0)`, calling `entry(arg)` on `stack` in the
+/// child.
+///
+/// The child never returns from this function; `entry` must not return.
+#[cfg(feature = "runtime")]
+#[inline]
+pub(in crate::backend) unsafe fn clone_with_entry(
+    nr: SyscallNumber<'_>,
+    flags: ArgReg<'_, A0>,
+    stack: ArgReg<'_, A1>,
+    entry: unsafe extern "C" fn(*mut core::ffi::c_void) -> !,
+    arg: *mut core::ffi::c_void,
+) -> RetReg<R0> {
+    let r0;
+    asm!(
+        "ecall",
+        "bnez a0, 2f",
+        // In the child, on the new stack. Clear the frame pointer and return
+        // address to mark the outermost frame, and call `entry(arg)`.
+        "mv s0, zero",
+        "mv ra, zero",
+        "mv a0, a5",
+        "jalr a6",
+        "unimp",
+        "2:",
+        in("a7") nr.to_asm(),
+        inlateout("a0") flags.to_asm() => r0,
+        in("a1") stack.to_asm(),
+        in("a2") 0_usize,
+        in("a3") 0_usize,
+        in("a4") 0_usize,
+        in("a5") arg,
+        in("a6") entry,
+        options(nostack)
+    );
+    FromAsm::from_asm(r0)
+}
@@ -291,3 +291,81 @@ pub(in crate::backend) unsafe fn syscall6_readonly(
     );
     FromAsm::from_asm(r0)
 }
+
+/// `clone(flags, stack, 0, 0, 0)`, calling `entry(arg)` on `stack` in the
+/// child.
+///
+/// The child never returns from this function; `entry` must not return.
+#[cfg(feature = "runtime")]
+#[inline]
+pub(in crate::backend) unsafe fn clone_with_entry(
+    nr: SyscallNumber<'_>,
+    flags: ArgReg<'_, A0>,
+    stack: ArgReg<'_, A1>,
+    entry: unsafe extern "C" fn(*mut core::ffi::c_void) -> !,
+    arg: *mut core::ffi::c_void,
+) -> RetReg<R0> {
+    let r0;
+    asm!(
+        "syscall",
+        "test rax, rax",
+        "jnz 2f",
+        // In the child, on the new stack. Clear the frame pointer to mark
+        // the outermost frame, and call `entry(arg)`.
+        "xor ebp, ebp",
+        "mov rdi, r9",
+        "call r12",
+        "ud2",
+        "2:",
+        inlateout("rax") nr.to_asm() => r0,
+        in("rdi") flags.to_asm(),
+        in("rsi") stack.to_asm(),
+        in("rdx") 0_usize,
+        in("r10") 0_usize,
+        in("r8") 0_usize,
+        in("r9") arg,
+        in("r12") entry,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack)
+    );
+    FromAsm::from_asm(r0)
+}
+
+/// `rdpkru`—Reads the PKRU register, which holds the access rights for each
+/// memory protection key.
+///
+/// # Safety
+///
+/// The CPU must support protection keys, and the OS must have enabled them,
+/// or this raises `SIGILL`.
+#[inline]
+pub(in crate::backend) unsafe fn rdpkru() -> u32 {
+    let pkru;
+    asm!(
+        "rdpkru",
+        in("ecx") 0,
+        lateout("eax") pkru,
+        lateout("edx") _,
+        options(nomem, nostack, preserves_flags)
+    );
+    pkru
+}
+
+/// `wrpkru`—Writes the PKRU register.
+///
+/// # Safety
+///
+/// The CPU must support protection keys, and the OS must have enabled them,
+/// or this raises `SIGILL`. Changing access rights may make memory that
+/// other code relies on inaccessible.
+#[inline]
+pub(in crate::backend) unsafe fn wrpkru(pkru: u32) {
+    asm!(
+        "wrpkru",
+        in("eax") pkru,
+        in("ecx") 0,
+        in("edx") 0,
+        options(nostack, preserves_flags)
+    );
+}
@@ -411,6 +411,14 @@ impl<'a, Num: ArgNumber> From<crate::backend::mm::types::MlockFlags> for ArgReg<
     }
 }
 
+#[cfg(feature = "mm")]
+impl<'a, Num: ArgNumber> From<crate::backend::mm::types::MlockallFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::backend::mm::types::MlockallFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
 #[cfg(feature = "mm")]
 impl<'a, Num: ArgNumber> From<crate::backend::mm::types::MapFlags> for ArgReg<'a, Num> {
     #[inline]
@@ -550,6 +558,14 @@ impl<'a, Num: ArgNumber> From<crate::fs::SealFlags> for ArgReg<'a, Num> {
     }
 }
 
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::SyncFileRangeFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::SyncFileRangeFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
 #[cfg(feature = "io_uring")]
 impl<'a, Num: ArgNumber> From<crate::io_uring::IoringEnterFlags> for ArgReg<'a, Num> {
     #[inline]
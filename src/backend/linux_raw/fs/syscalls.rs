@@ -9,8 +9,8 @@
 
 use super::super::c;
 use super::super::conv::{
-    by_ref, c_int, c_uint, dev_t, oflags_for_open_how, opt_mut, pass_usize, raw_fd, ret, ret_c_int,
-    ret_c_uint, ret_owned_fd, ret_usize, size_of, slice_mut, zero,
+    by_mut, by_ref, c_int, c_uint, dev_t, oflags_for_open_how, opt_mut, pass_usize, raw_fd, ret,
+    ret_c_int, ret_c_uint, ret_infallible, ret_owned_fd, ret_usize, size_of, slice_mut, zero,
 };
 #[cfg(target_pointer_width = "64")]
 use super::super::conv::{loff_t, loff_t_from_u64, ret_u64};
@@ -24,20 +24,35 @@ use crate::fd::AsFd;
 use crate::fd::{BorrowedFd, OwnedFd};
 use crate::ffi::CStr;
 use crate::fs::{
-    Access, Advice, AtFlags, FallocateFlags, FileType, FlockOperation, MemfdFlags, Mode, OFlags,
-    RenameFlags, ResolveFlags, SealFlags, Stat, StatFs, StatVfs, StatVfsMountFlags, StatxFlags,
-    Timestamps,
+    Access, Advice, AtFlags, DnotifyFlags, FallocateFlags, FileLock, FileLockConflict,
+    FileLockKind, FileLockWhence, FileOwner, FileType, FlockOperation, LeaseType, MemfdFlags,
+    MemfdSecretFlags, Mode, OFlags, RenameFlags, ResolveFlags, SealFlags, Stat, StatFs, StatVfs,
+    StatVfsMountFlags, Statx, StatxFlags, SyncFileRangeFlags, Timestamps,
 };
 use crate::io::{self, SeekFrom};
-use crate::process::{Gid, Uid};
+use crate::process::{Gid, Pid, RawPid, Uid};
 use core::convert::TryInto;
 use core::mem::MaybeUninit;
 #[cfg(target_arch = "mips64")]
 use linux_raw_sys::general::stat as linux_stat64;
 use linux_raw_sys::general::{
-    __kernel_fsid_t, __kernel_timespec, open_how, statx, AT_EACCESS, AT_FDCWD, AT_REMOVEDIR,
-    AT_SYMLINK_NOFOLLOW, F_ADD_SEALS, F_GETFL, F_GETLEASE, F_GETOWN, F_GETPIPE_SZ, F_GETSIG,
-    F_GET_SEALS, F_SETFL, F_SETPIPE_SZ, SEEK_CUR, SEEK_END, SEEK_SET, STATX__RESERVED,
+    __kernel_fsid_t, __kernel_timespec, f_owner_ex, file_clone_range, file_dedupe_range, fsxattr,
+    open_how, AT_EACCESS, AT_FDCWD, AT_REMOVEDIR, AT_SYMLINK_NOFOLLOW, F_ADD_SEALS, F_GETFL,
+    F_GETLEASE, F_GETOWN_EX, F_GETSIG, F_GET_SEALS, F_NOTIFY, F_OFD_GETLK, F_OFD_SETLK,
+    F_OFD_SETLKW, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID, F_RDLCK, F_SETFL, F_SETLEASE,
+    F_SETOWN_EX, F_SETSIG, F_UNLCK, F_WRLCK, SEEK_CUR, SEEK_DATA, SEEK_END, SEEK_HOLE, SEEK_SET,
+    STATX__RESERVED,
+};
+#[cfg(target_pointer_width = "64")]
+use linux_raw_sys::general::{flock, F_GETLK, F_SETLK, F_SETLKW};
+// On 32-bit platforms, use the `fcntl64` forms, which take 64-bit offsets.
+#[cfg(target_pointer_width = "32")]
+use linux_raw_sys::general::{
+    flock64 as flock, F_GETLK64 as F_GETLK, F_SETLK64 as F_SETLK, F_SETLKW64 as F_SETLKW,
+};
+use linux_raw_sys::ioctl::{
+    FICLONE, FICLONERANGE, FIDEDUPERANGE, FS_IOC_FIEMAP, FS_IOC_FSGETXATTR, FS_IOC_FSSETXATTR,
+    FS_IOC_GETFLAGS, FS_IOC_SETFLAGS,
 };
 #[cfg(target_pointer_width = "32")]
 use {
@@ -251,6 +266,18 @@ pub(crate) fn tell(fd: BorrowedFd<'_>) -> io::Result<u64> {
     _seek(fd, 0, SEEK_CUR).map(|x| x as u64)
 }
 
+#[inline]
+pub(crate) fn seek_data(fd: BorrowedFd<'_>, offset: u64) -> io::Result<u64> {
+    // Silently cast; we'll get `EINVAL` if the value is negative.
+    _seek(fd, offset as i64, SEEK_DATA)
+}
+
+#[inline]
+pub(crate) fn seek_hole(fd: BorrowedFd<'_>, offset: u64) -> io::Result<u64> {
+    // Silently cast; we'll get `EINVAL` if the value is negative.
+    _seek(fd, offset as i64, SEEK_HOLE)
+}
+
 #[inline]
 pub(crate) fn ftruncate(fd: BorrowedFd<'_>, length: u64) -> io::Result<()> {
     // <https://github.com/torvalds/linux/blob/fcadab740480e0e0e9fa9bd272acd409884d431a/arch/arm64/kernel/sys32.c#L81-L83>
@@ -402,6 +429,129 @@ pub(crate) fn fdatasync(fd: BorrowedFd<'_>) -> io::Result<()> {
     unsafe { ret(syscall_readonly!(__NR_fdatasync, fd)) }
 }
 
+#[inline]
+pub(crate) fn syncfs(fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe { ret(syscall_readonly!(__NR_syncfs, fd)) }
+}
+
+#[inline]
+pub(crate) fn sync() {
+    unsafe { ret_infallible(syscall_readonly!(__NR_sync)) }
+}
+
+#[inline]
+pub(crate) fn sync_file_range(
+    fd: BorrowedFd<'_>,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    // On ARM, the arguments are reordered so that the offset and nbytes
+    // argument pairs are aligned. And ARM has a custom syscall code for this.
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_arm_sync_file_range,
+            fd,
+            flags,
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes)
+        ))
+    }
+    // On mips, the arguments are not reordered, and padding is inserted
+    // instead to ensure alignment.
+    #[cfg(target_arch = "mips")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            zero(),
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes),
+            flags
+        ))
+    }
+    #[cfg(all(
+        target_pointer_width = "32",
+        not(any(target_arch = "arm", target_arch = "mips")),
+    ))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes),
+            flags
+        ))
+    }
+    // On powerpc64, the arguments are reordered as on ARM, for consistency
+    // with 32-bit powerpc.
+    #[cfg(target_arch = "powerpc64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range2,
+            fd,
+            flags,
+            loff_t_from_u64(offset),
+            loff_t_from_u64(nbytes)
+        ))
+    }
+    #[cfg(all(target_pointer_width = "64", not(target_arch = "powerpc64")))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            loff_t_from_u64(offset),
+            loff_t_from_u64(nbytes),
+            flags
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn readahead(fd: BorrowedFd<'_>, offset: u64, count: usize) -> io::Result<()> {
+    // On ARM and mips, padding is inserted to align the offset argument pair.
+    #[cfg(any(target_arch = "arm", target_arch = "mips"))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            zero(),
+            hi(offset),
+            lo(offset),
+            pass_usize(count)
+        ))
+    }
+    #[cfg(all(
+        target_pointer_width = "32",
+        not(any(target_arch = "arm", target_arch = "mips")),
+    ))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            hi(offset),
+            lo(offset),
+            pass_usize(count)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            loff_t_from_u64(offset),
+            pass_usize(count)
+        ))
+    }
+}
+
 #[inline]
 pub(crate) fn flock(fd: BorrowedFd<'_>, operation: FlockOperation) -> io::Result<()> {
     unsafe { ret(syscall!(__NR_flock, fd, c_uint(operation as c::c_uint))) }
@@ -720,7 +870,7 @@ pub(crate) fn statx(
     pathname: &CStr,
     flags: AtFlags,
     mask: StatxFlags,
-) -> io::Result<statx> {
+) -> io::Result<Statx> {
     // If a future Linux kernel adds more fields to `struct statx` and users
     // passing flags unknown to rustix in `StatxFlags`, we could end up
     // writing outside of the buffer. To prevent this possibility, we mask off
@@ -740,7 +890,7 @@ pub(crate) fn statx(
     let mask = mask & StatxFlags::all();
 
     unsafe {
-        let mut statx_buf = MaybeUninit::<statx>::uninit();
+        let mut statx_buf = MaybeUninit::<Statx>::uninit();
         ret(syscall!(
             __NR_statx,
             dirfd,
@@ -851,7 +1001,7 @@ fn statfs_to_statvfs(statfs: StatFs) -> StatVfs {
         f_ffree: statfs.f_ffree as u64,
         f_favail: statfs.f_ffree as u64,
         f_fsid: f_fsid_val0 as u32 as u64 | ((f_fsid_val1 as u32 as u64) << 32),
-        f_flag: unsafe { StatVfsMountFlags::from_bits_retain(statfs.f_flags as u64) },
+        f_flag: unsafe { StatVfsMountFlags::from_bits_unchecked(statfs.f_flags as u64) },
         f_namemax: statfs.f_namelen as u64,
     }
 }
@@ -911,26 +1061,104 @@ pub(crate) fn fcntl_setfl(fd: BorrowedFd<'_>, flags: OFlags) -> io::Result<()> {
 }
 
 #[inline]
-pub(crate) fn fcntl_getlease(fd: BorrowedFd<'_>) -> io::Result<c::c_int> {
+pub(crate) fn fcntl_getlease(fd: BorrowedFd<'_>) -> io::Result<LeaseType> {
+    #[cfg(target_pointer_width = "32")]
+    let lease = unsafe { ret_c_uint(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETLEASE)))? };
+    #[cfg(target_pointer_width = "64")]
+    let lease = unsafe { ret_c_uint(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETLEASE)))? };
+
+    match lease {
+        F_RDLCK => Ok(LeaseType::Read),
+        F_WRLCK => Ok(LeaseType::Write),
+        F_UNLCK => Ok(LeaseType::Unlock),
+        _ => Err(io::Errno::INVAL),
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_setlease(fd: BorrowedFd<'_>, lease: LeaseType) -> io::Result<()> {
+    let lease = match lease {
+        LeaseType::Read => F_RDLCK,
+        LeaseType::Write => F_WRLCK,
+        LeaseType::Unlock => F_UNLCK,
+    };
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETLEASE),
+            c_uint(lease)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETLEASE),
+            c_uint(lease)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_getown_ex(fd: BorrowedFd<'_>) -> io::Result<Option<FileOwner>> {
+    let mut owner = MaybeUninit::<f_owner_ex>::uninit();
+
     #[cfg(target_pointer_width = "32")]
     unsafe {
-        ret_c_int(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETLEASE)))
+        ret(syscall!(__NR_fcntl64, fd, c_uint(F_GETOWN_EX), &mut owner))?
     }
     #[cfg(target_pointer_width = "64")]
     unsafe {
-        ret_c_int(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETLEASE)))
+        ret(syscall!(__NR_fcntl, fd, c_uint(F_GETOWN_EX), &mut owner))?
+    }
+
+    let owner = unsafe { owner.assume_init() };
+    let pid = match unsafe { Pid::from_raw(owner.pid as RawPid) } {
+        Some(pid) => pid,
+        None => return Ok(None),
+    };
+    match owner.type_ as u32 {
+        F_OWNER_TID => Ok(Some(FileOwner::Thread(pid))),
+        F_OWNER_PID => Ok(Some(FileOwner::Process(pid))),
+        F_OWNER_PGRP => Ok(Some(FileOwner::ProcessGroup(pid))),
+        _ => Err(io::Errno::INVAL),
     }
 }
 
 #[inline]
-pub(crate) fn fcntl_getown(fd: BorrowedFd<'_>) -> io::Result<c::c_int> {
+pub(crate) fn fcntl_setown_ex(fd: BorrowedFd<'_>, owner: Option<FileOwner>) -> io::Result<()> {
+    let (type_, pid) = match owner {
+        None => (F_OWNER_PID, None),
+        Some(FileOwner::Thread(pid)) => (F_OWNER_TID, Some(pid)),
+        Some(FileOwner::Process(pid)) => (F_OWNER_PID, Some(pid)),
+        Some(FileOwner::ProcessGroup(pid)) => (F_OWNER_PGRP, Some(pid)),
+    };
+    let owner = f_owner_ex {
+        type_: type_ as _,
+        pid: Pid::as_raw(pid) as _,
+    };
+
     #[cfg(target_pointer_width = "32")]
     unsafe {
-        ret_c_int(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETOWN)))
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETOWN_EX),
+            by_ref(&owner)
+        ))
     }
     #[cfg(target_pointer_width = "64")]
     unsafe {
-        ret_c_int(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETOWN)))
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETOWN_EX),
+            by_ref(&owner)
+        ))
     }
 }
 
@@ -947,50 +1175,144 @@ pub(crate) fn fcntl_getsig(fd: BorrowedFd<'_>) -> io::Result<c::c_int> {
 }
 
 #[inline]
-pub(crate) fn fcntl_getpipe_sz(fd: BorrowedFd<'_>) -> io::Result<usize> {
+pub(crate) fn fcntl_setsig(fd: BorrowedFd<'_>, sig: c::c_int) -> io::Result<()> {
     #[cfg(target_pointer_width = "32")]
     unsafe {
-        ret_usize(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETPIPE_SZ)))
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETSIG),
+            c_int(sig)
+        ))
     }
     #[cfg(target_pointer_width = "64")]
     unsafe {
-        ret_usize(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETPIPE_SZ)))
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETSIG),
+            c_int(sig)
+        ))
     }
 }
 
 #[inline]
-pub(crate) fn fcntl_setpipe_sz(fd: BorrowedFd<'_>, size: c::c_int) -> io::Result<usize> {
+pub(crate) fn fcntl_notify(fd: BorrowedFd<'_>, flags: DnotifyFlags) -> io::Result<()> {
     #[cfg(target_pointer_width = "32")]
     unsafe {
-        ret_usize(syscall_readonly!(
+        ret(syscall_readonly!(
             __NR_fcntl64,
             fd,
-            c_uint(F_SETPIPE_SZ),
-            c_int(size)
+            c_uint(F_NOTIFY),
+            c_uint(flags.bits())
         ))
     }
     #[cfg(target_pointer_width = "64")]
     unsafe {
-        ret_usize(syscall_readonly!(
+        ret(syscall_readonly!(
             __NR_fcntl,
             fd,
-            c_uint(F_SETPIPE_SZ),
-            c_int(size)
+            c_uint(F_NOTIFY),
+            c_uint(flags.bits())
         ))
     }
 }
 
+#[inline]
+pub(crate) fn fcntl_lock(
+    fd: BorrowedFd<'_>,
+    lock: &FileLock,
+    ofd: bool,
+    wait: bool,
+) -> io::Result<()> {
+    let cmd = match (ofd, wait) {
+        (false, false) => F_SETLK,
+        (false, true) => F_SETLKW,
+        (true, false) => F_OFD_SETLK,
+        (true, true) => F_OFD_SETLKW,
+    };
+    let raw = flock_from_file_lock(lock);
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall!(__NR_fcntl64, fd, c_uint(cmd), by_ref(&raw)))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall!(__NR_fcntl, fd, c_uint(cmd), by_ref(&raw)))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_getlk(
+    fd: BorrowedFd<'_>,
+    lock: &FileLock,
+    ofd: bool,
+) -> io::Result<Option<FileLockConflict>> {
+    let cmd = if ofd { F_OFD_GETLK } else { F_GETLK };
+    let mut raw = flock_from_file_lock(lock);
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall!(__NR_fcntl64, fd, c_uint(cmd), by_mut(&mut raw)))?;
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall!(__NR_fcntl, fd, c_uint(cmd), by_mut(&mut raw)))?;
+    }
+
+    let kind = match raw.l_type as u32 {
+        F_UNLCK => return Ok(None),
+        F_RDLCK => FileLockKind::Read,
+        F_WRLCK => FileLockKind::Write,
+        _ => return Err(io::Errno::INVAL),
+    };
+    Ok(Some(FileLockConflict {
+        lock: FileLock {
+            kind,
+            whence: FileLockWhence::Start,
+            start: raw.l_start.into(),
+            len: raw.l_len.into(),
+        },
+        // Open file description locks have an `l_pid` of -1.
+        pid: if raw.l_pid > 0 {
+            unsafe { Pid::from_raw(raw.l_pid as RawPid) }
+        } else {
+            None
+        },
+    }))
+}
+
+#[inline]
+fn flock_from_file_lock(lock: &FileLock) -> flock {
+    // Use `zeroed` to initialize any padding fields.
+    let mut raw: flock = unsafe { core::mem::zeroed() };
+    raw.l_type = match lock.kind {
+        FileLockKind::Read => F_RDLCK,
+        FileLockKind::Write => F_WRLCK,
+        FileLockKind::Unlock => F_UNLCK,
+    } as _;
+    raw.l_whence = match lock.whence {
+        FileLockWhence::Start => SEEK_SET,
+        FileLockWhence::Current => SEEK_CUR,
+        FileLockWhence::End => SEEK_END,
+    } as _;
+    raw.l_start = lock.start;
+    raw.l_len = lock.len;
+    raw
+}
+
 #[inline]
 pub(crate) fn fcntl_get_seals(fd: BorrowedFd<'_>) -> io::Result<SealFlags> {
     #[cfg(target_pointer_width = "32")]
     unsafe {
         ret_c_int(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GET_SEALS)))
-            .map(|seals| SealFlags::from_bits_retain(seals as u32))
+            .map(|seals| SealFlags::from_bits_unchecked(seals as u32))
     }
     #[cfg(target_pointer_width = "64")]
     unsafe {
         ret_c_int(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GET_SEALS)))
-            .map(|seals| SealFlags::from_bits_retain(seals as u32))
+            .map(|seals| SealFlags::from_bits_unchecked(seals as u32))
     }
 }
 
@@ -1166,6 +1488,38 @@ pub(crate) fn symlink(oldname: &CStr, newname: &CStr) -> io::Result<()> {
     }
 }
 
+#[inline]
+pub(crate) unsafe fn name_to_handle_at(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    handle: *mut c::c_void,
+    mount_id: &mut c::c_int,
+    flags: AtFlags,
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_name_to_handle_at,
+        dirfd,
+        path,
+        handle,
+        by_mut(mount_id),
+        flags
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn open_by_handle_at(
+    mount_fd: BorrowedFd<'_>,
+    handle: *const c::c_void,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    ret_owned_fd(syscall_readonly!(
+        __NR_open_by_handle_at,
+        mount_fd,
+        handle,
+        flags
+    ))
+}
+
 #[inline]
 pub(crate) fn symlinkat(oldname: &CStr, dirfd: BorrowedFd<'_>, newname: &CStr) -> io::Result<()> {
     unsafe { ret(syscall_readonly!(__NR_symlinkat, oldname, dirfd, newname)) }
@@ -1364,6 +1718,19 @@ pub(crate) fn memfd_create(name: &CStr, flags: MemfdFlags) -> io::Result<OwnedFd
     unsafe { ret_owned_fd(syscall_readonly!(__NR_memfd_create, name, flags)) }
 }
 
+#[inline]
+pub(crate) fn memfd_secret(flags: MemfdSecretFlags) -> io::Result<OwnedFd> {
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+    unsafe {
+        ret_owned_fd(syscall_readonly!(__NR_memfd_secret, c_uint(flags.bits())))
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")))]
+    {
+        let _ = flags;
+        Err(io::Errno::NOSYS)
+    }
+}
+
 #[inline]
 pub(crate) fn sendfile(
     out_fd: BorrowedFd<'_>,
@@ -1413,3 +1780,88 @@ pub(crate) fn mount(
         ))
     }
 }
+
+#[inline]
+pub(crate) fn ioctl_ficlone(fd: BorrowedFd<'_>, src_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe { ret(syscall_readonly!(__NR_ioctl, fd, c_uint(FICLONE), src_fd)) }
+}
+
+#[inline]
+pub(crate) fn ioctl_ficlonerange(fd: BorrowedFd<'_>, range: &file_clone_range) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_ioctl,
+            fd,
+            c_uint(FICLONERANGE),
+            by_ref(range)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_fideduperange(
+    fd: BorrowedFd<'_>,
+    range: *mut file_dedupe_range,
+) -> io::Result<()> {
+    ret(syscall!(__NR_ioctl, fd, c_uint(FIDEDUPERANGE), range))
+}
+
+#[inline]
+pub(crate) fn ioctl_getflags(fd: BorrowedFd<'_>) -> io::Result<u32> {
+    // `FS_IOC_GETFLAGS` is declared with a `long` argument, but the kernel
+    // reads and writes an `int`.
+    let mut flags = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        ret(syscall!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_GETFLAGS),
+            &mut flags
+        ))?;
+        Ok(flags.assume_init())
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_setflags(fd: BorrowedFd<'_>, flags: u32) -> io::Result<()> {
+    let flags: c::c_uint = flags;
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_SETFLAGS),
+            by_ref(&flags)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_fsgetxattr(fd: BorrowedFd<'_>) -> io::Result<fsxattr> {
+    let mut attr = MaybeUninit::<fsxattr>::uninit();
+    unsafe {
+        ret(syscall!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_FSGETXATTR),
+            &mut attr
+        ))?;
+        Ok(attr.assume_init())
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_fssetxattr(fd: BorrowedFd<'_>, attr: &fsxattr) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_FSSETXATTR),
+            by_ref(attr)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_fiemap(fd: BorrowedFd<'_>, fiemap: *mut c::c_void) -> io::Result<()> {
+    ret(syscall!(__NR_ioctl, fd, c_uint(FS_IOC_FIEMAP), fiemap))
+}
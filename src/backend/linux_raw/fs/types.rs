@@ -392,6 +392,16 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags for use with [`memfd_secret`].
+    ///
+    /// [`memfd_secret`]: crate::fs::memfd_secret
+    pub struct MemfdSecretFlags: c::c_uint {
+        /// `O_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::O_CLOEXEC;
+    }
+}
+
 bitflags! {
     /// `F_SEAL_*` constants for use with [`fcntl_add_seals`] and
     /// [`fcntl_get_seals`].
@@ -459,11 +469,54 @@ bitflags! {
         /// `STATX_MNT_ID` (since Linux 5.8)
         const MNT_ID = linux_raw_sys::general::STATX_MNT_ID;
 
+        /// `STATX_DIOALIGN` (since Linux 6.1)
+        const DIOALIGN = STATX_DIOALIGN;
+
         /// `STATX_ALL`
         const ALL = linux_raw_sys::general::STATX_ALL;
     }
 }
 
+/// `STATX_DIOALIGN` from `<linux/stat.h>`, which linux-raw-sys doesn't
+/// provide yet.
+const STATX_DIOALIGN: u32 = 0x0000_2000;
+
+bitflags! {
+    /// `STATX_ATTR_*` constants describing a file, for use with
+    /// [`Statx::attributes`] and [`Statx::attributes_mask`].
+    ///
+    /// [`Statx::attributes`]: crate::fs::Statx::attributes
+    /// [`Statx::attributes_mask`]: crate::fs::Statx::attributes_mask
+    pub struct StatxAttributes: u64 {
+        /// `STATX_ATTR_COMPRESSED`
+        const COMPRESSED = linux_raw_sys::general::STATX_ATTR_COMPRESSED as u64;
+
+        /// `STATX_ATTR_IMMUTABLE`
+        const IMMUTABLE = linux_raw_sys::general::STATX_ATTR_IMMUTABLE as u64;
+
+        /// `STATX_ATTR_APPEND`
+        const APPEND = linux_raw_sys::general::STATX_ATTR_APPEND as u64;
+
+        /// `STATX_ATTR_NODUMP`
+        const NODUMP = linux_raw_sys::general::STATX_ATTR_NODUMP as u64;
+
+        /// `STATX_ATTR_ENCRYPTED`
+        const ENCRYPTED = linux_raw_sys::general::STATX_ATTR_ENCRYPTED as u64;
+
+        /// `STATX_ATTR_AUTOMOUNT`
+        const AUTOMOUNT = linux_raw_sys::general::STATX_ATTR_AUTOMOUNT as u64;
+
+        /// `STATX_ATTR_MOUNT_ROOT` (since Linux 5.8)
+        const MOUNT_ROOT = linux_raw_sys::general::STATX_ATTR_MOUNT_ROOT as u64;
+
+        /// `STATX_ATTR_VERITY` (since Linux 5.5)
+        const VERITY = linux_raw_sys::general::STATX_ATTR_VERITY as u64;
+
+        /// `STATX_ATTR_DAX` (since Linux 5.8)
+        const DAX = linux_raw_sys::general::STATX_ATTR_DAX as u64;
+    }
+}
+
 bitflags! {
     /// `FALLOC_FL_*` constants for use with [`fallocate`].
     ///
@@ -486,6 +539,147 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// `SYNC_FILE_RANGE_*` constants for use with [`sync_file_range`].
+    ///
+    /// [`sync_file_range`]: crate::fs::sync_file_range
+    pub struct SyncFileRangeFlags: c::c_uint {
+        /// `SYNC_FILE_RANGE_WAIT_BEFORE`
+        const WAIT_BEFORE = linux_raw_sys::general::SYNC_FILE_RANGE_WAIT_BEFORE;
+        /// `SYNC_FILE_RANGE_WRITE`
+        const WRITE = linux_raw_sys::general::SYNC_FILE_RANGE_WRITE;
+        /// `SYNC_FILE_RANGE_WAIT_AFTER`
+        const WAIT_AFTER = linux_raw_sys::general::SYNC_FILE_RANGE_WAIT_AFTER;
+    }
+}
+
+bitflags! {
+    /// `DN_*` constants for use with [`fcntl_notify`].
+    ///
+    /// [`fcntl_notify`]: crate::fs::fcntl_notify
+    pub struct DnotifyFlags: c::c_uint {
+        /// `DN_ACCESS`
+        const ACCESS = linux_raw_sys::general::DN_ACCESS;
+        /// `DN_MODIFY`
+        const MODIFY = linux_raw_sys::general::DN_MODIFY;
+        /// `DN_CREATE`
+        const CREATE = linux_raw_sys::general::DN_CREATE;
+        /// `DN_DELETE`
+        const DELETE = linux_raw_sys::general::DN_DELETE;
+        /// `DN_RENAME`
+        const RENAME = linux_raw_sys::general::DN_RENAME;
+        /// `DN_ATTRIB`
+        const ATTRIB = linux_raw_sys::general::DN_ATTRIB;
+        /// `DN_MULTISHOT`
+        const MULTISHOT = linux_raw_sys::general::DN_MULTISHOT;
+    }
+}
+
+bitflags! {
+    /// `FS_*_FL` inode flags, as shown by `lsattr` and set by `chattr`, for
+    /// use with [`ioctl_getflags`] and [`ioctl_setflags`].
+    ///
+    /// Which flags are supported, and which may be changed, depends on the
+    /// filesystem.
+    ///
+    /// [`ioctl_getflags`]: crate::fs::ioctl_getflags
+    /// [`ioctl_setflags`]: crate::fs::ioctl_setflags
+    pub struct InodeFlags: u32 {
+        /// `FS_SECRM_FL`
+        const SECRM = linux_raw_sys::general::FS_SECRM_FL;
+        /// `FS_UNRM_FL`
+        const UNRM = linux_raw_sys::general::FS_UNRM_FL;
+        /// `FS_COMPR_FL`
+        const COMPR = linux_raw_sys::general::FS_COMPR_FL;
+        /// `FS_SYNC_FL`
+        const SYNC = linux_raw_sys::general::FS_SYNC_FL;
+        /// `FS_IMMUTABLE_FL`
+        const IMMUTABLE = linux_raw_sys::general::FS_IMMUTABLE_FL;
+        /// `FS_APPEND_FL`
+        const APPEND = linux_raw_sys::general::FS_APPEND_FL;
+        /// `FS_NODUMP_FL`
+        const NODUMP = linux_raw_sys::general::FS_NODUMP_FL;
+        /// `FS_NOATIME_FL`
+        const NOATIME = linux_raw_sys::general::FS_NOATIME_FL;
+        /// `FS_NOCOMP_FL`
+        const NOCOMP = linux_raw_sys::general::FS_NOCOMP_FL;
+        /// `FS_ENCRYPT_FL`
+        const ENCRYPT = linux_raw_sys::general::FS_ENCRYPT_FL;
+        /// `FS_INDEX_FL`
+        const INDEX = linux_raw_sys::general::FS_INDEX_FL;
+        /// `FS_IMAGIC_FL`
+        const IMAGIC = linux_raw_sys::general::FS_IMAGIC_FL;
+        /// `FS_JOURNAL_DATA_FL`
+        const JOURNAL_DATA = linux_raw_sys::general::FS_JOURNAL_DATA_FL;
+        /// `FS_NOTAIL_FL`
+        const NOTAIL = linux_raw_sys::general::FS_NOTAIL_FL;
+        /// `FS_DIRSYNC_FL`
+        const DIRSYNC = linux_raw_sys::general::FS_DIRSYNC_FL;
+        /// `FS_TOPDIR_FL`
+        const TOPDIR = linux_raw_sys::general::FS_TOPDIR_FL;
+        /// `FS_HUGE_FILE_FL`
+        const HUGE_FILE = linux_raw_sys::general::FS_HUGE_FILE_FL;
+        /// `FS_EXTENT_FL`
+        const EXTENT = linux_raw_sys::general::FS_EXTENT_FL;
+        /// `FS_VERITY_FL`
+        const VERITY = linux_raw_sys::general::FS_VERITY_FL;
+        /// `FS_EA_INODE_FL`
+        const EA_INODE = linux_raw_sys::general::FS_EA_INODE_FL;
+        /// `FS_NOCOW_FL`
+        const NOCOW = linux_raw_sys::general::FS_NOCOW_FL;
+        /// `FS_DAX_FL`
+        const DAX = linux_raw_sys::general::FS_DAX_FL;
+        /// `FS_INLINE_DATA_FL`
+        const INLINE_DATA = linux_raw_sys::general::FS_INLINE_DATA_FL;
+        /// `FS_PROJINHERIT_FL`
+        const PROJINHERIT = linux_raw_sys::general::FS_PROJINHERIT_FL;
+        /// `FS_CASEFOLD_FL`
+        const CASEFOLD = linux_raw_sys::general::FS_CASEFOLD_FL;
+    }
+}
+
+bitflags! {
+    /// `FS_XFLAG_*` extended inode flags, for use with [`FsXattr`].
+    ///
+    /// [`FsXattr`]: crate::fs::FsXattr
+    pub struct FsXattrFlags: u32 {
+        /// `FS_XFLAG_REALTIME`
+        const REALTIME = linux_raw_sys::general::FS_XFLAG_REALTIME;
+        /// `FS_XFLAG_PREALLOC`
+        const PREALLOC = linux_raw_sys::general::FS_XFLAG_PREALLOC;
+        /// `FS_XFLAG_IMMUTABLE`
+        const IMMUTABLE = linux_raw_sys::general::FS_XFLAG_IMMUTABLE;
+        /// `FS_XFLAG_APPEND`
+        const APPEND = linux_raw_sys::general::FS_XFLAG_APPEND;
+        /// `FS_XFLAG_SYNC`
+        const SYNC = linux_raw_sys::general::FS_XFLAG_SYNC;
+        /// `FS_XFLAG_NOATIME`
+        const NOATIME = linux_raw_sys::general::FS_XFLAG_NOATIME;
+        /// `FS_XFLAG_NODUMP`
+        const NODUMP = linux_raw_sys::general::FS_XFLAG_NODUMP;
+        /// `FS_XFLAG_RTINHERIT`
+        const RTINHERIT = linux_raw_sys::general::FS_XFLAG_RTINHERIT;
+        /// `FS_XFLAG_PROJINHERIT`
+        const PROJINHERIT = linux_raw_sys::general::FS_XFLAG_PROJINHERIT;
+        /// `FS_XFLAG_NOSYMLINKS`
+        const NOSYMLINKS = linux_raw_sys::general::FS_XFLAG_NOSYMLINKS;
+        /// `FS_XFLAG_EXTSIZE`
+        const EXTSIZE = linux_raw_sys::general::FS_XFLAG_EXTSIZE;
+        /// `FS_XFLAG_EXTSZINHERIT`
+        const EXTSZINHERIT = linux_raw_sys::general::FS_XFLAG_EXTSZINHERIT;
+        /// `FS_XFLAG_NODEFRAG`
+        const NODEFRAG = linux_raw_sys::general::FS_XFLAG_NODEFRAG;
+        /// `FS_XFLAG_FILESTREAM`
+        const FILESTREAM = linux_raw_sys::general::FS_XFLAG_FILESTREAM;
+        /// `FS_XFLAG_DAX`
+        const DAX = linux_raw_sys::general::FS_XFLAG_DAX;
+        /// `FS_XFLAG_COWEXTSIZE`
+        const COWEXTSIZE = linux_raw_sys::general::FS_XFLAG_COWEXTSIZE;
+        /// `FS_XFLAG_HASATTR`
+        const HASATTR = linux_raw_sys::general::FS_XFLAG_HASATTR;
+    }
+}
+
 bitflags! {
     /// `ST_*` constants for use with [`StatVfs`].
     pub struct StatVfsMountFlags: u64 {
@@ -604,7 +798,37 @@ pub struct StatVfs {
 /// `struct statx` for use with [`statx`].
 ///
 /// [`statx`]: crate::fs::statx
-pub type Statx = linux_raw_sys::general::statx;
+// We declare this ourselves, rather than using the one in `linux_raw_sys`, so
+// that fields added to the end of `struct statx` by newer kernels can be used.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub struct Statx {
+    pub stx_mask: u32,
+    pub stx_blksize: u32,
+    pub stx_attributes: u64,
+    pub stx_nlink: u32,
+    pub stx_uid: u32,
+    pub stx_gid: u32,
+    pub stx_mode: u16,
+    __statx_pad1: [u16; 1],
+    pub stx_ino: u64,
+    pub stx_size: u64,
+    pub stx_blocks: u64,
+    pub stx_attributes_mask: u64,
+    pub stx_atime: StatxTimestamp,
+    pub stx_btime: StatxTimestamp,
+    pub stx_ctime: StatxTimestamp,
+    pub stx_mtime: StatxTimestamp,
+    pub stx_rdev_major: u32,
+    pub stx_rdev_minor: u32,
+    pub stx_dev_major: u32,
+    pub stx_dev_minor: u32,
+    pub stx_mnt_id: u64,
+    pub stx_dio_mem_align: u32,
+    pub stx_dio_offset_align: u32,
+    __statx_pad3: [u64; 12],
+}
 
 /// `struct statx_timestamp` for use with [`Statx`].
 pub type StatxTimestamp = linux_raw_sys::general::statx_timestamp;
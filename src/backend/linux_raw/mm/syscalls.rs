@@ -9,16 +9,27 @@
 use super::super::c;
 #[cfg(target_pointer_width = "64")]
 use super::super::conv::loff_t_from_u64;
-use super::super::conv::{c_uint, no_fd, pass_usize, ret, ret_owned_fd, ret_void_star};
+use super::super::conv::{
+    by_mut, by_ref, c_uint, no_fd, pass_usize, ret, ret_c_uint, ret_owned_fd, ret_usize,
+    ret_void_star, slice, slice_just_addr, zero,
+};
 use super::types::{
-    Advice, MapFlags, MlockFlags, MprotectFlags, MremapFlags, MsyncFlags, ProtFlags,
+    Advice, MapFlags, MlockFlags, MlockallFlags, MprotectFlags, MremapFlags, MsyncFlags, ProtFlags,
     UserfaultfdFlags,
 };
 use crate::fd::{BorrowedFd, OwnedFd};
-use crate::io;
+use crate::io::{self, IoSlice};
+use crate::process::Pid;
 #[cfg(target_pointer_width = "32")]
 use core::convert::TryInto;
-use linux_raw_sys::general::MAP_ANONYMOUS;
+use linux_raw_sys::general::{
+    uffdio_api, uffdio_continue, uffdio_copy, uffdio_range, uffdio_register, uffdio_writeprotect,
+    uffdio_zeropage, MAP_ANONYMOUS,
+};
+use linux_raw_sys::ioctl::{
+    UFFDIO_API, UFFDIO_CONTINUE, UFFDIO_COPY, UFFDIO_REGISTER, UFFDIO_UNREGISTER, UFFDIO_WAKE,
+    UFFDIO_WRITEPROTECT, UFFDIO_ZEROPAGE,
+};
 
 #[inline]
 pub(crate) fn madvise(addr: *mut c::c_void, len: usize, advice: Advice) -> io::Result<()> {
@@ -208,7 +219,293 @@ pub(crate) unsafe fn munlock(addr: *mut c::c_void, length: usize) -> io::Result<
     ret(syscall!(__NR_munlock, addr, pass_usize(length)))
 }
 
+#[inline]
+pub(crate) fn mlockall(flags: MlockallFlags) -> io::Result<()> {
+    unsafe { ret(syscall_readonly!(__NR_mlockall, flags)) }
+}
+
+/// # Safety
+///
+/// `munlockall` unlocks memory locked by unrelated code in the process.
+#[inline]
+pub(crate) unsafe fn munlockall() -> io::Result<()> {
+    ret(syscall_readonly!(__NR_munlockall))
+}
+
+/// # Safety
+///
+/// `vec` must have room for one byte for each page in `addr..addr + len`.
+#[inline]
+pub(crate) unsafe fn mincore(addr: *mut c::c_void, len: usize, vec: *mut u8) -> io::Result<()> {
+    ret(syscall!(__NR_mincore, addr, pass_usize(len), vec))
+}
+
+/// # Safety
+///
+/// If `pidfd` refers to the current process, `process_madvise` has the same
+/// hazards as `madvise`.
+#[inline]
+pub(crate) unsafe fn process_madvise(
+    pidfd: BorrowedFd<'_>,
+    ranges: &[IoSlice<'_>],
+    advice: Advice,
+) -> io::Result<usize> {
+    let (ranges_addr, ranges_len) = slice(ranges);
+    ret_usize(syscall_readonly!(
+        __NR_process_madvise,
+        pidfd,
+        ranges_addr,
+        ranges_len,
+        c_uint(advice as c::c_uint),
+        zero()
+    ))
+}
+
+#[inline]
+pub(crate) fn pkey_alloc(access_rights: u32) -> io::Result<u32> {
+    unsafe {
+        ret_c_uint(syscall_readonly!(
+            __NR_pkey_alloc,
+            zero(),
+            c_uint(access_rights)
+        ))
+    }
+}
+
+/// # Safety
+///
+/// Memory may still be tagged with `pkey`, and will be governed by its new
+/// access rights if it's allocated again.
+#[inline]
+pub(crate) unsafe fn pkey_free(pkey: u32) -> io::Result<()> {
+    ret(syscall_readonly!(__NR_pkey_free, c_uint(pkey)))
+}
+
+/// # Safety
+///
+/// `pkey_mprotect` operates on raw pointers and may round out to the nearest
+/// page boundaries.
+#[inline]
+pub(crate) unsafe fn pkey_mprotect(
+    ptr: *mut c::c_void,
+    len: usize,
+    flags: MprotectFlags,
+    pkey: u32,
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_pkey_mprotect,
+        ptr,
+        pass_usize(len),
+        flags,
+        c_uint(pkey)
+    ))
+}
+
+#[cfg(all(asm, target_arch = "x86_64"))]
+#[inline]
+pub(crate) unsafe fn rdpkru() -> u32 {
+    super::super::arch::asm::rdpkru()
+}
+
+#[cfg(all(asm, target_arch = "x86_64"))]
+#[inline]
+pub(crate) unsafe fn wrpkru(pkru: u32) {
+    super::super::arch::asm::wrpkru(pkru)
+}
+
+/// The `maxnode` argument for a node mask, which Linux expects to be one
+/// more than the number of bits in it.
+#[inline]
+fn maxnode(nodemask: &[usize]) -> usize {
+    nodemask.len() * usize::MAX.count_ones() as usize + 1
+}
+
+/// # Safety
+///
+/// `mbind` operates on raw pointers and may round out to the nearest page
+/// boundaries.
+#[inline]
+pub(crate) unsafe fn mbind(
+    addr: *mut c::c_void,
+    len: usize,
+    mode: u32,
+    nodemask: &[usize],
+    flags: u32,
+) -> io::Result<()> {
+    ret(syscall_readonly!(
+        __NR_mbind,
+        addr,
+        pass_usize(len),
+        c_uint(mode),
+        slice_just_addr(nodemask),
+        pass_usize(maxnode(nodemask)),
+        c_uint(flags)
+    ))
+}
+
+#[inline]
+pub(crate) fn set_mempolicy(mode: u32, nodemask: &[usize]) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_set_mempolicy,
+            c_uint(mode),
+            slice_just_addr(nodemask),
+            pass_usize(maxnode(nodemask))
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn get_mempolicy(
+    mode: &mut c::c_int,
+    nodemask: &mut [usize],
+    addr: *mut c::c_void,
+    flags: u32,
+) -> io::Result<()> {
+    let maxnode = maxnode(nodemask);
+    unsafe {
+        ret(syscall!(
+            __NR_get_mempolicy,
+            by_mut(mode),
+            nodemask.as_mut_ptr(),
+            pass_usize(maxnode),
+            addr,
+            c_uint(flags)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn migrate_pages(
+    pid: Option<Pid>,
+    old_nodes: &[usize],
+    new_nodes: &[usize],
+) -> io::Result<usize> {
+    debug_assert_eq!(old_nodes.len(), new_nodes.len());
+    unsafe {
+        ret_usize(syscall_readonly!(
+            __NR_migrate_pages,
+            c_uint(Pid::as_raw(pid)),
+            pass_usize(maxnode(old_nodes)),
+            slice_just_addr(old_nodes),
+            slice_just_addr(new_nodes)
+        ))
+    }
+}
+
+/// # Safety
+///
+/// `pages` and `status` must be valid for `count` elements, and `nodes` must
+/// be null or valid for `count` elements.
+#[inline]
+pub(crate) unsafe fn move_pages(
+    pid: Option<Pid>,
+    count: usize,
+    pages: *const *mut c::c_void,
+    nodes: *const c::c_int,
+    status: *mut c::c_int,
+    flags: u32,
+) -> io::Result<usize> {
+    ret_usize(syscall!(
+        __NR_move_pages,
+        c_uint(Pid::as_raw(pid)),
+        pass_usize(count),
+        pages,
+        nodes,
+        status,
+        c_uint(flags)
+    ))
+}
+
 #[inline]
 pub(crate) unsafe fn userfaultfd(flags: UserfaultfdFlags) -> io::Result<OwnedFd> {
     ret_owned_fd(syscall_readonly!(__NR_userfaultfd, flags))
 }
+
+#[inline]
+pub(crate) fn ioctl_uffdio_api(fd: BorrowedFd<'_>, api: &mut uffdio_api) -> io::Result<()> {
+    unsafe { ret(syscall!(__NR_ioctl, fd, c_uint(UFFDIO_API), by_mut(api))) }
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_register(
+    fd: BorrowedFd<'_>,
+    register: &mut uffdio_register,
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_REGISTER),
+        by_mut(register)
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_unregister(
+    fd: BorrowedFd<'_>,
+    range: &uffdio_range,
+) -> io::Result<()> {
+    ret(syscall_readonly!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_UNREGISTER),
+        by_ref(range)
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_wake(fd: BorrowedFd<'_>, range: &uffdio_range) -> io::Result<()> {
+    ret(syscall_readonly!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_WAKE),
+        by_ref(range)
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_copy(
+    fd: BorrowedFd<'_>,
+    copy: &mut uffdio_copy,
+) -> io::Result<()> {
+    ret(syscall!(__NR_ioctl, fd, c_uint(UFFDIO_COPY), by_mut(copy)))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_zeropage(
+    fd: BorrowedFd<'_>,
+    zeropage: &mut uffdio_zeropage,
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_ZEROPAGE),
+        by_mut(zeropage)
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_writeprotect(
+    fd: BorrowedFd<'_>,
+    writeprotect: &uffdio_writeprotect,
+) -> io::Result<()> {
+    ret(syscall_readonly!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_WRITEPROTECT),
+        by_ref(writeprotect)
+    ))
+}
+
+#[inline]
+pub(crate) unsafe fn ioctl_uffdio_continue(
+    fd: BorrowedFd<'_>,
+    cont: &mut uffdio_continue,
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_ioctl,
+        fd,
+        c_uint(UFFDIO_CONTINUE),
+        by_mut(cont)
+    ))
+}
@@ -107,6 +107,21 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// `MCL_*` flags for use with [`mlockall`].
+    ///
+    /// [`mlockall`]: crate::mm::mlockall
+    pub struct MlockallFlags: u32 {
+        /// `MCL_CURRENT`—Lock all pages which are currently mapped.
+        const CURRENT = linux_raw_sys::general::MCL_CURRENT;
+        /// `MCL_FUTURE`—Lock all pages which become mapped in the future.
+        const FUTURE = linux_raw_sys::general::MCL_FUTURE;
+        /// `MCL_ONFAULT` (since Linux 4.4)—Lock pages when they're faulted
+        /// in, rather than populating them immediately.
+        const ONFAULT = linux_raw_sys::general::MCL_ONFAULT;
+    }
+}
+
 bitflags! {
     /// `MS_*` flags for use with [`msync`].
     ///
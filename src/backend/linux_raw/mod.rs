@@ -24,9 +24,9 @@ mod arch;
 mod conv;
 mod elf;
 mod reg;
-#[cfg(any(feature = "time", target_arch = "x86"))]
+#[cfg(any(feature = "thread", feature = "time", target_arch = "x86"))]
 mod vdso;
-#[cfg(any(feature = "time", target_arch = "x86"))]
+#[cfg(any(feature = "thread", feature = "time", target_arch = "x86"))]
 mod vdso_wrappers;
 
 #[cfg(feature = "fs")]
@@ -39,8 +39,10 @@ pub(crate) mod mm;
 #[cfg(feature = "net")]
 pub(crate) mod net;
 #[cfg(any(
+    feature = "mm",
     feature = "param",
     feature = "runtime",
+    feature = "thread",
     feature = "time",
     target_arch = "x86",
 ))]
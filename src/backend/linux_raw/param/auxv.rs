@@ -26,7 +26,7 @@ use linux_raw_sys::general::{
     AT_PHNUM, AT_SYSINFO_EHDR,
 };
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "runtime"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     let mut page_size = PAGE_SIZE.load(Relaxed);
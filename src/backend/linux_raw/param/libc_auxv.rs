@@ -14,7 +14,7 @@ use core::slice;
 // `getauxval` wasn't supported in glibc until 2.16.
 weak!(fn getauxval(libc::c_ulong) -> *mut libc::c_void);
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "runtime"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
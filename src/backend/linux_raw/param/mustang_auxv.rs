@@ -19,7 +19,7 @@ use linux_raw_sys::general::{
     AT_SYSINFO_EHDR,
 };
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "runtime"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     // Safety: This is initialized during program startup.
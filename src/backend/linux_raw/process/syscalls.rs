@@ -9,9 +9,10 @@
 use super::super::c;
 use super::super::conv::{
     by_mut, by_ref, c_int, c_uint, negative_pid, pass_usize, ret, ret_c_int, ret_c_uint,
-    ret_infallible, ret_usize, ret_usize_infallible, size_of, slice_just_addr, slice_mut, zero,
+    ret_infallible, ret_usize, ret_usize_infallible, size_of, slice, slice_just_addr, slice_mut,
+    zero,
 };
-use super::types::{RawCpuSet, RawUname};
+use super::types::{RawCpuSet, RawSysinfo, RawUname};
 use crate::fd::BorrowedFd;
 use crate::ffi::CStr;
 use crate::io;
@@ -59,7 +60,7 @@ pub(crate) fn membarrier_query() -> MembarrierQuery {
                 // contains "source of truth" valid bits.
                 //
                 // [here]: https://github.com/bitflags/bitflags/pull/207#issuecomment-671668662
-                MembarrierQuery::from_bits_retain(query)
+                MembarrierQuery::from_bits_unchecked(query)
             }
             Err(_) => MembarrierQuery::empty(),
         }
@@ -190,6 +191,143 @@ pub(crate) fn geteuid() -> Uid {
     }
 }
 
+#[inline]
+pub(crate) fn setuid(uid: Uid) -> io::Result<()> {
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret(syscall_readonly!(__NR_setuid32, c_uint(uid.as_raw())))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret(syscall_readonly!(__NR_setuid, c_uint(uid.as_raw())))
+    }
+}
+
+#[inline]
+pub(crate) fn setgid(gid: Gid) -> io::Result<()> {
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret(syscall_readonly!(__NR_setgid32, c_uint(gid.as_raw())))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret(syscall_readonly!(__NR_setgid, c_uint(gid.as_raw())))
+    }
+}
+
+#[inline]
+pub(crate) fn setresuid(ruid: u32, euid: u32, suid: u32) -> io::Result<()> {
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setresuid32,
+            c_uint(ruid),
+            c_uint(euid),
+            c_uint(suid)
+        ))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setresuid,
+            c_uint(ruid),
+            c_uint(euid),
+            c_uint(suid)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn setresgid(rgid: u32, egid: u32, sgid: u32) -> io::Result<()> {
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setresgid32,
+            c_uint(rgid),
+            c_uint(egid),
+            c_uint(sgid)
+        ))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setresgid,
+            c_uint(rgid),
+            c_uint(egid),
+            c_uint(sgid)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn getresuid() -> (Uid, Uid, Uid) {
+    let mut ruid = MaybeUninit::<c::c_uint>::uninit();
+    let mut euid = MaybeUninit::<c::c_uint>::uninit();
+    let mut suid = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+        ret_infallible(syscall!(__NR_getresuid32, &mut ruid, &mut euid, &mut suid));
+        #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+        ret_infallible(syscall!(__NR_getresuid, &mut ruid, &mut euid, &mut suid));
+        (
+            Uid::from_raw(ruid.assume_init()),
+            Uid::from_raw(euid.assume_init()),
+            Uid::from_raw(suid.assume_init()),
+        )
+    }
+}
+
+#[inline]
+pub(crate) fn getresgid() -> (Gid, Gid, Gid) {
+    let mut rgid = MaybeUninit::<c::c_uint>::uninit();
+    let mut egid = MaybeUninit::<c::c_uint>::uninit();
+    let mut sgid = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+        ret_infallible(syscall!(__NR_getresgid32, &mut rgid, &mut egid, &mut sgid));
+        #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+        ret_infallible(syscall!(__NR_getresgid, &mut rgid, &mut egid, &mut sgid));
+        (
+            Gid::from_raw(rgid.assume_init()),
+            Gid::from_raw(egid.assume_init()),
+            Gid::from_raw(sgid.assume_init()),
+        )
+    }
+}
+
+#[inline]
+pub(crate) fn getgroups(buf: &mut [Gid]) -> io::Result<usize> {
+    let len = buf.len().min(c::c_int::MAX as usize) as c::c_int;
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret_usize(syscall!(__NR_getgroups32, c_int(len), buf.as_mut_ptr()))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret_usize(syscall!(__NR_getgroups, c_int(len), buf.as_mut_ptr()))
+    }
+}
+
+#[inline]
+pub(crate) fn setgroups(groups: &[Gid]) -> io::Result<()> {
+    #[cfg(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm"))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setgroups32,
+            pass_usize(groups.len()),
+            slice_just_addr(groups)
+        ))
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "sparc", target_arch = "arm")))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_setgroups,
+            pass_usize(groups.len()),
+            slice_just_addr(groups)
+        ))
+    }
+}
+
 #[inline]
 pub(crate) fn sched_getaffinity(pid: Option<Pid>, cpuset: &mut RawCpuSet) -> io::Result<()> {
     unsafe {
@@ -240,6 +378,32 @@ pub(crate) fn uname() -> RawUname {
     }
 }
 
+#[inline]
+pub(crate) fn sysinfo() -> RawSysinfo {
+    let mut info = MaybeUninit::<RawSysinfo>::uninit();
+    unsafe {
+        ret_infallible(syscall!(__NR_sysinfo, &mut info));
+        info.assume_init()
+    }
+}
+
+#[inline]
+pub(crate) fn sethostname(name: &[u8]) -> io::Result<()> {
+    let (name_addr, name_len) = slice(name);
+    unsafe { ret(syscall_readonly!(__NR_sethostname, name_addr, name_len)) }
+}
+
+#[inline]
+pub(crate) fn setdomainname(name: &[u8]) -> io::Result<()> {
+    let (name_addr, name_len) = slice(name);
+    unsafe { ret(syscall_readonly!(__NR_setdomainname, name_addr, name_len)) }
+}
+
+#[inline]
+pub(crate) fn personality(persona: u32) -> io::Result<u32> {
+    unsafe { ret_c_uint(syscall_readonly!(__NR_personality, c_uint(persona))) }
+}
+
 #[inline]
 pub(crate) fn nice(inc: i32) -> io::Result<i32> {
     let priority = if inc > -40 && inc < 40 {
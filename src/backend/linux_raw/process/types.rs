@@ -222,6 +222,8 @@ pub type RawCpuid = u32;
 
 pub(crate) type RawUname = linux_raw_sys::general::new_utsname;
 
+pub(crate) type RawSysinfo = linux_raw_sys::general::sysinfo;
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct RawCpuSet {
@@ -1,2 +1,11 @@
+#[cfg(all(
+    asm,
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+pub(crate) mod spawn;
 pub(crate) mod syscalls;
 pub(crate) mod tls;
@@ -0,0 +1,210 @@
+//! A `posix_spawn`-like process creation API built on
+//! `clone(CLONE_VM | CLONE_VFORK)`.
+//!
+//! # Safety
+//!
+//! The child shares the parent's address space until it calls `execve` or
+//! exits, so it runs on its own stack and only performs raw system calls.
+#![allow(unsafe_code)]
+
+use super::super::c;
+use super::super::io::syscalls as io_syscalls;
+use super::super::param::auxv::page_size;
+use super::super::process::syscalls as process_syscalls;
+use super::syscalls::execve;
+use super::syscalls::spawn::{
+    clone_vfork, dup2_raw, mmap_stack, mprotect_none, munmap, sigaction_reset_handler,
+    sigprocmask_setmask,
+};
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use crate::ffi::CStr;
+use crate::io::{self, PipeFlags};
+use crate::process::{Pid, WaitOptions};
+
+/// The size of the stack the child runs on until it calls `execve`, not
+/// counting the guard page below it.
+const CHILD_STACK_SIZE: usize = 64 * 1024;
+
+/// The highest signal number.
+const NSIG: c::c_int = 64;
+
+/// An action to perform in the child before it calls `execve`, for use with
+/// [`spawn`].
+///
+/// Actions are performed in order, after the child's signal handlers have
+/// been reset to their default dispositions.
+///
+/// [`spawn`]: crate::runtime::spawn
+#[derive(Debug, Clone, Copy)]
+pub enum SpawnAction<'a> {
+    /// `dup2(fd, target)`—Duplicate `fd` onto the file descriptor number
+    /// `target`, without `FD_CLOEXEC`.
+    ///
+    /// If `fd` is already `target`, its `FD_CLOEXEC` flag is cleared, as with
+    /// `posix_spawn_file_actions_adddup2`.
+    Dup2(BorrowedFd<'a>, RawFd),
+
+    /// `close(fd)`—Close the file descriptor number `fd`.
+    Close(RawFd),
+
+    /// `fchdir(fd)`—Change the child's working directory.
+    Fchdir(BorrowedFd<'a>),
+
+    /// `setsid()`—Create a new session with the child as its leader.
+    Setsid,
+
+    /// Unblock all signals in the child. Otherwise, the child inherits the
+    /// parent's signal mask.
+    ResetSignalMask,
+}
+
+/// State shared with the child, which runs in the parent's address space.
+struct ChildArgs<'a> {
+    path: &'a CStr,
+    argv: *const *const u8,
+    envp: *const *const u8,
+    actions: &'a [SpawnAction<'a>],
+    error_fd: RawFd,
+    min_fd: RawFd,
+    mask: u64,
+}
+
+pub(crate) unsafe fn spawn(
+    path: &CStr,
+    argv: *const *const u8,
+    envp: *const *const u8,
+    actions: &[SpawnAction<'_>],
+) -> io::Result<Pid> {
+    // The child reports a failure before `execve` by writing its errno to
+    // this pipe; on a successful `execve`, `O_CLOEXEC` closes it.
+    let (reader, writer) = io_syscalls::pipe_with(PipeFlags::CLOEXEC)?;
+
+    // The child moves its end of the pipe above every file descriptor
+    // mentioned by an action, so that no action can clobber it.
+    let min_fd = actions
+        .iter()
+        .map(|action| match *action {
+            SpawnAction::Dup2(_, target) | SpawnAction::Close(target) => target,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+        .saturating_add(1);
+
+    // The stack grows down on all supported architectures, so put a guard
+    // page at the low end, to turn an overflow in the child into a fault
+    // rather than a write into our memory. `mmap` returns page-aligned
+    // memory, which satisfies the 16-byte stack alignment these architectures
+    // require.
+    let guard_size = page_size();
+    let stack_len = guard_size + CHILD_STACK_SIZE;
+    let stack = mmap_stack(stack_len)?;
+    if let Err(err) = mprotect_none(stack, guard_size) {
+        let _ = munmap(stack, stack_len);
+        return Err(err);
+    }
+    let stack_top = stack.cast::<u8>().add(stack_len).cast::<c::c_void>();
+
+    // Block all signals, so that no signal handler runs in the child while
+    // it shares our address space.
+    let old_mask = sigprocmask_setmask(!0)?;
+
+    let mut args = ChildArgs {
+        path,
+        argv,
+        envp,
+        actions,
+        error_fd: writer.as_raw_fd(),
+        min_fd,
+        mask: old_mask,
+    };
+
+    // `CLONE_VFORK` suspends us until the child calls `execve` or exits.
+    let result = clone_vfork(
+        stack_top,
+        child_main,
+        (&mut args as *mut ChildArgs<'_>).cast(),
+    );
+
+    // Restoring the old mask can't fail, since it was just in effect. The
+    // child has called `execve` or exited, so it's done with the stack.
+    let _ = sigprocmask_setmask(old_mask);
+    let _ = munmap(stack, stack_len);
+    drop(writer);
+    let pid = result?;
+
+    let mut errno = [0_u8; 4];
+    let mut len = 0;
+    while len < errno.len() {
+        match io_syscalls::read(reader.as_fd(), &mut errno[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(io::Errno::INTR) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    if len == 0 {
+        // The pipe was closed by `execve`.
+        return Ok(pid);
+    }
+
+    // The child failed and exited; reap it before reporting the error.
+    while let Err(io::Errno::INTR) = process_syscalls::waitpid(Some(pid), WaitOptions::empty()) {}
+    Err(io::Errno::from_raw_os_error(i32::from_ne_bytes(errno)))
+}
+
+/// The child's entry point, running on its own stack.
+unsafe extern "C" fn child_main(arg: *mut c::c_void) -> ! {
+    let args = &*arg.cast::<ChildArgs<'_>>();
+
+    let error_fd = if args.error_fd < args.min_fd {
+        match io_syscalls::fcntl_dupfd_cloexec(BorrowedFd::borrow_raw(args.error_fd), args.min_fd) {
+            Ok(fd) => crate::fd::IntoRawFd::into_raw_fd(fd),
+            Err(err) => child_fail(args.error_fd, err),
+        }
+    } else {
+        args.error_fd
+    };
+
+    // Signal handlers installed in the parent must not run in the child.
+    for sig in 1..=NSIG {
+        // This only fails for `SIGKILL` and `SIGSTOP`, which can't have
+        // handlers.
+        let _ = sigaction_reset_handler(sig);
+    }
+
+    let mut mask = args.mask;
+    for action in args.actions {
+        let result = match *action {
+            SpawnAction::Dup2(fd, target) => dup2_raw(fd.as_raw_fd(), target),
+            SpawnAction::Close(fd) => {
+                io_syscalls::close(fd);
+                Ok(())
+            }
+            SpawnAction::Fchdir(fd) => process_syscalls::fchdir(fd),
+            SpawnAction::Setsid => process_syscalls::setsid().map(drop),
+            SpawnAction::ResetSignalMask => {
+                mask = 0;
+                Ok(())
+            }
+        };
+        if let Err(err) = result {
+            child_fail(error_fd, err);
+        }
+    }
+
+    if let Err(err) = sigprocmask_setmask(mask) {
+        child_fail(error_fd, err);
+    }
+
+    let err = execve(args.path, args.argv, args.envp);
+    child_fail(error_fd, err)
+}
+
+/// Report `err` to the parent and exit the child.
+unsafe fn child_fail(error_fd: RawFd, err: io::Errno) -> ! {
+    let errno = err.raw_os_error().to_ne_bytes();
+    let _ = io_syscalls::write(BorrowedFd::borrow_raw(error_fd), &errno);
+    process_syscalls::exit_group(127)
+}
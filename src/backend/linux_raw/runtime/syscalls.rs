@@ -7,10 +7,12 @@
 #![allow(clippy::undocumented_unsafe_blocks)]
 
 use super::super::c;
-#[cfg(target_arch = "x86")]
+#[cfg(any(target_arch = "x86", feature = "thread"))]
 use super::super::conv::by_mut;
 use super::super::conv::{c_int, c_uint, ret, ret_c_uint, ret_error, ret_usize_infallible, zero};
-#[cfg(feature = "fs")]
+#[cfg(feature = "thread")]
+use crate::fd::AsRawFd;
+#[cfg(any(feature = "fs", feature = "thread"))]
 use crate::fd::BorrowedFd;
 use crate::ffi::CStr;
 #[cfg(feature = "fs")]
@@ -18,6 +20,10 @@ use crate::fs::AtFlags;
 use crate::io;
 use crate::process::{Pid, RawNonZeroPid};
 use linux_raw_sys::general::{__kernel_pid_t, PR_SET_NAME, SIGCHLD};
+#[cfg(feature = "thread")]
+use linux_raw_sys::general::{
+    clone_args, CLONE_ARGS_SIZE_VER0, CLONE_ARGS_SIZE_VER1, CLONE_ARGS_SIZE_VER2,
+};
 #[cfg(target_arch = "x86_64")]
 use {super::super::conv::ret_infallible, linux_raw_sys::general::ARCH_SET_FS};
 
@@ -34,6 +40,48 @@ pub(crate) unsafe fn fork() -> io::Result<Option<Pid>> {
     Ok(Pid::from_raw(pid))
 }
 
+#[cfg(feature = "thread")]
+#[inline]
+pub(crate) unsafe fn clone3(
+    flags: u64,
+    pidfd: *mut c::c_int,
+    exit_signal: u64,
+    set_tid: &[Pid],
+    cgroup: Option<BorrowedFd<'_>>,
+) -> io::Result<Option<Pid>> {
+    let mut args = clone_args {
+        flags,
+        pidfd: pidfd as usize as u64,
+        child_tid: 0,
+        parent_tid: 0,
+        exit_signal,
+        stack: 0,
+        stack_size: 0,
+        tls: 0,
+        // The kernel rejects a non-null `set_tid` with a zero size.
+        set_tid: if set_tid.is_empty() {
+            0
+        } else {
+            set_tid.as_ptr() as usize as u64
+        },
+        set_tid_size: set_tid.len() as u64,
+        cgroup: cgroup.map_or(0, |fd| fd.as_raw_fd() as u64),
+    };
+    // Pass the smallest struct size that covers the fields in use, so that
+    // plain clones still work on kernels that predate the newer fields, and
+    // kernels that don't know about a field in use fail with `E2BIG`.
+    let size = if cgroup.is_some() {
+        CLONE_ARGS_SIZE_VER2
+    } else if !set_tid.is_empty() {
+        CLONE_ARGS_SIZE_VER1
+    } else {
+        CLONE_ARGS_SIZE_VER0
+    };
+    debug_assert!(size as usize <= core::mem::size_of::<clone_args>());
+    let pid = ret_c_uint(syscall!(__NR_clone3, by_mut(&mut args), c_uint(size)))?;
+    Ok(Pid::from_raw(pid))
+}
+
 #[cfg(feature = "fs")]
 pub(crate) unsafe fn execveat(
     dirfd: BorrowedFd<'_>,
@@ -105,3 +153,145 @@ pub(crate) mod tls {
         unsafe { syscall_noreturn!(__NR_exit, c_int(code)) }
     }
 }
+
+/// Raw syscalls supporting `runtime::spawn`, usable in a `CLONE_VM` child.
+#[cfg(all(
+    asm,
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+pub(crate) mod spawn {
+    use super::super::super::arch::asm::clone_with_entry;
+    use super::super::super::conv::{
+        by_mut, by_ref, loff_t_from_u64, no_fd, pass_usize, raw_fd, ret_c_int, ret_void_star,
+        size_of,
+    };
+    use super::super::super::reg::nr;
+    use super::*;
+    use crate::fd::RawFd;
+    use core::mem::MaybeUninit;
+    use core::ptr::{null, null_mut};
+    use linux_raw_sys::general::{
+        __NR_clone, sigaction, CLONE_VFORK, CLONE_VM, F_SETFD, MAP_ANONYMOUS, MAP_PRIVATE,
+        MAP_STACK, PROT_NONE, PROT_READ, PROT_WRITE, SIG_SETMASK,
+    };
+
+    /// `mmap(NULL, len, PROT_READ | PROT_WRITE, MAP_PRIVATE | MAP_ANONYMOUS |
+    /// MAP_STACK, -1, 0)`
+    #[inline]
+    pub(crate) unsafe fn mmap_stack(len: usize) -> io::Result<*mut c::c_void> {
+        ret_void_star(syscall!(
+            __NR_mmap,
+            null_mut::<c::c_void>(),
+            pass_usize(len),
+            c_uint(PROT_READ | PROT_WRITE),
+            c_uint(MAP_PRIVATE | MAP_ANONYMOUS | MAP_STACK),
+            no_fd(),
+            loff_t_from_u64(0)
+        ))
+    }
+
+    /// `mprotect(addr, len, PROT_NONE)`
+    #[inline]
+    pub(crate) unsafe fn mprotect_none(addr: *mut c::c_void, len: usize) -> io::Result<()> {
+        ret(syscall!(
+            __NR_mprotect,
+            addr,
+            pass_usize(len),
+            c_uint(PROT_NONE)
+        ))
+    }
+
+    /// `munmap(addr, len)`
+    #[inline]
+    pub(crate) unsafe fn munmap(addr: *mut c::c_void, len: usize) -> io::Result<()> {
+        ret(syscall!(__NR_munmap, addr, pass_usize(len)))
+    }
+
+    /// `clone(CLONE_VM | CLONE_VFORK | SIGCHLD, stack)`, calling `entry(arg)`
+    /// on `stack` in the child.
+    #[inline]
+    pub(crate) unsafe fn clone_vfork(
+        stack: *mut c::c_void,
+        entry: unsafe extern "C" fn(*mut c::c_void) -> !,
+        arg: *mut c::c_void,
+    ) -> io::Result<Pid> {
+        let pid = ret_c_uint(clone_with_entry(
+            nr(__NR_clone),
+            c_uint(CLONE_VM | CLONE_VFORK | SIGCHLD),
+            stack.into(),
+            entry,
+            arg,
+        ))?;
+        debug_assert_ne!(pid, 0);
+        Ok(Pid::from_raw_nonzero(RawNonZeroPid::new_unchecked(pid)))
+    }
+
+    /// `rt_sigprocmask(SIG_SETMASK, &mask, &old)`
+    #[inline]
+    pub(crate) unsafe fn sigprocmask_setmask(mask: u64) -> io::Result<u64> {
+        let mut old = MaybeUninit::<u64>::uninit();
+        ret(syscall!(
+            __NR_rt_sigprocmask,
+            c_uint(SIG_SETMASK),
+            by_ref(&mask),
+            &mut old,
+            size_of::<u64, _>()
+        ))?;
+        Ok(old.assume_init())
+    }
+
+    /// Reset the disposition of `sig` to `SIG_DFL` if it has a handler
+    /// installed, leaving ignored signals ignored.
+    #[inline]
+    pub(crate) unsafe fn sigaction_reset_handler(sig: c::c_int) -> io::Result<()> {
+        const SIG_IGN: usize = 1;
+
+        let mut old = MaybeUninit::<sigaction>::uninit();
+        ret(syscall!(
+            __NR_rt_sigaction,
+            c_int(sig),
+            null::<sigaction>(),
+            &mut old,
+            size_of::<u64, _>()
+        ))?;
+        match old.assume_init().sa_handler.map_or(0, |f| f as usize) {
+            0 | SIG_IGN => Ok(()),
+            _ => {
+                let mut dfl: sigaction = core::mem::zeroed();
+                ret(syscall!(
+                    __NR_rt_sigaction,
+                    c_int(sig),
+                    by_mut(&mut dfl),
+                    null::<sigaction>(),
+                    size_of::<u64, _>()
+                ))
+            }
+        }
+    }
+
+    /// `dup3(fd, target, 0)` on raw file descriptors, or clearing
+    /// `FD_CLOEXEC` if they are equal.
+    #[inline]
+    pub(crate) unsafe fn dup2_raw(fd: RawFd, target: RawFd) -> io::Result<()> {
+        if fd == target {
+            ret(syscall_readonly!(
+                __NR_fcntl,
+                raw_fd(fd),
+                c_uint(F_SETFD),
+                pass_usize(0)
+            ))
+        } else {
+            ret_c_int(syscall_readonly!(
+                __NR_dup3,
+                raw_fd(fd),
+                raw_fd(target),
+                c_uint(0)
+            ))
+            .map(drop)
+        }
+    }
+}
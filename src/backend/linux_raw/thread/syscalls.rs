@@ -8,19 +8,24 @@
 
 use super::super::c;
 use super::super::conv::{
-    by_ref, c_int, c_uint, ret, ret_c_int, ret_usize, ret_usize_infallible, zero,
+    by_ref, c_int, c_uint, pass_usize, ret, ret_c_int, ret_usize, ret_usize_infallible,
+    slice_just_addr, zero,
 };
 use crate::fd::BorrowedFd;
 use crate::io;
 use crate::process::{Pid, RawNonZeroPid};
-use crate::thread::{ClockId, FutexFlags, FutexOperation, NanosleepRelativeResult, Timespec};
-use core::mem::MaybeUninit;
+use crate::thread::{
+    ClockId, FutexFlags, FutexOperation, FutexWaitv, NanosleepRelativeResult, Rseq, Timespec,
+};
+use core::mem::{size_of, MaybeUninit};
 use linux_raw_sys::general::{__kernel_pid_t, __kernel_timespec, TIMER_ABSTIME};
 #[cfg(target_pointer_width = "32")]
 use {
     core::convert::TryInto, core::ptr, linux_raw_sys::general::timespec as __kernel_old_timespec,
 };
 
+pub(crate) use super::super::vdso_wrappers::sched_getcpu;
+
 #[inline]
 pub(crate) fn clock_nanosleep_relative(
     id: ClockId,
@@ -268,6 +273,27 @@ unsafe fn futex_old(
     uaddr2: *mut u32,
     val3: u32,
 ) -> io::Result<usize> {
+    // These operations pass `val2` in place of `utime`, and when there's no
+    // timeout there's nothing to convert.
+    let uses_val2 = matches!(
+        op,
+        FutexOperation::Requeue
+            | FutexOperation::CmpRequeue
+            | FutexOperation::CmpRequeuePi
+            | FutexOperation::WakeOp
+    );
+    if uses_val2 || utime.is_null() {
+        return ret_usize(syscall!(
+            __NR_futex,
+            uaddr,
+            (op, flags),
+            c_uint(val),
+            utime,
+            uaddr2,
+            c_uint(val3)
+        ));
+    }
+
     let old_utime = __kernel_old_timespec {
         tv_sec: (*utime).tv_sec.try_into().map_err(|_| io::Errno::INVAL)?,
         tv_nsec: (*utime).tv_nsec.try_into().map_err(|_| io::Errno::INVAL)?,
@@ -283,6 +309,24 @@ unsafe fn futex_old(
     ))
 }
 
+#[inline]
+pub(crate) fn futex_waitv(
+    waiters: &[FutexWaitv<'_>],
+    timeout: Option<&Timespec>,
+    clockid: ClockId,
+) -> io::Result<usize> {
+    unsafe {
+        ret_usize(syscall!(
+            __NR_futex_waitv,
+            slice_just_addr(waiters),
+            pass_usize(waiters.len()),
+            c_uint(0),
+            timeout.map_or(core::ptr::null(), |timeout| timeout as *const Timespec),
+            clockid
+        ))
+    }
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 #[inline]
 pub(crate) fn setns(fd: BorrowedFd, nstype: c::c_int) -> io::Result<c::c_int> {
@@ -294,3 +338,32 @@ pub(crate) fn setns(fd: BorrowedFd, nstype: c::c_int) -> io::Result<c::c_int> {
 pub(crate) fn unshare(flags: crate::thread::UnshareFlags) -> io::Result<()> {
     unsafe { ret(syscall_readonly!(__NR_unshare, c_uint(flags.bits()))) }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn capget(
+    header: *mut crate::thread::CapUserHeader,
+    data: *mut crate::thread::CapUserData,
+) -> io::Result<()> {
+    ret(syscall!(__NR_capget, header, data))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub(crate) unsafe fn capset(
+    header: *mut crate::thread::CapUserHeader,
+    data: *const crate::thread::CapUserData,
+) -> io::Result<()> {
+    ret(syscall!(__NR_capset, header, data))
+}
+
+#[inline]
+pub(crate) unsafe fn rseq(rseq: &Rseq, flags: u32, sig: u32) -> io::Result<()> {
+    ret(syscall!(
+        __NR_rseq,
+        by_ref(rseq),
+        c_uint(size_of::<Rseq>() as u32),
+        c_uint(flags),
+        c_uint(sig)
+    ))
+}
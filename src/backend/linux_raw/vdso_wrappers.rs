@@ -83,6 +83,25 @@ pub(crate) fn clock_gettime_dynamic(which_clock: DynamicClockId<'_>) -> io::Resu
     }
 }
 
+#[cfg(feature = "thread")]
+#[inline]
+pub(crate) fn sched_getcpu() -> io::Result<usize> {
+    // Safety: `GETCPU` contains either null or the address of a function
+    // with an ABI like Linux's `getcpu`, and calling it has the side effect
+    // of writing to the result buffers, and no others.
+    unsafe {
+        let mut cpu = MaybeUninit::<u32>::uninit();
+        let callee = match transmute(GETCPU.load(Relaxed)) {
+            Some(callee) => callee,
+            None => init_getcpu(),
+        };
+        match callee(cpu.as_mut_ptr(), null_mut(), null_mut()) {
+            0 => Ok(cpu.assume_init() as usize),
+            r0 => Err(io::Errno::from_raw_os_error(r0.wrapping_neg())),
+        }
+    }
+}
+
 #[cfg(target_arch = "x86")]
 pub(super) mod x86_via_vdso {
     use super::{transmute, ArgReg, Relaxed, RetReg, SyscallNumber, A0, A1, A2, A3, A4, A5, R0};
@@ -208,6 +227,9 @@ pub(super) mod x86_via_vdso {
 
 type ClockGettimeType = unsafe extern "C" fn(c::c_int, *mut Timespec) -> c::c_int;
 
+#[cfg(feature = "thread")]
+type GetcpuType = unsafe extern "C" fn(*mut u32, *mut u32, *mut c::c_void) -> c::c_int;
+
 /// The underlying syscall functions are only called from asm, using the
 /// special syscall calling convention to pass arguments and return values,
 /// which the signature here doesn't reflect.
@@ -222,6 +244,15 @@ fn init_clock_gettime() -> ClockGettimeType {
     unsafe { transmute(CLOCK_GETTIME.load(Relaxed)) }
 }
 
+/// Initialize `GETCPU` and return its value.
+#[cfg(feature = "thread")]
+fn init_getcpu() -> GetcpuType {
+    init();
+    // Safety: Load the function address from static storage that we
+    // just initialized.
+    unsafe { transmute(GETCPU.load(Relaxed)) }
+}
+
 /// Initialize `SYSCALL` and return its value.
 #[cfg(target_arch = "x86")]
 fn init_syscall() -> SyscallType {
@@ -235,6 +266,8 @@ fn init_syscall() -> SyscallType {
 /// placeholder type, and cast it as needed.
 struct Function;
 static mut CLOCK_GETTIME: AtomicPtr<Function> = AtomicPtr::new(null_mut());
+#[cfg(feature = "thread")]
+static mut GETCPU: AtomicPtr<Function> = AtomicPtr::new(null_mut());
 #[cfg(target_arch = "x86")]
 static mut SYSCALL: AtomicPtr<Function> = AtomicPtr::new(null_mut());
 
@@ -248,6 +281,18 @@ unsafe extern "C" fn rustix_clock_gettime_via_syscall(
     }
 }
 
+#[cfg(feature = "thread")]
+unsafe extern "C" fn rustix_getcpu_via_syscall(
+    cpu: *mut u32,
+    node: *mut u32,
+    unused: *mut c::c_void,
+) -> c::c_int {
+    match ret(syscall!(__NR_getcpu, cpu, node, unused)) {
+        Ok(()) => 0,
+        Err(err) => err.raw_os_error().wrapping_neg(),
+    }
+}
+
 #[cfg(target_pointer_width = "32")]
 unsafe fn _rustix_clock_gettime_via_syscall(
     clockid: c::c_int,
@@ -322,6 +367,17 @@ fn minimal_init() {
                 Relaxed,
             )
             .ok();
+        #[cfg(feature = "thread")]
+        {
+            GETCPU
+                .compare_exchange(
+                    null_mut(),
+                    rustix_getcpu_via_syscall as *mut Function,
+                    Relaxed,
+                    Relaxed,
+                )
+                .ok();
+        }
         #[cfg(target_arch = "x86")]
         {
             SYSCALL
@@ -383,6 +439,33 @@ fn init() {
             }
         }
 
+        // Look up `getcpu` on the platforms whose vDSOs have it. Elsewhere,
+        // `GETCPU` keeps using the syscall.
+        #[cfg(feature = "thread")]
+        {
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            let ptr = vdso.sym(cstr!("LINUX_2.6"), cstr!("__vdso_getcpu"));
+            #[cfg(target_arch = "riscv64")]
+            let ptr = vdso.sym(cstr!("LINUX_4.15"), cstr!("__vdso_getcpu"));
+            #[cfg(target_arch = "powerpc64")]
+            let ptr = vdso.sym(cstr!("LINUX_2.6.15"), cstr!("__kernel_getcpu"));
+            #[cfg(not(any(
+                target_arch = "x86_64",
+                target_arch = "x86",
+                target_arch = "riscv64",
+                target_arch = "powerpc64",
+            )))]
+            let ptr: *mut c::c_void = null_mut();
+
+            if !ptr.is_null() {
+                // Safety: As above, store the computed function addresses in
+                // static storage.
+                unsafe {
+                    GETCPU.store(ptr.cast(), Relaxed);
+                }
+            }
+        }
+
         // On x86, also look up the vsyscall entry point.
         #[cfg(target_arch = "x86")]
         {
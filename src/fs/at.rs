@@ -222,6 +222,160 @@ pub fn renameat_with<P: path::Arg, Q: path::Arg, PFd: AsFd, QFd: AsFd>(
     })
 }
 
+/// The largest file handle the kernel will return, `MAX_HANDLE_SZ`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const MAX_HANDLE_SZ: usize = 128;
+
+/// The size of the `handle_bytes` and `handle_type` fields of
+/// `struct file_handle`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const FILE_HANDLE_HEADER_SIZE: usize = 8;
+
+/// A file handle, returned by [`name_to_handle_at`], which identifies a file
+/// independently of its path.
+///
+/// The contents of a handle are opaque and filesystem-specific. A handle
+/// stays valid across renames, and on most filesystems across reboots, so
+/// it may be stored and passed to [`open_by_handle_at`] later.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct FileHandle {
+    /// `struct file_handle`, in the kernel's layout.
+    raw: Vec<u8>,
+    mount_id: i32,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl FileHandle {
+    /// Constructs a `FileHandle` from its parts, such as ones previously
+    /// obtained from [`FileHandle::handle_type`], [`FileHandle::as_bytes`],
+    /// and [`FileHandle::mount_id`].
+    pub fn new(handle_type: i32, bytes: &[u8], mount_id: i32) -> Self {
+        let mut raw = Vec::with_capacity(FILE_HANDLE_HEADER_SIZE + bytes.len());
+        raw.extend_from_slice(&(bytes.len() as u32).to_ne_bytes());
+        raw.extend_from_slice(&handle_type.to_ne_bytes());
+        raw.extend_from_slice(bytes);
+        Self { raw, mount_id }
+    }
+
+    /// Returns the filesystem-specific type of the handle.
+    #[inline]
+    pub fn handle_type(&self) -> i32 {
+        let mut handle_type = [0_u8; 4];
+        handle_type.copy_from_slice(&self.raw[4..FILE_HANDLE_HEADER_SIZE]);
+        i32::from_ne_bytes(handle_type)
+    }
+
+    /// Returns the opaque contents of the handle.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.raw[FILE_HANDLE_HEADER_SIZE..]
+    }
+
+    /// Returns the ID of the mount containing the file, which corresponds to
+    /// the first field of `/proc/self/mountinfo`.
+    ///
+    /// This identifies which mount file descriptor to pass to
+    /// [`open_by_handle_at`].
+    #[inline]
+    pub fn mount_id(&self) -> i32 {
+        self.mount_id
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl core::fmt::Debug for FileHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FileHandle")
+            .field("handle_type", &self.handle_type())
+            .field("bytes", &self.as_bytes())
+            .field("mount_id", &self.mount_id)
+            .finish()
+    }
+}
+
+/// `name_to_handle_at(dirfd, path, &mut handle, &mut mount_id, flags)`—
+/// Returns a file handle and mount ID for a file.
+///
+/// `flags` may contain [`AtFlags::SYMLINK_FOLLOW`] and
+/// [`AtFlags::EMPTY_PATH`]. Filesystems which don't support file handles
+/// fail with [`io::Errno::OPNOTSUPP`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/name_to_handle_at.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[allow(unsafe_code)]
+pub fn name_to_handle_at<P: path::Arg, Fd: AsFd>(
+    dirfd: Fd,
+    path: P,
+    flags: AtFlags,
+) -> io::Result<FileHandle> {
+    path.into_with_c_str(|path| {
+        let mut capacity = MAX_HANDLE_SZ;
+        loop {
+            let mut raw = alloc::vec![0_u8; FILE_HANDLE_HEADER_SIZE + capacity];
+            raw[..4].copy_from_slice(&(capacity as u32).to_ne_bytes());
+            let mut mount_id = 0;
+
+            // Safety: `raw` holds a `struct file_handle` with room for
+            // `capacity` bytes of handle.
+            let result = unsafe {
+                backend::fs::syscalls::name_to_handle_at(
+                    dirfd.as_fd(),
+                    path,
+                    raw.as_mut_ptr().cast(),
+                    &mut mount_id,
+                    flags,
+                )
+            };
+
+            let mut handle_bytes = [0_u8; 4];
+            handle_bytes.copy_from_slice(&raw[..4]);
+            let handle_bytes = u32::from_ne_bytes(handle_bytes) as usize;
+            match result {
+                Ok(()) => {
+                    raw.truncate(FILE_HANDLE_HEADER_SIZE + handle_bytes);
+                    return Ok(FileHandle { raw, mount_id });
+                }
+                // On `EOVERFLOW`, the kernel reports the size it needs. Future
+                // kernels may exceed `MAX_HANDLE_SZ`.
+                Err(io::Errno::OVERFLOW) if handle_bytes > capacity => capacity = handle_bytes,
+                Err(err) => return Err(err),
+            }
+        }
+    })
+}
+
+/// `open_by_handle_at(mount_fd, handle, flags)`—Opens a file by its handle.
+///
+/// `mount_fd` is any file descriptor on the mount containing the file, such
+/// as the mount's root directory. This typically requires the
+/// `CAP_DAC_READ_SEARCH` capability.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/open_by_handle_at.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[allow(unsafe_code)]
+pub fn open_by_handle_at<Fd: AsFd>(
+    mount_fd: Fd,
+    handle: &FileHandle,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    // Safety: `handle.raw` holds a `struct file_handle`.
+    unsafe {
+        backend::fs::syscalls::open_by_handle_at(
+            mount_fd.as_fd(),
+            handle.raw.as_ptr().cast(),
+            flags,
+        )
+    }
+}
+
 /// `symlinkat(old_path, new_dirfd, new_path)`—Creates a symlink.
 ///
 /// # References
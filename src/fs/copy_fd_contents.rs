@@ -0,0 +1,251 @@
+//! Copying data between file descriptors with the fastest available method.
+
+use crate::fd::{AsFd, BorrowedFd};
+#[cfg(target_os = "linux")]
+use crate::fs::sendfile;
+use crate::fs::{copy_file_range, fstat, ioctl_ficlonerange, seek, tell, FileType};
+use crate::io::{self, pipe_with, read, splice, write, PipeFlags, SeekFrom, SpliceFlags};
+use alloc::vec;
+
+/// The most [`copy_file_range`] and [`sendfile`] are asked to copy at once.
+///
+/// Linux copies at most this much in one call anyway.
+const MAX_CHUNK: u64 = 0x7fff_f000;
+
+/// The size of the chunks moved through a pipe, and of the buffer used for
+/// `read` and `write`.
+const BUF_SIZE: usize = 64 * 1024;
+
+/// The method [`copy_fd_contents`] used to finish a copy.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CopyMethod {
+    /// `ioctl(FICLONERANGE)`—The data is shared copy-on-write, rather than
+    /// copied.
+    Reflink,
+    /// `copy_file_range`
+    CopyFileRange,
+    /// `sendfile`
+    Sendfile,
+    /// `splice` through a pipe
+    Splice,
+    /// `read` and `write`
+    ReadWrite,
+}
+
+/// Copies up to `len` bytes from `from` to `to`, using the fastest method
+/// the file descriptors and kernel support.
+///
+/// The data is read from the current file offset of `from` and written at
+/// the current file offset of `to`, and both offsets are advanced by the
+/// number of bytes copied, as if by `read` and `write`. Copying stops early
+/// at the end of `from`. Pass `u64::MAX` to copy everything that remains.
+///
+/// The methods tried, in order, are a reflink with `FICLONERANGE`,
+/// [`copy_file_range`], [`sendfile`], [`splice`] through a pipe, and `read`
+/// and `write`. Each is skipped when it fails in a way indicating that it's
+/// not supported for these file descriptors, such as with `EXDEV`, `EINVAL`,
+/// or `EOPNOTSUPP`, and when it reports the end of the file before copying
+/// anything, since some files in pseudo-filesystems such as procfs can only
+/// be read with `read`.
+///
+/// Returns the number of bytes copied and the method which finished the
+/// copy. Earlier methods may have copied part of the data before failing.
+///
+/// [`sendfile`]: crate::fs::sendfile
+pub fn copy_fd_contents<FromFd: AsFd, ToFd: AsFd>(
+    from: FromFd,
+    to: ToFd,
+    len: u64,
+) -> io::Result<(u64, CopyMethod)> {
+    let from = from.as_fd();
+    let to = to.as_fd();
+
+    if let Some(copied) = copy_with_reflink(from, to, len)? {
+        return Ok((copied, CopyMethod::Reflink));
+    }
+
+    let mut copied = 0;
+    if copy_with_copy_file_range(from, to, len, &mut copied)? {
+        return Ok((copied, CopyMethod::CopyFileRange));
+    }
+    #[cfg(target_os = "linux")]
+    if copy_with_sendfile(from, to, len, &mut copied)? {
+        return Ok((copied, CopyMethod::Sendfile));
+    }
+    if copy_with_splice(from, to, len, &mut copied)? {
+        return Ok((copied, CopyMethod::Splice));
+    }
+    copy_with_read_write(from, to, len, &mut copied)?;
+    Ok((copied, CopyMethod::ReadWrite))
+}
+
+/// Tests whether `err` means a method isn't supported for the file
+/// descriptors it was given.
+fn is_unsupported(err: io::Errno) -> bool {
+    matches!(
+        err,
+        io::Errno::NOSYS
+            | io::Errno::OPNOTSUPP
+            | io::Errno::XDEV
+            | io::Errno::INVAL
+            | io::Errno::NOTTY
+            | io::Errno::BADF
+            | io::Errno::SPIPE
+    )
+}
+
+/// Reflinks the rest of `from`, up to `len` bytes, returning `None` if
+/// that's not possible.
+fn copy_with_reflink(
+    from: BorrowedFd<'_>,
+    to: BorrowedFd<'_>,
+    len: u64,
+) -> io::Result<Option<u64>> {
+    // `FICLONERANGE` needs an exact length, so only regular files, whose
+    // sizes are known, are reflinked.
+    let stat = fstat(from)?;
+    if FileType::from_raw_mode(stat.st_mode) != FileType::RegularFile {
+        return Ok(None);
+    }
+    let (pos_in, pos_out) = match (tell(from), tell(to)) {
+        (Ok(pos_in), Ok(pos_out)) => (pos_in, pos_out),
+        (Err(err), _) | (_, Err(err)) if is_unsupported(err) => return Ok(None),
+        (Err(err), _) | (_, Err(err)) => return Err(err),
+    };
+    let size = stat.st_size as u64;
+    if pos_in >= size || len == 0 {
+        return Ok(None);
+    }
+    let n = len.min(size - pos_in);
+
+    match ioctl_ficlonerange(to, from, pos_in, n, pos_out) {
+        Ok(()) => {}
+        Err(err) if is_unsupported(err) => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    seek(from, SeekFrom::Start(pos_in + n))?;
+    seek(to, SeekFrom::Start(pos_out + n))?;
+    Ok(Some(n))
+}
+
+/// Copies with `copy_file_range`, returning `false` if it's not supported.
+fn copy_with_copy_file_range(
+    from: BorrowedFd<'_>,
+    to: BorrowedFd<'_>,
+    len: u64,
+    copied: &mut u64,
+) -> io::Result<bool> {
+    let mut progress = false;
+    while *copied < len {
+        let chunk = (len - *copied).min(MAX_CHUNK);
+        match copy_file_range(from, None, to, None, chunk) {
+            Ok(0) => return Ok(progress),
+            Ok(n) => {
+                *copied += n;
+                progress = true;
+            }
+            Err(io::Errno::INTR) => {}
+            Err(err) if !progress && is_unsupported(err) => return Ok(false),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+/// Copies with `sendfile`, returning `false` if it's not supported.
+#[cfg(target_os = "linux")]
+fn copy_with_sendfile(
+    from: BorrowedFd<'_>,
+    to: BorrowedFd<'_>,
+    len: u64,
+    copied: &mut u64,
+) -> io::Result<bool> {
+    let mut progress = false;
+    while *copied < len {
+        let chunk = (len - *copied).min(MAX_CHUNK) as usize;
+        match sendfile(to, from, None, chunk) {
+            Ok(0) => return Ok(progress),
+            Ok(n) => {
+                *copied += n as u64;
+                progress = true;
+            }
+            Err(io::Errno::INTR) => {}
+            Err(err) if !progress && is_unsupported(err) => return Ok(false),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+/// Copies with `splice` through a pipe, returning `false` if it's not
+/// supported.
+fn copy_with_splice(
+    from: BorrowedFd<'_>,
+    to: BorrowedFd<'_>,
+    len: u64,
+    copied: &mut u64,
+) -> io::Result<bool> {
+    let (reader, writer) = pipe_with(PipeFlags::CLOEXEC)?;
+    let mut progress = false;
+    while *copied < len {
+        let chunk = (len - *copied).min(BUF_SIZE as u64) as usize;
+        let n = match splice(from, None, &writer, None, chunk, SpliceFlags::empty()) {
+            Ok(0) => return Ok(progress),
+            Ok(n) => n,
+            Err(io::Errno::INTR) => continue,
+            Err(err) if !progress && is_unsupported(err) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+
+        let mut pending = n;
+        while pending > 0 {
+            match splice(&reader, None, to, None, pending, SpliceFlags::empty()) {
+                Ok(0) => return Err(io::Errno::IO),
+                Ok(m) => pending -= m,
+                Err(io::Errno::INTR) => {}
+                // The data is already out of `from`, so move what's in the
+                // pipe by hand, and then let `read` and `write` take over.
+                Err(err) if is_unsupported(err) => {
+                    copy_with_read_write(reader.as_fd(), to, pending as u64, &mut 0)?;
+                    *copied += n as u64;
+                    return Ok(false);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        *copied += n as u64;
+        progress = true;
+    }
+    Ok(true)
+}
+
+/// Copies with `read` and `write`.
+fn copy_with_read_write(
+    from: BorrowedFd<'_>,
+    to: BorrowedFd<'_>,
+    len: u64,
+    copied: &mut u64,
+) -> io::Result<()> {
+    let mut buf = vec![0_u8; BUF_SIZE];
+    while *copied < len {
+        let chunk = (len - *copied).min(BUF_SIZE as u64) as usize;
+        let n = match read(from, &mut buf[..chunk]) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(io::Errno::INTR) => continue,
+            Err(err) => return Err(err),
+        };
+
+        let mut written = 0;
+        while written < n {
+            match write(to, &buf[written..n]) {
+                Ok(0) => return Err(io::Errno::IO),
+                Ok(m) => written += m,
+                Err(io::Errno::INTR) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        *copied += n as u64;
+    }
+    Ok(())
+}
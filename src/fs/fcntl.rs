@@ -3,6 +3,10 @@
 //! a type-safe API, rustix makes them all separate functions so that they
 //! can have dedicated static type signatures.
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::backend::c::c_int;
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+use crate::process::Pid;
 use crate::{backend, io};
 use backend::fd::AsFd;
 use backend::fs::types::OFlags;
@@ -85,3 +89,342 @@ pub use backend::fs::types::SealFlags;
 pub fn fcntl_add_seals<Fd: AsFd>(fd: Fd, seals: SealFlags) -> io::Result<()> {
     backend::fs::syscalls::fcntl_add_seals(fd.as_fd(), seals)
 }
+
+/// `F_RDLCK`, `F_WRLCK`, or `F_UNLCK`—The type of a [`FileLock`].
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FileLockKind {
+    /// `F_RDLCK`—A shared lock, for reading.
+    Read,
+    /// `F_WRLCK`—An exclusive lock, for writing.
+    Write,
+    /// `F_UNLCK`—Remove a lock.
+    Unlock,
+}
+
+/// `SEEK_SET`, `SEEK_CUR`, or `SEEK_END`—The position that a
+/// [`FileLock`]'s `start` is relative to.
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FileLockWhence {
+    /// `SEEK_SET`—The start of the file.
+    Start,
+    /// `SEEK_CUR`—The current position of the file descriptor.
+    Current,
+    /// `SEEK_END`—The end of the file.
+    End,
+}
+
+/// `struct flock`—A byte-range lock, for use with [`fcntl_lock`] and
+/// related functions.
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[doc(alias = "flock")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FileLock {
+    /// `l_type`—The type of the lock.
+    pub kind: FileLockKind,
+    /// `l_whence`—The position that `start` is relative to.
+    pub whence: FileLockWhence,
+    /// `l_start`—The offset of the first byte of the range.
+    pub start: i64,
+    /// `l_len`—The length of the range, or 0 to extend to the end of the
+    /// file however large it grows.
+    pub len: i64,
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+impl FileLock {
+    /// Constructs a `FileLock` of `len` bytes starting at offset `start`
+    /// from the start of the file.
+    ///
+    /// As with `l_len`, a negative `len` describes the range of `-len` bytes
+    /// preceding `start`.
+    #[inline]
+    pub const fn new(kind: FileLockKind, start: i64, len: i64) -> Self {
+        Self {
+            kind,
+            whence: FileLockWhence::Start,
+            start,
+            len,
+        }
+    }
+}
+
+/// A lock that conflicts with a requested lock, returned by [`fcntl_getlk`]
+/// and [`fcntl_ofd_getlk`].
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FileLockConflict {
+    /// The conflicting lock. Its `whence` is always
+    /// [`FileLockWhence::Start`].
+    pub lock: FileLock,
+    /// `l_pid`—The process holding the lock, or `None` if it's an open file
+    /// description lock.
+    pub pid: Option<Pid>,
+}
+
+/// `fcntl(fd, F_SETLK, lock)`—Acquires or releases a process-associated
+/// byte-range lock, without waiting.
+///
+/// Fails with [`io::Errno::ACCESS`] or [`io::Errno::AGAIN`] if a
+/// conflicting lock is held by another process.
+///
+/// Process-associated locks are released when the process closes *any* file
+/// descriptor for the file, and they aren't exclusive between threads of a
+/// process. [`fcntl_ofd_lock`] avoids both problems.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[inline]
+#[doc(alias = "F_SETLK")]
+pub fn fcntl_lock<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_lock(fd.as_fd(), lock, false, false)
+}
+
+/// `fcntl(fd, F_SETLKW, lock)`—Acquires or releases a process-associated
+/// byte-range lock, waiting for any conflicting lock to be released.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[inline]
+#[doc(alias = "F_SETLKW")]
+pub fn fcntl_lock_wait<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_lock(fd.as_fd(), lock, false, true)
+}
+
+/// `fcntl(fd, F_GETLK, lock)`—Tests whether a process-associated byte-range
+/// lock could be acquired.
+///
+/// Returns the first lock that would conflict with `lock`, or `None` if
+/// `lock` could be acquired.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[inline]
+#[doc(alias = "F_GETLK")]
+pub fn fcntl_getlk<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<Option<FileLockConflict>> {
+    backend::fs::syscalls::fcntl_getlk(fd.as_fd(), lock, false)
+}
+
+/// `fcntl(fd, F_OFD_SETLK, lock)`—Acquires or releases an open file
+/// description byte-range lock, without waiting.
+///
+/// Open file description locks are associated with the open file
+/// description rather than the process, so they conflict between file
+/// descriptors opened separately, even within one process, and are only
+/// released when the last file descriptor referring to the open file
+/// description is closed.
+///
+/// Fails with [`io::Errno::AGAIN`] if a conflicting lock is held.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_OFD_SETLK")]
+pub fn fcntl_ofd_lock<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_lock(fd.as_fd(), lock, true, false)
+}
+
+/// `fcntl(fd, F_OFD_SETLKW, lock)`—Acquires or releases an open file
+/// description byte-range lock, waiting for any conflicting lock to be
+/// released.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_OFD_SETLKW")]
+pub fn fcntl_ofd_lock_wait<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_lock(fd.as_fd(), lock, true, true)
+}
+
+/// `fcntl(fd, F_OFD_GETLK, lock)`—Tests whether an open file description
+/// byte-range lock could be acquired.
+///
+/// Returns the first lock that would conflict with `lock`, or `None` if
+/// `lock` could be acquired.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_OFD_GETLK")]
+pub fn fcntl_ofd_getlk<Fd: AsFd>(fd: Fd, lock: &FileLock) -> io::Result<Option<FileLockConflict>> {
+    backend::fs::syscalls::fcntl_getlk(fd.as_fd(), lock, true)
+}
+
+/// `F_RDLCK`, `F_WRLCK`, or `F_UNLCK`—The type of a lease, for use with
+/// [`fcntl_setlease`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LeaseType {
+    /// `F_RDLCK`—A read lease, broken when the file is opened for writing
+    /// or truncated.
+    Read,
+    /// `F_WRLCK`—A write lease, broken when the file is opened at all.
+    Write,
+    /// `F_UNLCK`—No lease.
+    Unlock,
+}
+
+/// `fcntl(fd, F_GETLEASE)`—Returns the type of lease held on a file.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_GETLEASE")]
+pub fn fcntl_getlease<Fd: AsFd>(fd: Fd) -> io::Result<LeaseType> {
+    backend::fs::syscalls::fcntl_getlease(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETLEASE, lease)`—Acquires, changes, or releases a lease on
+/// a file.
+///
+/// When another process opens or truncates the file in a way that
+/// conflicts with the lease, the kernel sends the lease holder a signal, as
+/// configured with [`fcntl_setsig`], and blocks the other process until the
+/// lease is released or downgraded, or the lease-break timeout expires.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_SETLEASE")]
+pub fn fcntl_setlease<Fd: AsFd>(fd: Fd, lease: LeaseType) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setlease(fd.as_fd(), lease)
+}
+
+/// `struct f_owner_ex`—The target of the signals sent for I/O events on a
+/// file descriptor, for use with [`fcntl_setown_ex`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[doc(alias = "f_owner_ex")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FileOwner {
+    /// `F_OWNER_TID`—A specific thread.
+    Thread(Pid),
+    /// `F_OWNER_PID`—A process.
+    Process(Pid),
+    /// `F_OWNER_PGRP`—A process group.
+    ProcessGroup(Pid),
+}
+
+/// `fcntl(fd, F_GETOWN_EX)`—Returns the target of the signals sent for I/O
+/// events and lease breaks on a file descriptor.
+///
+/// Returns `None` if no owner is set.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_GETOWN_EX")]
+pub fn fcntl_getown_ex<Fd: AsFd>(fd: Fd) -> io::Result<Option<FileOwner>> {
+    backend::fs::syscalls::fcntl_getown_ex(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETOWN_EX, owner)`—Sets the target of the signals sent for
+/// I/O events and lease breaks on a file descriptor.
+///
+/// If `owner` is `None`, this clears the owner, so that no signals are sent.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_SETOWN_EX")]
+pub fn fcntl_setown_ex<Fd: AsFd>(fd: Fd, owner: Option<FileOwner>) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setown_ex(fd.as_fd(), owner)
+}
+
+/// `fcntl(fd, F_GETSIG)`—Returns the signal sent for I/O events and lease
+/// breaks on a file descriptor.
+///
+/// Returns the raw signal number, or 0 if the default, `SIGIO`, is in use.
+/// This may be a real-time signal, which [`Signal`] can't represent; use
+/// [`Signal::from_raw`] to convert other values. The result can be passed
+/// back to [`fcntl_setsig`].
+///
+/// # References
+///  - [Linux]
+///
+/// [`Signal`]: crate::process::Signal
+/// [`Signal::from_raw`]: crate::process::Signal::from_raw
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_GETSIG")]
+pub fn fcntl_getsig<Fd: AsFd>(fd: Fd) -> io::Result<c_int> {
+    backend::fs::syscalls::fcntl_getsig(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETSIG, sig)`—Sets the signal sent for I/O events and lease
+/// breaks on a file descriptor.
+///
+/// `sig` is a raw signal number, such as `Signal::Usr1 as c_int` or a
+/// real-time signal. If it's 0, this restores the default, `SIGIO`. Setting a
+/// signal explicitly, even `SIGIO`, makes extra information available to
+/// handlers installed with `SA_SIGINFO`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_SETSIG")]
+pub fn fcntl_setsig<Fd: AsFd>(fd: Fd, sig: c_int) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setsig(fd.as_fd(), sig)
+}
+
+/// `fcntl(fd, F_NOTIFY, flags)`—Requests a signal when the directory `fd`,
+/// or the files in it, change.
+///
+/// The signal is `SIGIO`, or the signal configured with [`fcntl_setsig`].
+/// Unless `flags` includes [`DnotifyFlags::MULTISHOT`], the request is
+/// removed after the first notification. Passing empty `flags` cancels all
+/// requests on `fd`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_NOTIFY")]
+pub fn fcntl_notify<Fd: AsFd>(fd: Fd, flags: DnotifyFlags) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_notify(fd.as_fd(), flags)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use backend::fs::types::DnotifyFlags;
@@ -22,6 +22,9 @@ pub use backend::fs::types::FlockOperation;
 )))]
 pub use backend::fs::types::FallocateFlags;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use backend::fs::types::SyncFileRangeFlags;
+
 pub use backend::fs::types::Stat;
 
 #[cfg(not(any(
@@ -110,6 +113,60 @@ pub fn tell<Fd: AsFd>(fd: Fd) -> io::Result<u64> {
     backend::fs::syscalls::tell(fd.as_fd())
 }
 
+/// `lseek(fd, offset, SEEK_DATA)`—Returns the offset of the first byte of
+/// data at or after `offset`.
+///
+/// This also moves the file descriptor's position to the returned offset.
+/// Fails with [`io::Errno::NXIO`] if there is no data at or after `offset`.
+///
+/// On filesystems that don't track holes, the whole file is reported as data.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/lseek.2.html
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+#[inline]
+#[doc(alias = "lseek")]
+#[doc(alias = "SEEK_DATA")]
+pub fn seek_data<Fd: AsFd>(fd: Fd, offset: u64) -> io::Result<u64> {
+    backend::fs::syscalls::seek_data(fd.as_fd(), offset)
+}
+
+/// `lseek(fd, offset, SEEK_HOLE)`—Returns the offset of the first byte of
+/// the first hole at or after `offset`.
+///
+/// This also moves the file descriptor's position to the returned offset.
+/// The end of a file is always considered to be a hole, so this returns the
+/// file size if there are no holes after `offset`. Fails with
+/// [`io::Errno::NXIO`] if `offset` is at or beyond the end of the file.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/lseek.2.html
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+#[inline]
+#[doc(alias = "lseek")]
+#[doc(alias = "SEEK_HOLE")]
+pub fn seek_hole<Fd: AsFd>(fd: Fd, offset: u64) -> io::Result<u64> {
+    backend::fs::syscalls::seek_hole(fd.as_fd(), offset)
+}
+
 /// `fchmod(fd)`—Sets open file or directory permissions.
 ///
 /// This implementation does not support `O_PATH` file descriptors, even on
@@ -324,6 +381,74 @@ pub fn fdatasync<Fd: AsFd>(fd: Fd) -> io::Result<()> {
     backend::fs::syscalls::fdatasync(fd.as_fd())
 }
 
+/// `syncfs(fd)`—Ensures that all data and metadata of the filesystem
+/// containing `fd` is written to the underlying storage device.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/syncfs.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn syncfs<Fd: AsFd>(fd: Fd) -> io::Result<()> {
+    backend::fs::syscalls::syncfs(fd.as_fd())
+}
+
+/// `sync()`—Schedules all filesystem data and metadata to be written to the
+/// underlying storage devices.
+///
+/// On Linux, this also waits for the writes to complete.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/sync.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/sync.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn sync() {
+    backend::fs::syscalls::sync()
+}
+
+/// `sync_file_range(fd, offset, nbytes, flags)`—Starts or waits for
+/// writeback of a range of a file's dirty pages.
+///
+/// A `nbytes` of 0 means through the end of the file. This neither writes
+/// metadata nor flushes the device's write cache, so it is not a substitute
+/// for [`fsync`] or [`fdatasync`] for durability; it's useful for limiting
+/// the amount of dirty data in the page cache.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/sync_file_range.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn sync_file_range<Fd: AsFd>(
+    fd: Fd,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    backend::fs::syscalls::sync_file_range(fd.as_fd(), offset, nbytes, flags)
+}
+
+/// `readahead(fd, offset, count)`—Starts reading a range of a file into the
+/// page cache.
+///
+/// This returns without waiting for the reads to complete.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/readahead.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn readahead<Fd: AsFd>(fd: Fd, offset: u64, count: usize) -> io::Result<()> {
+    backend::fs::syscalls::readahead(fd.as_fd(), offset, count)
+}
+
 /// `ftruncate(fd, length)`—Sets the length of a file.
 ///
 /// # References
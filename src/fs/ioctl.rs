@@ -0,0 +1,520 @@
+//! Filesystem-oriented `ioctl`s.
+
+#![allow(unsafe_code)]
+
+use crate::backend::c;
+use crate::fd::{AsFd, AsRawFd, BorrowedFd};
+use crate::{backend, io};
+use alloc::vec;
+use alloc::vec::Vec;
+use bitflags::bitflags;
+use core::convert::TryInto;
+use core::fmt;
+use core::mem::size_of;
+use linux_raw_sys::general::{
+    file_clone_range, file_dedupe_range, file_dedupe_range_info, fsxattr,
+    FILE_DEDUPE_RANGE_DIFFERS, FILE_DEDUPE_RANGE_SAME,
+};
+
+/// `ioctl(fd, FICLONE, src_fd)`—Makes `fd` a copy-on-write clone of all of
+/// `src_fd`'s contents.
+///
+/// Both files must be on the same filesystem, which must support sharing
+/// extents, such as Btrfs or XFS. Callers can fall back to
+/// [`copy_file_range`] when this fails with [`io::Errno::OPNOTSUPP`],
+/// [`io::Errno::XDEV`], or [`io::Errno::INVAL`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_ficlone.2.html
+/// [`copy_file_range`]: crate::fs::copy_file_range
+#[inline]
+#[doc(alias = "FICLONE")]
+pub fn ioctl_ficlone<Fd: AsFd, SrcFd: AsFd>(fd: Fd, src_fd: SrcFd) -> io::Result<()> {
+    backend::fs::syscalls::ioctl_ficlone(fd.as_fd(), src_fd.as_fd())
+}
+
+/// `ioctl(fd, FICLONERANGE, &file_clone_range { .. })`—Makes a range of
+/// `fd` a copy-on-write clone of a range of `src_fd`.
+///
+/// A `src_length` of 0 clones through the end of `src_fd`. Offsets and
+/// lengths generally must be aligned to the filesystem block size.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_ficlonerange.2.html
+#[inline]
+#[doc(alias = "FICLONERANGE")]
+pub fn ioctl_ficlonerange<Fd: AsFd, SrcFd: AsFd>(
+    fd: Fd,
+    src_fd: SrcFd,
+    src_offset: u64,
+    src_length: u64,
+    dest_offset: u64,
+) -> io::Result<()> {
+    let range = file_clone_range {
+        src_fd: src_fd.as_fd().as_raw_fd().into(),
+        src_offset,
+        src_length,
+        dest_offset,
+    };
+    backend::fs::syscalls::ioctl_ficlonerange(fd.as_fd(), &range)
+}
+
+/// A destination for [`ioctl_fideduperange`].
+#[derive(Debug, Copy, Clone)]
+pub struct FileDedupeRange<'a> {
+    /// `dest_fd`—The file to share extents with the source, which must be
+    /// open for writing.
+    pub dest_fd: BorrowedFd<'a>,
+    /// `dest_offset`—The offset of the range in `dest_fd`.
+    pub dest_offset: u64,
+}
+
+/// The outcome of [`ioctl_fideduperange`] for one destination.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FileDedupeResult {
+    /// `FILE_DEDUPE_RANGE_SAME`—The contents were identical, and this many
+    /// bytes now share extents with the source.
+    Deduped(u64),
+    /// `FILE_DEDUPE_RANGE_DIFFERS`—The contents differed, so nothing was
+    /// shared.
+    Differs,
+    /// Deduplication failed for this destination.
+    Failed(io::Errno),
+}
+
+/// `ioctl(src_fd, FIDEDUPERANGE, &file_dedupe_range { .. })`—Shares
+/// extents between a range of `src_fd` and ranges of other files whose
+/// contents are identical.
+///
+/// Returns one [`FileDedupeResult`] for each entry of `dests`, in order. The
+/// kernel may process fewer than `src_length` bytes per call, and limits
+/// the number of destinations per call.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_fideduperange.2.html
+#[doc(alias = "FIDEDUPERANGE")]
+pub fn ioctl_fideduperange<Fd: AsFd>(
+    src_fd: Fd,
+    src_offset: u64,
+    src_length: u64,
+    dests: &[FileDedupeRange<'_>],
+) -> io::Result<Vec<FileDedupeResult>> {
+    let dest_count: u16 = dests.len().try_into().map_err(|_| io::Errno::INVAL)?;
+
+    // `struct file_dedupe_range` is followed by a flexible array of
+    // `struct file_dedupe_range_info`. Allocate them together, using `u64`
+    // for alignment.
+    let size = size_of::<file_dedupe_range>() + dests.len() * size_of::<file_dedupe_range_info>();
+    let mut buf = vec![0_u64; (size + size_of::<u64>() - 1) / size_of::<u64>()];
+    let range = buf.as_mut_ptr().cast::<file_dedupe_range>();
+
+    // Safety: `buf` is large enough and sufficiently aligned for the header
+    // and `dest_count` entries, and zero-initialized.
+    unsafe {
+        (*range).src_offset = src_offset;
+        (*range).src_length = src_length;
+        (*range).dest_count = dest_count;
+        let info = (*range).info.as_mut_ptr();
+        for (i, dest) in dests.iter().enumerate() {
+            let info = &mut *info.add(i);
+            info.dest_fd = dest.dest_fd.as_raw_fd().into();
+            info.dest_offset = dest.dest_offset;
+        }
+
+        backend::fs::syscalls::ioctl_fideduperange(src_fd.as_fd(), range)?;
+
+        let info = (*range).info.as_ptr();
+        Ok((0..dests.len())
+            .map(|i| {
+                let info = &*info.add(i);
+                match info.status {
+                    status if status < 0 => {
+                        FileDedupeResult::Failed(io::Errno::from_raw_os_error(-status))
+                    }
+                    status if status as u32 == FILE_DEDUPE_RANGE_SAME => {
+                        FileDedupeResult::Deduped(info.bytes_deduped)
+                    }
+                    status if status as u32 == FILE_DEDUPE_RANGE_DIFFERS => {
+                        FileDedupeResult::Differs
+                    }
+                    _ => FileDedupeResult::Failed(io::Errno::INVAL),
+                }
+            })
+            .collect())
+    }
+}
+
+pub use backend::fs::types::InodeFlags;
+
+/// `ioctl(fd, FS_IOC_GETFLAGS)`—Returns the inode flags of `fd`.
+///
+/// Bits not known to rustix are preserved, so that the flags round-trip
+/// through [`ioctl_setflags`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_iflags.2.html
+#[inline]
+#[doc(alias = "FS_IOC_GETFLAGS")]
+pub fn ioctl_getflags<Fd: AsFd>(fd: Fd) -> io::Result<InodeFlags> {
+    let flags = backend::fs::syscalls::ioctl_getflags(fd.as_fd())?;
+    Ok(unsafe { InodeFlags::from_bits_unchecked(flags) })
+}
+
+/// `ioctl(fd, FS_IOC_SETFLAGS, &flags)`—Sets the inode flags of `fd`.
+///
+/// Setting [`InodeFlags::IMMUTABLE`] or [`InodeFlags::APPEND`] requires
+/// `CAP_LINUX_IMMUTABLE`. Callers should usually read the current flags with
+/// [`ioctl_getflags`] and modify them, rather than setting flags from
+/// scratch.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_iflags.2.html
+#[inline]
+#[doc(alias = "FS_IOC_SETFLAGS")]
+pub fn ioctl_setflags<Fd: AsFd>(fd: Fd, flags: InodeFlags) -> io::Result<()> {
+    backend::fs::syscalls::ioctl_setflags(fd.as_fd(), flags.bits())
+}
+
+pub use backend::fs::types::FsXattrFlags;
+
+/// Extended inode attributes, for use with [`ioctl_fsgetxattr`] and
+/// [`ioctl_fssetxattr`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FsXattr {
+    /// `fsx_xflags`—Extended inode flags.
+    pub xflags: FsXattrFlags,
+    /// `fsx_extsize`—The extent size hint, in bytes, used when
+    /// [`FsXattrFlags::EXTSIZE`] is set.
+    pub extsize: u32,
+    /// `fsx_nextents`—The number of data extents. This is ignored by
+    /// [`ioctl_fssetxattr`].
+    pub nextents: u32,
+    /// `fsx_projid`—The project ID, used for project quotas.
+    pub projid: u32,
+    /// `fsx_cowextsize`—The copy-on-write extent size hint, in bytes, used
+    /// when [`FsXattrFlags::COWEXTSIZE`] is set.
+    pub cowextsize: u32,
+}
+
+/// `ioctl(fd, FS_IOC_FSGETXATTR)`—Returns the extended inode attributes of
+/// `fd`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_xfs_fsgetxattr.2.html
+#[doc(alias = "FS_IOC_FSGETXATTR")]
+pub fn ioctl_fsgetxattr<Fd: AsFd>(fd: Fd) -> io::Result<FsXattr> {
+    let attr = backend::fs::syscalls::ioctl_fsgetxattr(fd.as_fd())?;
+    Ok(FsXattr {
+        // Preserve bits rustix doesn't know about, as with `ioctl_getflags`.
+        xflags: unsafe { FsXattrFlags::from_bits_unchecked(attr.fsx_xflags) },
+        extsize: attr.fsx_extsize,
+        nextents: attr.fsx_nextents,
+        projid: attr.fsx_projid,
+        cowextsize: attr.fsx_cowextsize,
+    })
+}
+
+/// `ioctl(fd, FS_IOC_FSSETXATTR, &fsxattr { .. })`—Sets the extended inode
+/// attributes of `fd`.
+///
+/// Changing [`FsXattr::projid`] requires privilege outside of the initial
+/// user namespace. Callers should usually read the current attributes with
+/// [`ioctl_fsgetxattr`] and modify them.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_xfs_fsgetxattr.2.html
+#[doc(alias = "FS_IOC_FSSETXATTR")]
+pub fn ioctl_fssetxattr<Fd: AsFd>(fd: Fd, attr: &FsXattr) -> io::Result<()> {
+    let attr = fsxattr {
+        fsx_xflags: attr.xflags.bits(),
+        fsx_extsize: attr.extsize,
+        fsx_nextents: attr.nextents,
+        fsx_projid: attr.projid,
+        fsx_cowextsize: attr.cowextsize,
+        fsx_pad: Default::default(),
+    };
+    backend::fs::syscalls::ioctl_fssetxattr(fd.as_fd(), &attr)
+}
+
+/// The number of extents [`FiemapExtents`] requests per `ioctl`.
+const FIEMAP_EXTENTS_PER_CALL: usize = 32;
+
+bitflags! {
+    /// `FIEMAP_FLAG_*` constants for use with [`Fiemap`].
+    #[repr(transparent)]
+    pub struct FiemapFlags: u32 {
+        /// `FIEMAP_FLAG_SYNC`—Sync the file before mapping its extents.
+        const SYNC = 0x0000_0001;
+        /// `FIEMAP_FLAG_XATTR`—Map the extended attribute tree instead of
+        /// the file's data.
+        const XATTR = 0x0000_0002;
+        /// `FIEMAP_FLAG_CACHE`—Request caching of the extents.
+        const CACHE = 0x0000_0004;
+    }
+}
+
+bitflags! {
+    /// `FIEMAP_EXTENT_*` constants describing a [`FiemapExtent`].
+    #[repr(transparent)]
+    pub struct FiemapExtentFlags: u32 {
+        /// `FIEMAP_EXTENT_LAST`—This is the file's last extent.
+        const LAST = 0x0000_0001;
+        /// `FIEMAP_EXTENT_UNKNOWN`—The data location is unknown.
+        const UNKNOWN = 0x0000_0002;
+        /// `FIEMAP_EXTENT_DELALLOC`—Space is reserved by delayed
+        /// allocation, but not yet allocated. Implies `UNKNOWN`.
+        const DELALLOC = 0x0000_0004;
+        /// `FIEMAP_EXTENT_ENCODED`—The data is encoded, such as compressed.
+        const ENCODED = 0x0000_0008;
+        /// `FIEMAP_EXTENT_DATA_ENCRYPTED`—The data is encrypted. Implies
+        /// `ENCODED`.
+        const DATA_ENCRYPTED = 0x0000_0080;
+        /// `FIEMAP_EXTENT_NOT_ALIGNED`—The extent offsets may not be block
+        /// aligned.
+        const NOT_ALIGNED = 0x0000_0100;
+        /// `FIEMAP_EXTENT_DATA_INLINE`—The data is stored in a metadata
+        /// block. Implies `NOT_ALIGNED`.
+        const DATA_INLINE = 0x0000_0200;
+        /// `FIEMAP_EXTENT_DATA_TAIL`—Multiple files' data is packed into one
+        /// block. Implies `NOT_ALIGNED`.
+        const DATA_TAIL = 0x0000_0400;
+        /// `FIEMAP_EXTENT_UNWRITTEN`—Space is allocated, but not
+        /// initialized, and reads as zeros.
+        const UNWRITTEN = 0x0000_0800;
+        /// `FIEMAP_EXTENT_MERGED`—The filesystem doesn't track extents, and
+        /// this one was merged from contiguous blocks.
+        const MERGED = 0x0000_1000;
+        /// `FIEMAP_EXTENT_SHARED`—The space is shared with other files.
+        const SHARED = 0x0000_2000;
+    }
+}
+
+/// `struct fiemap_extent`—An extent of a file, reported by
+/// [`ioctl_fiemap`] or [`fiemap_extents`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FiemapExtent {
+    /// `fe_logical`—The byte offset of the extent within the file.
+    pub logical: u64,
+    /// `fe_physical`—The byte offset of the extent on the device. This is
+    /// meaningless if [`FiemapExtentFlags::UNKNOWN`] is set.
+    pub physical: u64,
+    /// `fe_length`—The length of the extent, in bytes.
+    pub length: u64,
+    reserved64: [u64; 2],
+    /// `fe_flags`—Flags describing the extent.
+    pub flags: FiemapExtentFlags,
+    reserved: [u32; 3],
+}
+
+impl Default for FiemapExtent {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            logical: 0,
+            physical: 0,
+            length: 0,
+            reserved64: [0; 2],
+            flags: FiemapExtentFlags::empty(),
+            reserved: [0; 3],
+        }
+    }
+}
+
+/// `struct fiemap`—A request for [`ioctl_fiemap`], followed by a
+/// caller-supplied buffer for the extents it reports.
+///
+/// The buffer is typically an array, as in `Fiemap<[FiemapExtent; 32]>`,
+/// which coerces to the `Fiemap<[FiemapExtent]>` that [`ioctl_fiemap`]
+/// takes, so the kernel writes the extents directly into it.
+#[repr(C)]
+pub struct Fiemap<E: ?Sized = [FiemapExtent]> {
+    start: u64,
+    length: u64,
+    flags: FiemapFlags,
+    mapped_extents: u32,
+    extent_count: u32,
+    reserved: u32,
+    extents: E,
+}
+
+impl<E> Fiemap<E> {
+    /// Constructs a request to map the extents of the byte range
+    /// `start..start + length` of a file, to be stored in `extents`.
+    #[inline]
+    pub const fn new(start: u64, length: u64, flags: FiemapFlags, extents: E) -> Self {
+        Self {
+            start,
+            length,
+            flags,
+            mapped_extents: 0,
+            extent_count: 0,
+            reserved: 0,
+            extents,
+        }
+    }
+}
+
+impl<E: AsRef<[FiemapExtent]> + ?Sized> Fiemap<E> {
+    /// Sets the byte range `start..start + length` to map, so that the
+    /// request can be reused.
+    #[inline]
+    pub fn set_range(&mut self, start: u64, length: u64) {
+        self.start = start;
+        self.length = length;
+    }
+
+    /// Returns the extents reported by the last call to [`ioctl_fiemap`].
+    #[inline]
+    pub fn extents(&self) -> &[FiemapExtent] {
+        let extents = self.extents.as_ref();
+        &extents[..(self.mapped_extents as usize).min(extents.len())]
+    }
+}
+
+impl<E: AsRef<[FiemapExtent]> + ?Sized> fmt::Debug for Fiemap<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fiemap")
+            .field("start", &self.start)
+            .field("length", &self.length)
+            .field("flags", &self.flags)
+            .field("extents", &self.extents())
+            .finish()
+    }
+}
+
+/// `ioctl(fd, FS_IOC_FIEMAP, fiemap)`—Maps the extents of a byte range of
+/// a file.
+///
+/// Up to `fiemap`'s capacity of extents overlapping the range are written
+/// into its buffer, where [`Fiemap::extents`] returns them, and the number
+/// written is returned. If the buffer is empty, the number of extents in
+/// the range is returned instead. Use [`fiemap_extents`] to iterate over all
+/// of a file's extents.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://www.kernel.org/doc/html/latest/filesystems/fiemap.html
+#[doc(alias = "FS_IOC_FIEMAP")]
+pub fn ioctl_fiemap<Fd: AsFd>(fd: Fd, fiemap: &mut Fiemap) -> io::Result<usize> {
+    fiemap.extent_count = fiemap
+        .extents
+        .len()
+        .try_into()
+        .map_err(|_| io::Errno::INVAL)?;
+    fiemap.mapped_extents = 0;
+
+    // Safety: `Fiemap` has the layout of `struct fiemap` followed by
+    // `extent_count` elements with the layout of `struct fiemap_extent`,
+    // which consist of integers that the kernel may freely overwrite.
+    unsafe {
+        backend::fs::syscalls::ioctl_fiemap(
+            fd.as_fd(),
+            (fiemap as *mut Fiemap).cast::<c::c_void>(),
+        )?;
+    }
+    Ok(fiemap.mapped_extents as usize)
+}
+
+/// Returns an iterator over all the extents of a file.
+///
+/// See [`FiemapExtents`] for details.
+#[inline]
+pub fn fiemap_extents<Fd: AsFd>(fd: Fd, flags: FiemapFlags) -> FiemapExtents<Fd> {
+    FiemapExtents {
+        fd,
+        fiemap: Fiemap::new(
+            0,
+            u64::MAX,
+            flags,
+            [FiemapExtent::default(); FIEMAP_EXTENTS_PER_CALL],
+        ),
+        pos: 0,
+        len: 0,
+        done: false,
+    }
+}
+
+/// An iterator over the extents of a file, returned by [`fiemap_extents`].
+///
+/// Extents are fetched in batches with [`ioctl_fiemap`], which is re-issued
+/// after the end of the previous batch until an extent marked
+/// [`FiemapExtentFlags::LAST`] is seen. Holes have no extents. If the file
+/// is modified during iteration, the extents may not be consistent.
+pub struct FiemapExtents<Fd: AsFd> {
+    fd: Fd,
+    fiemap: Fiemap<[FiemapExtent; FIEMAP_EXTENTS_PER_CALL]>,
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+impl<Fd: AsFd> FiemapExtents<Fd> {
+    /// Consumes the iterator, returning the file descriptor.
+    #[inline]
+    pub fn into_inner(self) -> Fd {
+        self.fd
+    }
+}
+
+impl<Fd: AsFd> Iterator for FiemapExtents<Fd> {
+    type Item = io::Result<FiemapExtent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.len {
+            if self.done {
+                return None;
+            }
+
+            self.pos = 0;
+            self.len = 0;
+            if let Err(err) = ioctl_fiemap(&self.fd, &mut self.fiemap) {
+                self.done = true;
+                return Some(Err(err));
+            }
+            self.len = self.fiemap.extents().len();
+
+            match self.fiemap.extents().last() {
+                // No extents means there's nothing after `start`.
+                None => {
+                    self.done = true;
+                    return None;
+                }
+                Some(last) => {
+                    let start = self.fiemap.start;
+                    let end = last.logical.saturating_add(last.length);
+                    // Stop at the last extent, or if the kernel makes no
+                    // progress, rather than looping forever.
+                    if last.flags.contains(FiemapExtentFlags::LAST)
+                        || end <= start
+                        || end == u64::MAX
+                    {
+                        self.done = true;
+                    }
+                    self.fiemap.set_range(end, u64::MAX - end);
+                }
+            }
+        }
+
+        let extent = self.fiemap.extents[self.pos];
+        self.pos += 1;
+        Some(Ok(extent))
+    }
+}
+
+impl<Fd: AsFd> core::iter::FusedIterator for FiemapExtents<Fd> {}
@@ -2,6 +2,8 @@ use crate::fd::OwnedFd;
 use crate::{backend, io, path};
 
 pub use backend::fs::types::MemfdFlags;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use backend::fs::types::MemfdSecretFlags;
 
 /// `memfd_create(path, flags)`
 ///
@@ -13,3 +15,25 @@ pub use backend::fs::types::MemfdFlags;
 pub fn memfd_create<P: path::Arg>(path: P, flags: MemfdFlags) -> io::Result<OwnedFd> {
     path.into_with_c_str(|path| backend::fs::syscalls::memfd_create(path, flags))
 }
+
+/// `memfd_secret(flags)`—Creates an anonymous file whose memory is hidden
+/// from the kernel and other processes.
+///
+/// The file can be sized with [`ftruncate`] and then mapped with `mmap` using
+/// `MAP_SHARED`. It can't be read or written with `read` or `write`.
+///
+/// This fails with [`io::Errno::NOSYS`] on kernels older than Linux 5.14, on
+/// architectures which don't support it, and on Linux versions before 6.5
+/// which weren't booted with `secretmem.enable=1`, so callers should be
+/// prepared to fall back to other kinds of memory.
+///
+/// # References
+///  - [Linux]
+///
+/// [`ftruncate`]: crate::fs::ftruncate
+/// [Linux]: https://man7.org/linux/man-pages/man2/memfd_secret.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn memfd_secret(flags: MemfdSecretFlags) -> io::Result<OwnedFd> {
+    backend::fs::syscalls::memfd_secret(flags)
+}
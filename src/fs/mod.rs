@@ -5,6 +5,8 @@ mod abs;
 mod at;
 mod constants;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod copy_fd_contents;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod copy_file_range;
 #[cfg(not(target_os = "redox"))]
 mod cwd;
@@ -31,6 +33,8 @@ pub(crate) mod fd;
 mod file_type;
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 mod getpath;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod ioctl;
 #[cfg(not(any(
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -55,8 +59,23 @@ mod openat2;
 mod raw_dir;
 #[cfg(target_os = "linux")]
 mod sendfile;
+#[cfg(target_os = "linux")]
+mod shm;
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+mod sparse;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod statx;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod tmpfile;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod walk;
 
 #[cfg(not(any(
     target_os = "haiku",
@@ -86,8 +105,6 @@ pub use at::fclonefileat;
     target_os = "wasi",
 )))]
 pub use at::mknodat;
-#[cfg(any(target_os = "android", target_os = "linux"))]
-pub use at::renameat_with;
 #[cfg(not(any(target_os = "redox", target_os = "wasi")))]
 pub use at::{chmodat, chownat};
 #[cfg(not(target_os = "redox"))]
@@ -95,6 +112,8 @@ pub use at::{
     linkat, mkdirat, openat, readlinkat, renameat, statat, symlinkat, unlinkat, utimensat, RawMode,
     UTIME_NOW, UTIME_OMIT,
 };
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use at::{name_to_handle_at, open_by_handle_at, renameat_with, FileHandle};
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 pub use constants::CloneFlags;
 /// `copyfile_flags_t`
@@ -106,6 +125,8 @@ pub use constants::{AtFlags, Dev};
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use constants::{MountFlags, MountPropagationFlags, RenameFlags, ResolveFlags};
 #[cfg(any(target_os = "android", target_os = "linux"))]
+pub use copy_fd_contents::{copy_fd_contents, CopyMethod};
+#[cfg(any(target_os = "android", target_os = "linux"))]
 pub use copy_file_range::copy_file_range;
 #[cfg(not(target_os = "redox"))]
 pub use cwd::cwd;
@@ -133,6 +154,17 @@ pub use fcntl::fcntl_dupfd_cloexec;
 ))]
 pub use fcntl::{fcntl_add_seals, fcntl_get_seals, SealFlags};
 pub use fcntl::{fcntl_getfd, fcntl_getfl, fcntl_setfd, fcntl_setfl};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use fcntl::{
+    fcntl_getlease, fcntl_getown_ex, fcntl_getsig, fcntl_notify, fcntl_ofd_getlk, fcntl_ofd_lock,
+    fcntl_ofd_lock_wait, fcntl_setlease, fcntl_setown_ex, fcntl_setsig, DnotifyFlags, FileOwner,
+    LeaseType,
+};
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+pub use fcntl::{
+    fcntl_getlk, fcntl_lock, fcntl_lock_wait, FileLock, FileLockConflict, FileLockKind,
+    FileLockWhence,
+};
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 pub use fcntl_darwin::{fcntl_fullfsync, fcntl_rdadvise};
 #[cfg(any(target_os = "ios", target_os = "macos"))]
@@ -181,10 +213,28 @@ pub use fd::{fstatfs, StatFs};
 )))]
 pub use fd::{fstatvfs, StatVfs, StatVfsMountFlags};
 #[cfg(any(target_os = "android", target_os = "linux"))]
+pub use fd::{readahead, sync, sync_file_range, syncfs, SyncFileRangeFlags};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+pub use fd::{seek_data, seek_hole};
+#[cfg(any(target_os = "android", target_os = "linux"))]
 pub use fd::{FsWord, NFS_SUPER_MAGIC, PROC_SUPER_MAGIC};
 pub use file_type::FileType;
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 pub use getpath::getpath;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use ioctl::{
+    fiemap_extents, ioctl_ficlone, ioctl_ficlonerange, ioctl_fideduperange, ioctl_fiemap,
+    ioctl_fsgetxattr, ioctl_fssetxattr, ioctl_getflags, ioctl_setflags, Fiemap, FiemapExtent,
+    FiemapExtentFlags, FiemapExtents, FiemapFlags, FileDedupeRange, FileDedupeResult, FsXattr,
+    FsXattrFlags, InodeFlags,
+};
 #[cfg(not(any(
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -202,6 +252,8 @@ pub use makedev::{major, makedev, minor};
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
 pub use memfd_create::{memfd_create, MemfdFlags};
 #[cfg(any(target_os = "android", target_os = "linux"))]
+pub use memfd_create::{memfd_secret, MemfdSecretFlags};
+#[cfg(any(target_os = "android", target_os = "linux"))]
 pub use mount::{bind_mount, change_mount, mount, move_mount, recursive_bind_mount, remount};
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use openat2::openat2;
@@ -209,8 +261,23 @@ pub use openat2::openat2;
 pub use raw_dir::{RawDir, RawDirEntry};
 #[cfg(target_os = "linux")]
 pub use sendfile::sendfile;
+#[cfg(target_os = "linux")]
+pub use shm::{shm_open, shm_unlink};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+pub use sparse::{sparse_regions, SparseRegions};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use statx::{statx, statx_with_fallback, Statx, StatxAttributes, StatxFlags, StatxTimestamp};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use tmpfile::TmpFile;
 #[cfg(any(target_os = "android", target_os = "linux"))]
-pub use statx::{statx, Statx, StatxFlags, StatxTimestamp};
+pub use walk::{Walk, WalkEntry, WalkOrder};
 
 /// Re-export types common to POSIX-ish platforms.
 #[cfg(feature = "std")]
@@ -0,0 +1,85 @@
+//! POSIX shared memory objects.
+//!
+//! These are implemented in terms of `openat` and `unlinkat` on `/dev/shm`,
+//! in the same way glibc implements them, so they interoperate with other
+//! processes using `shm_open` and `shm_unlink` from libc.
+
+use crate::fd::OwnedFd;
+use crate::ffi::CStr;
+use crate::fs::{cwd, openat, unlinkat, AtFlags, Mode, OFlags};
+use crate::{io, path};
+
+/// The directory shared memory objects live in.
+const SHM_DIR: &[u8] = b"/dev/shm/";
+
+/// The longest name of a shared memory object.
+const NAME_MAX: usize = 255;
+
+/// `shm_open(name, oflags, mode)`—Opens a POSIX shared memory object.
+///
+/// Any leading `/` characters in `name` are ignored, and the rest of `name`
+/// must be non-empty, must not be `.` or `..`, and must not contain any `/`
+/// characters. `oflags` should
+/// contain one of [`OFlags::RDONLY`] or [`OFlags::RDWR`], optionally with
+/// [`OFlags::CREATE`], [`OFlags::EXCL`], and [`OFlags::TRUNC`].
+/// [`OFlags::CLOEXEC`] and [`OFlags::NOFOLLOW`] are always set.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/shm_open.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/shm_open.3.html
+#[inline]
+pub fn shm_open<P: path::Arg>(name: P, oflags: OFlags, mode: Mode) -> io::Result<OwnedFd> {
+    name.into_with_c_str(|name| {
+        with_shm_path(name, |path| {
+            openat(
+                cwd(),
+                path,
+                oflags | OFlags::CLOEXEC | OFlags::NOFOLLOW,
+                mode,
+            )
+        })
+    })
+}
+
+/// `shm_unlink(name)`—Removes a POSIX shared memory object.
+///
+/// `name` is interpreted as it is by [`shm_open`].
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/shm_unlink.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/shm_unlink.3.html
+#[inline]
+pub fn shm_unlink<P: path::Arg>(name: P) -> io::Result<()> {
+    name.into_with_c_str(|name| with_shm_path(name, |path| unlinkat(cwd(), path, AtFlags::empty())))
+}
+
+/// Validate `name` as glibc's `__shm_get_name` does, also rejecting `.` and
+/// `..` as musl does, and call `f` with the path of the shared memory object
+/// it names.
+fn with_shm_path<T, F>(name: &CStr, f: F) -> io::Result<T>
+where
+    F: FnOnce(&CStr) -> io::Result<T>,
+{
+    let mut name = name.to_bytes();
+    while let [b'/', rest @ ..] = name {
+        name = rest;
+    }
+    if name.is_empty() || name == b"." || name == b".." || name.contains(&b'/') {
+        return Err(io::Errno::INVAL);
+    }
+    if name.len() > NAME_MAX {
+        return Err(io::Errno::NAMETOOLONG);
+    }
+
+    let mut buf = [0_u8; SHM_DIR.len() + NAME_MAX + 1];
+    buf[..SHM_DIR.len()].copy_from_slice(SHM_DIR);
+    buf[SHM_DIR.len()..][..name.len()].copy_from_slice(name);
+    let path = CStr::from_bytes_with_nul(&buf[..SHM_DIR.len() + name.len() + 1]).unwrap();
+    f(path)
+}
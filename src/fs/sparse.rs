@@ -0,0 +1,73 @@
+//! Iteration over the data regions of sparse files.
+
+use crate::fd::AsFd;
+use crate::fs::{seek_data, seek_hole};
+use crate::io;
+use core::ops::Range;
+
+/// Returns an iterator over the data regions of a file, skipping its holes.
+///
+/// See [`SparseRegions`] for details.
+#[inline]
+pub fn sparse_regions<Fd: AsFd>(fd: Fd) -> SparseRegions<Fd> {
+    SparseRegions {
+        fd,
+        offset: 0,
+        done: false,
+    }
+}
+
+/// An iterator over the data regions of a file, returned by
+/// [`sparse_regions`].
+///
+/// Each item is the byte range of a run of data, found with
+/// [`seek_data`] and [`seek_hole`]. Bytes outside these ranges are holes,
+/// which read as zeros. On filesystems that don't track holes, the whole file
+/// is reported as a single region.
+///
+/// Iterating moves the file descriptor's position, so it should not be
+/// shared with concurrent readers or writers. The regions reflect the file
+/// as it is when each one is found.
+pub struct SparseRegions<Fd: AsFd> {
+    fd: Fd,
+    offset: u64,
+    done: bool,
+}
+
+impl<Fd: AsFd> SparseRegions<Fd> {
+    /// Consumes the iterator, returning the file descriptor.
+    #[inline]
+    pub fn into_inner(self) -> Fd {
+        self.fd
+    }
+}
+
+impl<Fd: AsFd> Iterator for SparseRegions<Fd> {
+    type Item = io::Result<Range<u64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = seek_data(&self.fd, self.offset)
+            .and_then(|start| seek_hole(&self.fd, start).map(|end| start..end));
+        match result {
+            Ok(range) => {
+                self.offset = range.end;
+                Some(Ok(range))
+            }
+            // `ENXIO` means there's no more data after `offset`.
+            Err(io::Errno::NXIO) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<Fd: AsFd> core::iter::FusedIterator for SparseRegions<Fd> {}
@@ -2,11 +2,178 @@
 
 use crate::fd::{AsFd, BorrowedFd};
 use crate::ffi::CStr;
-use crate::fs::AtFlags;
+use crate::fs::{major, makedev, minor, AtFlags, Stat};
 use crate::{backend, io, path};
+use core::convert::TryInto;
 use core::sync::atomic::{AtomicU8, Ordering};
 
-pub use backend::fs::types::{Statx, StatxFlags, StatxTimestamp};
+pub use backend::fs::types::{Statx, StatxAttributes, StatxFlags, StatxTimestamp};
+
+impl Statx {
+    /// Returns the fields which were filled in, from `stx_mask`.
+    ///
+    /// This may omit fields which were requested, if the kernel or
+    /// filesystem doesn't support them, and may include fields which
+    /// weren't requested.
+    #[inline]
+    pub fn mask(&self) -> StatxFlags {
+        StatxFlags::from_bits_truncate(self.stx_mask)
+    }
+
+    /// Returns the file's attributes, from `stx_attributes`.
+    ///
+    /// Only the attributes in [`Statx::attributes_mask`] are meaningful; the
+    /// others are always clear.
+    #[inline]
+    pub fn attributes(&self) -> StatxAttributes {
+        StatxAttributes::from_bits_truncate(self.stx_attributes)
+    }
+
+    /// Returns the attributes which the filesystem supports, from
+    /// `stx_attributes_mask`.
+    #[inline]
+    pub fn attributes_mask(&self) -> StatxAttributes {
+        StatxAttributes::from_bits_truncate(self.stx_attributes_mask)
+    }
+
+    /// Returns the ID of the mount containing the file, as in
+    /// `/proc/self/mountinfo`, from `stx_mnt_id`.
+    ///
+    /// Returns `None` if [`StatxFlags::MNT_ID`] isn't in [`Statx::mask`].
+    #[inline]
+    pub fn mount_id(&self) -> Option<u64> {
+        if self.mask().contains(StatxFlags::MNT_ID) {
+            Some(self.stx_mnt_id)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the alignment required for user memory buffers for direct
+    /// I/O on the file, from `stx_dio_mem_align`.
+    ///
+    /// Returns `None` if [`StatxFlags::DIOALIGN`] isn't in [`Statx::mask`],
+    /// and `Some(0)` if direct I/O isn't supported.
+    #[inline]
+    pub fn dio_mem_align(&self) -> Option<u32> {
+        if self.mask().contains(StatxFlags::DIOALIGN) {
+            Some(self.stx_dio_mem_align)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the alignment required for file offsets and I/O segment
+    /// lengths for direct I/O on the file, from `stx_dio_offset_align`.
+    ///
+    /// Returns `None` if [`StatxFlags::DIOALIGN`] isn't in [`Statx::mask`],
+    /// and `Some(0)` if direct I/O isn't supported.
+    #[inline]
+    pub fn dio_offset_align(&self) -> Option<u32> {
+        if self.mask().contains(StatxFlags::DIOALIGN) {
+            Some(self.stx_dio_offset_align)
+        } else {
+            None
+        }
+    }
+
+    /// Converts this to a [`Stat`], as returned by [`statat`] and [`fstat`].
+    ///
+    /// Fields which aren't in [`Statx::mask`] are converted as they are.
+    /// This fails with [`io::Errno::OVERFLOW`] if a value doesn't fit in the
+    /// corresponding field of `Stat`.
+    ///
+    /// [`statat`]: crate::fs::statat
+    /// [`fstat`]: crate::fs::fstat
+    #[allow(unsafe_code)]
+    pub fn to_stat(&self) -> io::Result<Stat> {
+        // Safety: `Stat` contains only integers, for which zero is valid.
+        // Its exact layout varies between platforms, so start from zero and
+        // fill in the fields common to all of them.
+        let mut stat: Stat = unsafe { core::mem::zeroed() };
+        stat.st_dev = makedev(self.stx_dev_major, self.stx_dev_minor)
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_ino = self.stx_ino.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_mode = self.stx_mode.into();
+        stat.st_nlink = self.stx_nlink.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_uid = self.stx_uid;
+        stat.st_gid = self.stx_gid;
+        stat.st_rdev = makedev(self.stx_rdev_major, self.stx_rdev_minor)
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_size = self.stx_size.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_blksize = self
+            .stx_blksize
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_blocks = self
+            .stx_blocks
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_atime = self
+            .stx_atime
+            .tv_sec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_atime_nsec = self.stx_atime.tv_nsec.into();
+        stat.st_mtime = self
+            .stx_mtime
+            .tv_sec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_mtime_nsec = self.stx_mtime.tv_nsec.into();
+        stat.st_ctime = self
+            .stx_ctime
+            .tv_sec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        stat.st_ctime_nsec = self.stx_ctime.tv_nsec.into();
+        Ok(stat)
+    }
+
+    /// Converts a [`Stat`] to a `Statx`, with [`StatxFlags::BASIC_STATS`] in
+    /// its mask.
+    #[allow(unsafe_code)]
+    fn from_stat(stat: &Stat) -> io::Result<Self> {
+        // Safety: `Statx` contains only integers, for which zero is valid.
+        let mut statx: Self = unsafe { core::mem::zeroed() };
+        statx.stx_mask = StatxFlags::BASIC_STATS.bits();
+        statx.stx_blksize = stat
+            .st_blksize
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_nlink = stat.st_nlink.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_uid = stat.st_uid;
+        statx.stx_gid = stat.st_gid;
+        statx.stx_mode = stat.st_mode.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_ino = stat.st_ino.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_size = stat.st_size.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_blocks = stat.st_blocks.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_atime.tv_sec = stat.st_atime.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_atime.tv_nsec = stat
+            .st_atime_nsec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_mtime.tv_sec = stat.st_mtime.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_mtime.tv_nsec = stat
+            .st_mtime_nsec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_ctime.tv_sec = stat.st_ctime.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        statx.stx_ctime.tv_nsec = stat
+            .st_ctime_nsec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        let rdev = stat.st_rdev.into();
+        statx.stx_rdev_major = major(rdev);
+        statx.stx_rdev_minor = minor(rdev);
+        let dev = stat.st_dev.into();
+        statx.stx_dev_major = major(dev);
+        statx.stx_dev_minor = minor(dev);
+        Ok(statx)
+    }
+}
 
 /// `statx(dirfd, path, flags, mask, statxbuf)`
 ///
@@ -29,6 +196,42 @@ pub fn statx<P: path::Arg, Fd: AsFd>(
     path.into_with_c_str(|path| _statx(dirfd.as_fd(), path, flags, mask))
 }
 
+/// `statx(dirfd, path, flags, mask, statxbuf)`, falling back to `fstatat`
+/// if `statx` isn't available.
+///
+/// This is like [`statx`], except that where `statx` would fail with
+/// [`io::Errno::NOSYS`], it calls `fstatat` and converts the result to a
+/// `Statx`, with only [`StatxFlags::BASIC_STATS`] in its mask. Check
+/// [`Statx::mask`] for the fields which were filled in.
+///
+/// # References
+///  - [Linux `statx`]
+///  - [Linux `fstatat`]
+///
+/// [Linux `statx`]: https://man7.org/linux/man-pages/man2/statx.2.html
+/// [Linux `fstatat`]: https://man7.org/linux/man-pages/man2/fstatat.2.html
+#[inline]
+pub fn statx_with_fallback<P: path::Arg, Fd: AsFd>(
+    dirfd: Fd,
+    path: P,
+    flags: AtFlags,
+    mask: StatxFlags,
+) -> io::Result<Statx> {
+    path.into_with_c_str(|path| match _statx(dirfd.as_fd(), path, flags, mask) {
+        Err(io::Errno::NOSYS) => statx_via_statat(dirfd.as_fd(), path, flags),
+        result => result,
+    })
+}
+
+/// Emulates `statx` with `fstatat`.
+#[cold]
+fn statx_via_statat(dirfd: BorrowedFd<'_>, path: &CStr, flags: AtFlags) -> io::Result<Statx> {
+    // `fstatat` rejects the `AT_STATX_*` flags.
+    let flags = flags & (AtFlags::SYMLINK_NOFOLLOW | AtFlags::EMPTY_PATH);
+    let stat = backend::fs::syscalls::statat(dirfd, path, flags)?;
+    Statx::from_stat(&stat)
+}
+
 // Linux kernel prior to 4.11 old versions of Docker don't support `statx`. We
 // store the availability in a global to avoid unnecessary syscalls.
 //
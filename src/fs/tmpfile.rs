@@ -0,0 +1,238 @@
+//! Atomic file creation and replacement with `O_TMPFILE`.
+
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::ffi::{CStr, CString};
+use crate::fs::{
+    linkat, openat, renameat, renameat_with, unlinkat, AtFlags, Mode, OFlags, RenameFlags,
+};
+use crate::{backend, io, path};
+use alloc::format;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of temporary names to try before giving up with `EEXIST`.
+const MAX_ATTEMPTS: usize = 128;
+
+/// A counter to make temporary names distinct within a process.
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary file in a directory, which can be atomically given a name
+/// once it's been written.
+///
+/// On Linux 3.11 and later, on filesystems which support it, the file is
+/// created with `O_TMPFILE` and has no name until it's persisted, so no
+/// partially-written file is ever visible, and nothing is left behind if the
+/// process exits early. Otherwise, it's created with a random name starting
+/// with `.tmp` in the same directory, which is unlinked if the `TmpFile` is
+/// dropped without being persisted.
+///
+/// For the contents to be durable once persisted, call [`fsync`] on the
+/// `TmpFile` before persisting it, and on the directory afterwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rustix::fs::{cwd, fsync, openat, Mode, OFlags, TmpFile};
+/// # use rustix::io::write;
+/// # fn main() -> rustix::io::Result<()> {
+/// let dir = openat(cwd(), "etc", OFlags::RDONLY | OFlags::DIRECTORY, Mode::empty())?;
+/// let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR)?;
+/// write(&file, b"key = value\n")?;
+/// fsync(&file)?;
+/// file.persist("app.conf")?;
+/// fsync(&dir)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # References
+///  - [Linux]
+///
+/// [`fsync`]: crate::fs::fsync
+/// [Linux]: https://man7.org/linux/man-pages/man2/open.2.html
+#[doc(alias = "O_TMPFILE")]
+pub struct TmpFile<Fd: AsFd> {
+    dirfd: Fd,
+    file: OwnedFd,
+    /// The file's temporary name, or `None` if it's unnamed.
+    name: Option<CString>,
+}
+
+impl<Fd: AsFd> TmpFile<Fd> {
+    /// Creates a temporary file in the directory `dirfd`, open for reading
+    /// and writing, with the permissions `mode`.
+    pub fn new(dirfd: Fd, mode: Mode) -> io::Result<Self> {
+        match openat(
+            &dirfd,
+            cstr!("."),
+            OFlags::RDWR | OFlags::TMPFILE | OFlags::CLOEXEC,
+            mode,
+        ) {
+            Ok(file) => {
+                return Ok(Self {
+                    dirfd,
+                    file,
+                    name: None,
+                })
+            }
+            // Kernels without `O_TMPFILE` see `O_DIRECTORY` and fail with
+            // `EISDIR`, and filesystems without it fail with `EOPNOTSUPP`.
+            Err(io::Errno::ISDIR) | Err(io::Errno::OPNOTSUPP) | Err(io::Errno::INVAL) => {}
+            Err(err) => return Err(err),
+        }
+
+        let (file, name) = with_temp_name(|name| {
+            openat(
+                &dirfd,
+                name,
+                OFlags::RDWR | OFlags::CREATE | OFlags::EXCL | OFlags::CLOEXEC,
+                mode,
+            )
+        })?;
+        Ok(Self {
+            dirfd,
+            file,
+            name: Some(name),
+        })
+    }
+
+    /// Returns `true` if the file was created with `O_TMPFILE`, and so has
+    /// no name.
+    #[inline]
+    pub fn is_unnamed(&self) -> bool {
+        self.name.is_none()
+    }
+
+    /// Atomically gives the file the name `name` in its directory, replacing
+    /// any existing file with that name.
+    ///
+    /// An unnamed file is linked under a temporary name, which is then
+    /// renamed over `name`.
+    pub fn persist<P: path::Arg>(mut self, name: P) -> io::Result<()> {
+        name.into_with_c_str(|name| {
+            let tmp = match self.name.take() {
+                Some(tmp) => tmp,
+                None => with_temp_name(|tmp| self.link(tmp))?.1,
+            };
+            match renameat(&self.dirfd, &tmp, &self.dirfd, name) {
+                Ok(()) => Ok(()),
+                Err(err) => {
+                    // Leave the temporary name for `drop` to unlink.
+                    self.name = Some(tmp);
+                    Err(err)
+                }
+            }
+        })
+    }
+
+    /// Atomically gives the file the name `name` in its directory, failing
+    /// with `EEXIST` if a file with that name already exists.
+    pub fn persist_noclobber<P: path::Arg>(mut self, name: P) -> io::Result<()> {
+        name.into_with_c_str(|name| {
+            let tmp = match self.name.take() {
+                Some(tmp) => tmp,
+                None => return self.link(name),
+            };
+            let result =
+                match renameat_with(&self.dirfd, &tmp, &self.dirfd, name, RenameFlags::NOREPLACE) {
+                    // Filesystems without `RENAME_NOREPLACE` fail with `EINVAL`.
+                    // `linkat` doesn't replace its target either.
+                    Err(io::Errno::INVAL) | Err(io::Errno::NOSYS) => {
+                        linkat(&self.dirfd, &tmp, &self.dirfd, name, AtFlags::empty()).map(|()| {
+                            unlinkat(&self.dirfd, &tmp, AtFlags::empty()).ok();
+                        })
+                    }
+                    result => result,
+                };
+            if result.is_err() {
+                self.name = Some(tmp);
+            }
+            result
+        })
+    }
+
+    /// Links the unnamed file as `name`.
+    fn link(&self, name: &CStr) -> io::Result<()> {
+        match linkat(
+            &self.file,
+            cstr!(""),
+            &self.dirfd,
+            name,
+            AtFlags::EMPTY_PATH,
+        ) {
+            // Without `CAP_DAC_READ_SEARCH`, `AT_EMPTY_PATH` fails with
+            // `ENOENT`. Linking the file's `/proc/self/fd` entry, following
+            // the magic link, needs no privileges.
+            #[cfg(feature = "procfs")]
+            Err(io::Errno::NOENT) => {
+                let proc_self_fd = io::proc_self_fd()?;
+                linkat(
+                    proc_self_fd,
+                    path::DecInt::from_fd(&self.file).as_c_str(),
+                    &self.dirfd,
+                    name,
+                    AtFlags::SYMLINK_FOLLOW,
+                )
+            }
+            result => result,
+        }
+    }
+}
+
+impl<Fd: AsFd> AsFd for TmpFile<Fd> {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+impl<Fd: AsFd> Drop for TmpFile<Fd> {
+    fn drop(&mut self) {
+        if let Some(name) = &self.name {
+            unlinkat(&self.dirfd, name, AtFlags::empty()).ok();
+        }
+    }
+}
+
+impl<Fd: AsFd> fmt::Debug for TmpFile<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TmpFile")
+            .field("dirfd", &self.dirfd.as_fd())
+            .field("file", &self.file)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Calls `f` with fresh temporary names until it fails with something other
+/// than `EEXIST`.
+fn with_temp_name<T>(mut f: impl FnMut(&CStr) -> io::Result<T>) -> io::Result<(T, CString)> {
+    for _ in 0..MAX_ATTEMPTS {
+        let name = temp_name();
+        match f(&name) {
+            Ok(value) => return Ok((value, name)),
+            Err(io::Errno::EXIST) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Errno::EXIST)
+}
+
+/// Returns a name which is unlikely to be in use.
+///
+/// Names are created with `O_EXCL` or `linkat`, so they needn't be
+/// unpredictable to be safe, only unlikely to collide.
+fn temp_name() -> CString {
+    let pid = backend::process::syscalls::getpid().as_raw_nonzero().get() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    // Stack addresses vary between runs when ASLR is enabled.
+    let addr = &count as *const usize as usize;
+
+    // Mix the bits with the SplitMix64 finalizer.
+    let mut x = (pid << 32) ^ (count as u64) ^ (addr as u64).rotate_left(17);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+
+    CString::new(format!(".tmp{:016x}", x)).unwrap()
+}
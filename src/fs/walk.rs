@@ -0,0 +1,659 @@
+//! Recursive directory traversal.
+//!
+//! [`Walk`] descends only through file descriptors: each directory is opened
+//! relative to its parent with `O_NOFOLLOW`, and its device and inode
+//! numbers are recorded. A directory whose file descriptor was closed to stay
+//! within the open file descriptor budget is reopened one component at a
+//! time from its nearest open ancestor, and its device and inode numbers are
+//! checked, so renames and symlink swaps during the walk can't redirect it
+//! outside the tree.
+
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::ffi::{CStr, CString};
+use crate::fs::{
+    fstat, openat, openat2, statat, statx, AtFlags, FileType, Mode, OFlags, RawDir, ResolveFlags,
+    Statx, StatxFlags,
+};
+use crate::io;
+use alloc::borrow::ToOwned;
+use alloc::collections::VecDeque;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem::{take, MaybeUninit};
+
+/// The initial size of the buffer used to read directory entries.
+const DIR_BUF_SIZE: usize = 8192;
+
+/// The largest size the directory entry buffer grows to before giving up.
+const MAX_DIR_BUF_SIZE: usize = 1 << 20;
+
+/// The default limit on the number of directories [`Walk`] holds open.
+const DEFAULT_MAX_OPEN_FDS: usize = 64;
+
+/// The order in which [`Walk`] visits entries.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WalkOrder {
+    /// Visit each directory before its contents, descending into it before
+    /// visiting its following siblings.
+    DepthFirst,
+
+    /// Visit each directory after its contents, descending into it before
+    /// visiting its following siblings.
+    ///
+    /// This is the order needed to remove a tree: by the time a directory is
+    /// visited, everything in it has been visited.
+    DepthFirstPostOrder,
+
+    /// Visit all the entries at each depth before any at the next depth.
+    BreadthFirst,
+}
+
+/// A directory which has been opened during a walk.
+struct Node {
+    /// A unique identifier, used to look up the node's file descriptor.
+    id: u64,
+    /// The directory containing this one, or `None` for the root.
+    parent: Option<Rc<Node>>,
+    /// The name of this directory within `parent`.
+    name: CString,
+    dev: u64,
+    ino: u64,
+    depth: usize,
+}
+
+/// An entry read from a directory.
+struct Child {
+    name: CString,
+    file_type: FileType,
+    ino: u64,
+}
+
+/// A directory whose entries are being visited.
+struct Frame {
+    dir: Rc<Node>,
+    children: vec::IntoIter<Child>,
+    /// For `DepthFirstPostOrder`, the entry for `dir` itself, visited once
+    /// `children` is exhausted.
+    post: Option<Child>,
+}
+
+/// The entry most recently returned by [`Walk::next`].
+struct Current {
+    parent: Rc<Node>,
+    child: Child,
+    depth: usize,
+    cycle: bool,
+}
+
+enum Step {
+    Entry,
+    Done,
+}
+
+/// A recursive directory walker, similar to `fts`.
+///
+/// `Walk` visits every entry beneath a root directory, without following
+/// symlinks. Each entry is reported with a file descriptor for its parent
+/// directory, so that it can be operated on with `*at` functions without
+/// resolving any paths, which makes it suitable for removing or copying
+/// trees which may be concurrently modified by others.
+///
+/// Directory contents are read in full when a directory is first visited.
+/// At most [`Walk::max_open_fds`] directories are held open at a time,
+/// beyond the root; other directories are reopened as needed. Directories
+/// which would revisit one of their own ancestors, such as through a bind
+/// mount, are visited but not descended into; see [`WalkEntry::is_cycle`].
+///
+/// Entries are returned by [`Walk::next`], which borrows from the walker,
+/// so `Walk` does not implement `Iterator`.
+///
+/// # Examples
+///
+/// Removing everything inside a directory:
+///
+/// ```no_run
+/// # use rustix::fs::{cwd, openat, unlinkat, AtFlags, FileType, Mode, OFlags, Walk, WalkOrder};
+/// # fn main() -> rustix::io::Result<()> {
+/// let root = openat(cwd(), "dir", OFlags::RDONLY | OFlags::DIRECTORY, Mode::empty())?;
+/// let mut walk = Walk::new(&root)?.order(WalkOrder::DepthFirstPostOrder);
+/// while let Some(entry) = walk.next() {
+///     let entry = entry?;
+///     let flags = if entry.file_type() == FileType::Directory {
+///         AtFlags::REMOVEDIR
+///     } else {
+///         AtFlags::empty()
+///     };
+///     unlinkat(entry.parent_fd(), entry.file_name(), flags)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Walk<Fd: AsFd> {
+    root: Fd,
+    root_node: Rc<Node>,
+    order: WalkOrder,
+    max_depth: usize,
+    max_open_fds: usize,
+    /// Open directory file descriptors, least recently used first.
+    open: Vec<(u64, OwnedFd)>,
+    next_id: u64,
+    frames: Vec<Frame>,
+    /// For `BreadthFirst`, directories waiting to be read.
+    queue: VecDeque<Rc<Node>>,
+    /// A directory to descend into on the next call to `next`, unless
+    /// `skip_current_dir` is called.
+    descend: Option<Rc<Node>>,
+    /// An error to report on the next call to `next`.
+    pending_error: Option<io::Errno>,
+    current: Option<Current>,
+    use_openat2: bool,
+    started: bool,
+    buf: Vec<MaybeUninit<u8>>,
+}
+
+impl<Fd: AsFd> Walk<Fd> {
+    /// Creates a walker for the tree beneath the directory `root`.
+    ///
+    /// The root itself is not visited. `root` must be open for reading.
+    pub fn new(root: Fd) -> io::Result<Self> {
+        let stat = fstat(&root)?;
+        if FileType::from_raw_mode(stat.st_mode) != FileType::Directory {
+            return Err(io::Errno::NOTDIR);
+        }
+        let root_node = Rc::new(Node {
+            id: 0,
+            parent: None,
+            name: CString::default(),
+            dev: stat.st_dev as u64,
+            ino: stat.st_ino as u64,
+            depth: 0,
+        });
+        Ok(Self {
+            root,
+            root_node,
+            order: WalkOrder::DepthFirst,
+            max_depth: usize::MAX,
+            max_open_fds: DEFAULT_MAX_OPEN_FDS,
+            open: Vec::new(),
+            next_id: 1,
+            frames: Vec::new(),
+            queue: VecDeque::new(),
+            descend: None,
+            pending_error: None,
+            current: None,
+            use_openat2: true,
+            started: false,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Sets the order in which entries are visited. The default is
+    /// [`WalkOrder::DepthFirst`].
+    #[inline]
+    pub fn order(mut self, order: WalkOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets the maximum depth to visit. Entries in the root have depth 1.
+    /// Directories at the maximum depth are visited but not descended into.
+    #[inline]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum number of directories held open at a time, not
+    /// counting the root. The default is 64, and values less than 2 are
+    /// treated as 2.
+    ///
+    /// Lower limits cause directories to be reopened more often.
+    #[inline]
+    pub fn max_open_fds(mut self, max_open_fds: usize) -> Self {
+        self.max_open_fds = max_open_fds.max(2);
+        self
+    }
+
+    /// Don't descend into the directory most recently returned by
+    /// [`Walk::next`].
+    ///
+    /// This has no effect if that entry isn't a directory, or with
+    /// [`WalkOrder::DepthFirstPostOrder`], where a directory's contents are
+    /// visited before it.
+    #[inline]
+    pub fn skip_current_dir(&mut self) {
+        self.descend = None;
+    }
+
+    /// Returns the next entry.
+    ///
+    /// An error reading or opening a directory is returned in place of its
+    /// contents, and the walk continues with the next entry.
+    ///
+    /// Note: this interface will be broken to implement a stdlib iterator
+    /// API with GAT support once one becomes available.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<io::Result<WalkEntry<'_>>> {
+        match self.step() {
+            Ok(Step::Entry) => {}
+            Ok(Step::Done) => return None,
+            Err(err) => return Some(Err(err)),
+        }
+
+        let current = self.current.as_ref().unwrap();
+        Some(Ok(WalkEntry {
+            parent_fd: self.fd(&current.parent),
+            parent: &current.parent,
+            name: &current.child.name,
+            file_type: current.child.file_type,
+            ino: current.child.ino,
+            depth: current.depth,
+            cycle: current.cycle,
+        }))
+    }
+
+    /// Advances to the next entry, leaving it in `self.current`.
+    fn step(&mut self) -> io::Result<Step> {
+        self.current = None;
+
+        if !self.started {
+            self.started = true;
+            let root = Rc::clone(&self.root_node);
+            self.push_dir(root, None)?;
+        }
+
+        loop {
+            if let Some(dir) = self.descend.take() {
+                if self.order == WalkOrder::BreadthFirst {
+                    self.queue.push_back(dir);
+                } else {
+                    self.push_dir(dir, None)?;
+                }
+            }
+            if let Some(err) = self.pending_error.take() {
+                return Err(err);
+            }
+
+            let frame = match self.frames.last_mut() {
+                Some(frame) => frame,
+                None => match self.queue.pop_front() {
+                    Some(dir) => {
+                        self.push_dir(dir, None)?;
+                        continue;
+                    }
+                    None => return Ok(Step::Done),
+                },
+            };
+
+            let child = match frame.children.next() {
+                Some(child) => child,
+                None => {
+                    let frame = self.frames.pop().unwrap();
+                    self.close(&frame.dir);
+                    if let Some(child) = frame.post {
+                        let parent = Rc::clone(frame.dir.parent.as_ref().unwrap());
+                        self.ensure_open(&parent)?;
+                        self.current = Some(Current {
+                            depth: frame.dir.depth,
+                            parent,
+                            child,
+                            cycle: false,
+                        });
+                        return Ok(Step::Entry);
+                    }
+                    continue;
+                }
+            };
+
+            let parent = Rc::clone(&frame.dir);
+            let depth = parent.depth + 1;
+            let mut child = child;
+            self.ensure_open(&parent)?;
+
+            if child.file_type == FileType::Unknown {
+                let stat = statat(self.fd(&parent), &*child.name, AtFlags::SYMLINK_NOFOLLOW)?;
+                child.file_type = FileType::from_raw_mode(stat.st_mode);
+            }
+
+            let mut cycle = false;
+            if child.file_type == FileType::Directory && depth < self.max_depth {
+                match self.open_child(&parent, &child.name) {
+                    Ok((fd, dev, ino)) => {
+                        if is_ancestor(&parent, dev, ino) {
+                            cycle = true;
+                        } else {
+                            let node = Rc::new(Node {
+                                id: self.next_id,
+                                parent: Some(Rc::clone(&parent)),
+                                name: child.name.clone(),
+                                dev,
+                                ino,
+                                depth,
+                            });
+                            self.next_id += 1;
+                            self.insert(node.id, fd);
+
+                            if self.order == WalkOrder::DepthFirstPostOrder {
+                                self.push_dir(node, Some(child))?;
+                                continue;
+                            }
+                            self.descend = Some(node);
+                        }
+                    }
+                    // Report the directory, then the error opening it.
+                    Err(err) => self.pending_error = Some(err),
+                }
+                self.ensure_open(&parent)?;
+            }
+
+            self.current = Some(Current {
+                parent,
+                child,
+                depth,
+                cycle,
+            });
+            return Ok(Step::Entry);
+        }
+    }
+
+    /// Reads the contents of `dir`, and pushes a frame to visit them.
+    fn push_dir(&mut self, dir: Rc<Node>, post: Option<Child>) -> io::Result<()> {
+        let result = self.ensure_open(&dir).and_then(|()| self.read_dir(&dir));
+        let (children, result) = match result {
+            Ok(children) => (children, Ok(())),
+            Err(err) => (Vec::new(), Err(err)),
+        };
+        self.frames.push(Frame {
+            dir,
+            children: children.into_iter(),
+            post,
+        });
+        result
+    }
+
+    /// Reads all the entries of `dir`, other than `.` and `..`.
+    fn read_dir(&mut self, dir: &Node) -> io::Result<Vec<Child>> {
+        let mut buf = take(&mut self.buf);
+        if buf.is_empty() {
+            buf = vec![MaybeUninit::uninit(); DIR_BUF_SIZE];
+        }
+
+        // Read the root through a new open file description, so that the
+        // caller's file descriptor is left at its current position.
+        let root_dup = if dir.parent.is_none() {
+            match openat(
+                &self.root,
+                cstr!("."),
+                OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+                Mode::empty(),
+            ) {
+                Ok(fd) => Some(fd),
+                Err(err) => {
+                    self.buf = buf;
+                    return Err(err);
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut children = Vec::new();
+        let result = 'read: loop {
+            let can_grow = buf.len() < MAX_DIR_BUF_SIZE;
+            let fd = match &root_dup {
+                Some(fd) => fd.as_fd(),
+                None => self.fd(dir),
+            };
+            let mut iter = RawDir::new(fd, &mut buf);
+            let mut grow = false;
+            while let Some(entry) = iter.next() {
+                match entry {
+                    Ok(entry) => {
+                        let name = entry.file_name().to_bytes();
+                        if name == b"." || name == b".." {
+                            continue;
+                        }
+                        children.push(Child {
+                            name: entry.file_name().to_owned(),
+                            file_type: entry.file_type(),
+                            ino: entry.ino(),
+                        });
+                    }
+                    // `EINVAL` means the buffer is too small for the next
+                    // entry. `RawDir` continues where it left off when
+                    // recreated with the same file descriptor.
+                    Err(io::Errno::INVAL) if can_grow => {
+                        grow = true;
+                        break;
+                    }
+                    Err(err) => break 'read Err(err),
+                }
+            }
+            if !grow {
+                break Ok(children);
+            }
+            let len = buf.len() * 2;
+            buf.resize(len, MaybeUninit::uninit());
+        };
+
+        self.buf = buf;
+        result
+    }
+
+    /// Opens the directory `name` in `parent`, returning its file descriptor
+    /// and device and inode numbers.
+    fn open_child(&mut self, parent: &Node, name: &CStr) -> io::Result<(OwnedFd, u64, u64)> {
+        let oflags = OFlags::RDONLY | OFlags::DIRECTORY | OFlags::NOFOLLOW | OFlags::CLOEXEC;
+
+        let result = if self.use_openat2 {
+            openat2(
+                self.fd(parent),
+                name,
+                oflags,
+                Mode::empty(),
+                ResolveFlags::BENEATH | ResolveFlags::NO_SYMLINKS,
+            )
+        } else {
+            Err(io::Errno::NOSYS)
+        };
+        let fd = match result {
+            // Fall back to `openat` on kernels without `openat2`.
+            Err(io::Errno::NOSYS) => {
+                self.use_openat2 = false;
+                openat(self.fd(parent), name, oflags, Mode::empty())?
+            }
+            result => result?,
+        };
+
+        let stat = fstat(&fd)?;
+        Ok((fd, stat.st_dev as u64, stat.st_ino as u64))
+    }
+
+    /// Ensures that `dir` has an open file descriptor, reopening it and any
+    /// of its ancestors as needed.
+    fn ensure_open(&mut self, dir: &Rc<Node>) -> io::Result<()> {
+        if dir.parent.is_none() {
+            return Ok(());
+        }
+        if let Some(index) = self.open.iter().position(|(id, _)| *id == dir.id) {
+            let entry = self.open.remove(index);
+            self.open.push(entry);
+            return Ok(());
+        }
+
+        // Find the closed ancestors, and reopen them from the top down.
+        let mut closed = Vec::new();
+        let mut node = dir;
+        while let Some(parent) = &node.parent {
+            if self.open.iter().any(|(id, _)| *id == node.id) {
+                break;
+            }
+            closed.push(node);
+            node = parent;
+        }
+        for node in closed.into_iter().rev() {
+            let parent = node.parent.as_ref().unwrap();
+            let (fd, dev, ino) = self.open_child(parent, &node.name)?;
+            // As in `fts`, fail if the directory has been replaced since it
+            // was first opened.
+            if dev != node.dev || ino != node.ino {
+                return Err(io::Errno::NOENT);
+            }
+            self.insert(node.id, fd);
+        }
+        Ok(())
+    }
+
+    /// Records an open file descriptor for the directory `id`, closing the
+    /// least recently used one if the budget is exhausted.
+    fn insert(&mut self, id: u64, fd: OwnedFd) {
+        if self.open.len() >= self.max_open_fds {
+            self.open.remove(0);
+        }
+        self.open.push((id, fd));
+    }
+
+    /// Closes the file descriptor for `dir`, if it's open.
+    fn close(&mut self, dir: &Node) {
+        if let Some(index) = self.open.iter().position(|(id, _)| *id == dir.id) {
+            self.open.remove(index);
+        }
+    }
+
+    /// Returns the file descriptor for `dir`, which must be open.
+    fn fd(&self, dir: &Node) -> BorrowedFd<'_> {
+        if dir.parent.is_none() {
+            return self.root.as_fd();
+        }
+        self.open
+            .iter()
+            .find(|(id, _)| *id == dir.id)
+            .map(|(_, fd)| fd.as_fd())
+            .unwrap()
+    }
+}
+
+impl<Fd: AsFd> fmt::Debug for Walk<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Walk")
+            .field("root", &self.root.as_fd())
+            .field("order", &self.order)
+            .field("max_depth", &self.max_depth)
+            .field("max_open_fds", &self.max_open_fds)
+            .finish()
+    }
+}
+
+/// Tests whether `dir` or any of its ancestors has the given device and inode
+/// numbers.
+fn is_ancestor(dir: &Node, dev: u64, ino: u64) -> bool {
+    let mut node = Some(dir);
+    while let Some(dir) = node {
+        if dir.dev == dev && dir.ino == ino {
+            return true;
+        }
+        node = dir.parent.as_deref();
+    }
+    false
+}
+
+/// An entry visited by [`Walk`].
+pub struct WalkEntry<'a> {
+    parent_fd: BorrowedFd<'a>,
+    parent: &'a Node,
+    name: &'a CStr,
+    file_type: FileType,
+    ino: u64,
+    depth: usize,
+    cycle: bool,
+}
+
+impl<'a> WalkEntry<'a> {
+    /// Returns an open file descriptor for the directory containing this
+    /// entry.
+    #[inline]
+    pub fn parent_fd(&self) -> BorrowedFd<'a> {
+        self.parent_fd
+    }
+
+    /// Returns the file name of this entry within its parent directory.
+    #[inline]
+    pub fn file_name(&self) -> &'a CStr {
+        self.name
+    }
+
+    /// Returns the type of this entry.
+    ///
+    /// Symlinks are reported as [`FileType::Symlink`], and are never
+    /// followed.
+    #[inline]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns the inode number of this entry.
+    #[inline]
+    pub fn ino(&self) -> u64 {
+        self.ino
+    }
+
+    /// Returns the depth of this entry. Entries in the root have depth 1.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns `true` if this is a directory which was not descended into
+    /// because it is the same directory as one of its ancestors.
+    #[inline]
+    pub fn is_cycle(&self) -> bool {
+        self.cycle
+    }
+
+    /// `statx(parent_fd, file_name, AT_SYMLINK_NOFOLLOW, mask)`—Queries
+    /// metadata for this entry.
+    #[inline]
+    pub fn statx(&self, mask: StatxFlags) -> io::Result<Statx> {
+        statx(self.parent_fd, self.name, AtFlags::SYMLINK_NOFOLLOW, mask)
+    }
+
+    /// Returns the path of this entry relative to the root, for display or
+    /// for mirroring the tree elsewhere.
+    ///
+    /// The path is not used by the walker itself, and may not resolve to this
+    /// entry if the tree has been modified.
+    pub fn path(&self) -> CString {
+        let mut names = Vec::new();
+        let mut node = Some(self.parent);
+        while let Some(dir) = node {
+            if dir.parent.is_some() {
+                names.push(dir.name.as_bytes());
+            }
+            node = dir.parent.as_deref();
+        }
+
+        let mut path = Vec::new();
+        for name in names.into_iter().rev() {
+            path.extend_from_slice(name);
+            path.push(b'/');
+        }
+        path.extend_from_slice(self.name.to_bytes());
+        // The names came from `CStr`s, so they have no interior NULs.
+        CString::new(path).unwrap()
+    }
+}
+
+impl<'a> fmt::Debug for WalkEntry<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkEntry")
+            .field("parent_fd", &self.parent_fd)
+            .field("file_name", &self.name)
+            .field("file_type", &self.file_type)
+            .field("ino", &self.ino)
+            .field("depth", &self.depth)
+            .field("cycle", &self.cycle)
+            .finish()
+    }
+}
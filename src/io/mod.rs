@@ -62,6 +62,8 @@ pub use pipe::pipe;
     target_os = "wasi",
 )))]
 pub use pipe::PIPE_BUF;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use pipe::{fcntl_getpipe_sz, fcntl_setpipe_sz, splice, vmsplice, IoSliceRaw, SpliceFlags};
 #[cfg(not(any(
     windows,
     target_os = "aix",
@@ -71,8 +73,6 @@ pub use pipe::PIPE_BUF;
     target_os = "wasi"
 )))]
 pub use pipe::{pipe_with, PipeFlags};
-#[cfg(any(target_os = "android", target_os = "linux"))]
-pub use pipe::{splice, vmsplice, IoSliceRaw, SpliceFlags};
 pub use poll::{poll, PollFd, PollFlags};
 #[cfg(all(feature = "procfs", any(target_os = "android", target_os = "linux")))]
 pub use procfs::{
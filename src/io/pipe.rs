@@ -128,3 +128,33 @@ pub unsafe fn vmsplice<PipeFd: AsFd>(
 ) -> io::Result<usize> {
     backend::io::syscalls::vmsplice(fd.as_fd(), bufs, flags)
 }
+
+/// `fcntl(fd, F_GETPIPE_SZ)`—Returns the buffer capacity of a pipe.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_GETPIPE_SZ")]
+pub fn fcntl_getpipe_sz<Fd: AsFd>(fd: Fd) -> io::Result<usize> {
+    backend::io::syscalls::fcntl_getpipe_sz(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETPIPE_SZ, size)`—Sets the buffer capacity of a pipe.
+///
+/// The kernel rounds `size` up, to at least a page, and returns the
+/// resulting capacity. Unprivileged processes can't exceed the limit in
+/// `/proc/sys/fs/pipe-max-size`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[doc(alias = "F_SETPIPE_SZ")]
+pub fn fcntl_setpipe_sz<Fd: AsFd>(fd: Fd, size: usize) -> io::Result<usize> {
+    backend::io::syscalls::fcntl_setpipe_sz(fd.as_fd(), size)
+}
@@ -218,7 +218,12 @@ pub mod runtime;
 #[cfg(not(windows))]
 #[cfg(all(
     not(feature = "param"),
-    any(feature = "runtime", feature = "time", target_arch = "x86"),
+    any(
+        feature = "mm",
+        feature = "runtime",
+        feature = "time",
+        target_arch = "x86"
+    ),
 ))]
 pub(crate) mod param;
 #[cfg(not(windows))]
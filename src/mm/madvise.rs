@@ -6,6 +6,10 @@
 //! mutate the memory or have other side effects.
 #![allow(unsafe_code)]
 
+#[cfg(target_os = "linux")]
+use crate::fd::AsFd;
+#[cfg(target_os = "linux")]
+use crate::io::IoSlice;
 use crate::{backend, io};
 use core::ffi::c_void;
 
@@ -33,3 +37,33 @@ pub use backend::mm::types::Advice;
 pub unsafe fn madvise(addr: *mut c_void, len: usize, advice: Advice) -> io::Result<()> {
     backend::mm::syscalls::madvise(addr, len, advice)
 }
+
+/// `process_madvise(pidfd, ranges, advice, 0)`—Declares an expected access
+/// pattern for memory in another process.
+///
+/// `pidfd` is a pidfd referring to the process, and `ranges` describe
+/// address ranges in that process. Linux accepts only some forms of
+/// `advice` here, such as [`Advice::LinuxCold`], [`Advice::LinuxPageOut`],
+/// and [`Advice::WillNeed`].
+///
+/// Returns the number of bytes advised, which may be less than the total
+/// length of `ranges` if an error occurs partway through.
+///
+/// # Safety
+///
+/// If `pidfd` refers to the current process, `ranges` must describe memory
+/// that is appropriate to call [`madvise`] on with `advice`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/process_madvise.2.html
+#[cfg(target_os = "linux")]
+#[inline]
+pub unsafe fn process_madvise<Fd: AsFd>(
+    pidfd: Fd,
+    ranges: &[IoSlice<'_>],
+    advice: Advice,
+) -> io::Result<usize> {
+    backend::mm::syscalls::process_madvise(pidfd.as_fd(), ranges, advice)
+}
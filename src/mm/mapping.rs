@@ -0,0 +1,336 @@
+//! An owned memory mapping.
+//!
+//! # Safety
+//!
+//! `Mapping` wraps the raw `mmap` functions, and upholds their requirements
+//! by owning the mapped region exclusively.
+#![allow(unsafe_code)]
+
+use crate::fd::AsFd;
+use crate::io;
+#[cfg(not(target_os = "redox"))]
+use crate::mm::{madvise, Advice};
+use crate::mm::{
+    mlock, mmap, mmap_anonymous, mprotect, msync, munlock, munmap, MapFlags, MprotectFlags,
+    MsyncFlags, ProtFlags,
+};
+#[cfg(any(linux_raw, all(libc, target_os = "linux")))]
+use crate::mm::{mremap, MremapFlags};
+use core::ffi::c_void;
+use core::ptr::null_mut;
+use core::{fmt, slice};
+
+/// A memory mapping, which is unmapped when dropped.
+///
+/// `Mapping` tracks the protection of the region, so that its contents can
+/// be accessed as a slice when that's permitted.
+///
+/// # Examples
+///
+/// ```
+/// # use rustix::mm::{Mapping, MapFlags, ProtFlags};
+/// # fn main() -> rustix::io::Result<()> {
+/// let mut mapping = Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE)?;
+/// mapping.as_mut_slice().unwrap()[0] = 1;
+/// assert_eq!(mapping.as_slice().unwrap()[0], 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Mapping {
+    ptr: *mut c_void,
+    len: usize,
+    prot: ProtFlags,
+}
+
+// Safety: A `Mapping` exclusively owns its region, like a `Box<[u8]>`.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// `mmap(NULL, len, prot, MAP_ANONYMOUS | flags, -1, 0)`—Creates an
+    /// anonymous mapping.
+    ///
+    /// The contents are initially zero. This fails with
+    /// [`io::Errno::INVAL`] if `flags` contains [`MapFlags::FIXED`], which
+    /// could replace existing mappings; use [`Mapping::from_raw_parts`] for
+    /// fixed mappings.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mmap.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/mmap.2.html
+    #[doc(alias = "mmap_anonymous")]
+    pub fn anonymous(len: usize, prot: ProtFlags, flags: MapFlags) -> io::Result<Self> {
+        if flags.contains(MapFlags::FIXED) {
+            return Err(io::Errno::INVAL);
+        }
+        // Safety: Without `MAP_FIXED`, the kernel picks an unused address.
+        unsafe {
+            let ptr = mmap_anonymous(null_mut(), len, prot, flags)?;
+            Ok(Self::from_raw_parts(ptr, len, prot))
+        }
+    }
+
+    /// `mmap(NULL, len, prot, flags, fd, offset)`—Creates a file-backed
+    /// mapping.
+    ///
+    /// # Safety
+    ///
+    /// If `flags` contains [`MapFlags::SHARED`], or the file is modified
+    /// while it's mapped, the contents may change while they're borrowed
+    /// through [`Mapping::as_slice`], which is undefined behavior. The caller
+    /// must ensure that doesn't happen, for example by using a file no other
+    /// code modifies. `flags` must not contain [`MapFlags::FIXED`].
+    ///
+    /// Accessing pages beyond the end of the file raises `SIGBUS`.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mmap.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/mmap.2.html
+    #[doc(alias = "mmap")]
+    pub unsafe fn map<Fd: AsFd>(
+        len: usize,
+        prot: ProtFlags,
+        flags: MapFlags,
+        fd: Fd,
+        offset: u64,
+    ) -> io::Result<Self> {
+        let ptr = mmap(null_mut(), len, prot, flags, fd, offset)?;
+        Ok(Self::from_raw_parts(ptr, len, prot))
+    }
+
+    /// Takes ownership of an existing mapping.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `len` must describe a mapping, created with protection
+    /// `prot`, which isn't owned or referenced by anything else. It will be
+    /// unmapped with `munmap` when the `Mapping` is dropped.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut c_void, len: usize, prot: ProtFlags) -> Self {
+        Self { ptr, len, prot }
+    }
+
+    /// Releases ownership of the mapping, returning its address and length.
+    ///
+    /// The caller becomes responsible for unmapping it.
+    #[inline]
+    pub fn into_raw_parts(self) -> (*mut c_void, usize) {
+        let parts = (self.ptr, self.len);
+        core::mem::forget(self);
+        parts
+    }
+
+    /// Returns the address of the mapping.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+
+    /// Returns the length of the mapping, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the mapping has a length of zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the current protection of the mapping.
+    #[inline]
+    pub fn prot(&self) -> ProtFlags {
+        self.prot
+    }
+
+    /// Returns the contents of the mapping, or `None` if it isn't readable.
+    #[inline]
+    pub fn as_slice(&self) -> Option<&[u8]> {
+        if self.prot.contains(ProtFlags::READ) {
+            // Safety: The region is mapped and readable, and we own it.
+            Some(unsafe { slice::from_raw_parts(self.ptr.cast::<u8>(), self.len) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the contents of the mapping, or `None` if it isn't both
+    /// readable and writable.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> Option<&mut [u8]> {
+        if self.prot.contains(ProtFlags::READ | ProtFlags::WRITE) {
+            // Safety: The region is mapped, readable, and writable, and we
+            // own it exclusively.
+            Some(unsafe { slice::from_raw_parts_mut(self.ptr.cast::<u8>(), self.len) })
+        } else {
+            None
+        }
+    }
+
+    /// `mprotect(ptr, len, flags)`—Changes the protection of the mapping.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mprotect.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/mprotect.2.html
+    #[doc(alias = "mprotect")]
+    pub fn protect(&mut self, flags: MprotectFlags) -> io::Result<()> {
+        // Safety: We own the region, and nothing borrows its contents.
+        unsafe { mprotect(self.ptr, self.len, flags)? };
+        let access = MprotectFlags::READ | MprotectFlags::WRITE | MprotectFlags::EXEC;
+        self.prot = ProtFlags::from_bits_truncate((flags & access).bits());
+        Ok(())
+    }
+
+    /// `madvise(ptr, len, advice)`—Declares an expected access pattern for
+    /// the mapping.
+    ///
+    /// Some advice, such as [`Advice::LinuxDontNeed`], discards the contents
+    /// of private mappings, which then read as zero, or as the file's
+    /// contents.
+    ///
+    /// This fails with [`io::Errno::INVAL`] if `advice` is
+    /// [`Advice::LinuxFree`], [`Advice::LinuxRemove`],
+    /// [`Advice::LinuxHwPoison`], or [`Advice::LinuxSoftOffline`], whose
+    /// effects reach beyond the mapping's own contents; use
+    /// [`Mapping::advise_unchecked`] for those.
+    ///
+    /// # References
+    ///  - [Linux]
+    ///
+    /// [Linux]: https://man7.org/linux/man-pages/man2/madvise.2.html
+    #[cfg(not(target_os = "redox"))]
+    #[doc(alias = "madvise")]
+    pub fn advise(&mut self, advice: Advice) -> io::Result<()> {
+        match advice {
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            Advice::LinuxFree | Advice::LinuxRemove | Advice::LinuxHwPoison => {
+                return Err(io::Errno::INVAL)
+            }
+            #[cfg(all(
+                any(target_os = "android", target_os = "linux"),
+                not(any(target_arch = "mips", target_arch = "mips64")),
+            ))]
+            Advice::LinuxSoftOffline => return Err(io::Errno::INVAL),
+            _ => (),
+        }
+
+        // Safety: We own the region, nothing borrows its contents, and the
+        // remaining advice only affects the region itself.
+        unsafe { self.advise_unchecked(advice) }
+    }
+
+    /// `madvise(ptr, len, advice)`—Declares an expected access pattern for
+    /// the mapping, accepting any `advice`.
+    ///
+    /// # Safety
+    ///
+    /// The effects of `advice` must not break any assumptions made by other
+    /// code. [`Advice::LinuxFree`] lets the kernel free the pages lazily, so
+    /// their contents may change at any later time until they're written.
+    /// [`Advice::LinuxRemove`] frees the file's backing store, changing the
+    /// contents of every mapping of that range of the file.
+    /// [`Advice::LinuxHwPoison`] and [`Advice::LinuxSoftOffline`] take the
+    /// underlying physical pages out of service, and may kill any process
+    /// which maps them.
+    ///
+    /// # References
+    ///  - [Linux]
+    ///
+    /// [Linux]: https://man7.org/linux/man-pages/man2/madvise.2.html
+    #[cfg(not(target_os = "redox"))]
+    #[doc(alias = "madvise")]
+    pub unsafe fn advise_unchecked(&mut self, advice: Advice) -> io::Result<()> {
+        madvise(self.ptr, self.len, advice)
+    }
+
+    /// `msync(ptr, len, flags)`—Flushes changes to a file-backed mapping to
+    /// the file.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/msync.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/msync.2.html
+    #[doc(alias = "msync")]
+    pub fn sync(&self, flags: MsyncFlags) -> io::Result<()> {
+        // Safety: We own the region.
+        unsafe { msync(self.ptr, self.len, flags) }
+    }
+
+    /// `mlock(ptr, len)`—Locks the mapping into RAM.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mlock.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/mlock.2.html
+    #[doc(alias = "mlock")]
+    pub fn lock(&self) -> io::Result<()> {
+        // Safety: We own the region.
+        unsafe { mlock(self.ptr, self.len) }
+    }
+
+    /// `munlock(ptr, len)`—Unlocks the mapping.
+    ///
+    /// # References
+    ///  - [POSIX]
+    ///  - [Linux]
+    ///
+    /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/munlock.html
+    /// [Linux]: https://man7.org/linux/man-pages/man2/munlock.2.html
+    #[doc(alias = "munlock")]
+    pub fn unlock(&self) -> io::Result<()> {
+        // Safety: We own the region.
+        unsafe { munlock(self.ptr, self.len) }
+    }
+
+    /// `mremap(ptr, len, new_len, flags)`—Resizes the mapping.
+    ///
+    /// With [`MremapFlags::MAYMOVE`], the mapping may be moved to a new
+    /// address. Otherwise, this fails with [`io::Errno::NOMEM`] if it can't
+    /// be resized in place.
+    ///
+    /// # References
+    ///  - [Linux]
+    ///
+    /// [Linux]: https://man7.org/linux/man-pages/man2/mremap.2.html
+    #[cfg(any(linux_raw, all(libc, target_os = "linux")))]
+    #[doc(alias = "mremap")]
+    pub fn remap(&mut self, new_len: usize, flags: MremapFlags) -> io::Result<()> {
+        // Safety: We own the region, and nothing borrows its contents.
+        self.ptr = unsafe { mremap(self.ptr, self.len, new_len, flags)? };
+        self.len = new_len;
+        Ok(())
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // Safety: We own the region.
+        unsafe {
+            munmap(self.ptr, self.len).ok();
+        }
+    }
+}
+
+impl fmt::Debug for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mapping")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("prot", &self.prot)
+            .finish()
+    }
+}
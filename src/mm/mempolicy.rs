@@ -0,0 +1,384 @@
+//! NUMA memory policy.
+//!
+//! # Safety
+//!
+//! `mbind` operates on raw pointers.
+#![allow(unsafe_code)]
+
+use crate::process::Pid;
+use crate::{backend, io};
+use bitflags::bitflags;
+use core::ffi::c_void;
+use core::mem::size_of;
+use core::ptr::null_mut;
+
+/// The number of `usize` words in a [`NodeMask`].
+const NODE_MASK_WORDS: usize = NodeMask::MAX_NODE / (8 * size_of::<usize>());
+
+/// `NodeMask` represents a bit-mask of NUMA nodes.
+///
+/// `NodeMask`s are used by [`mbind`] and [`set_mempolicy`], for example.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/set_mempolicy.2.html
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NodeMask {
+    bits: [usize; NODE_MASK_WORDS],
+}
+
+impl NodeMask {
+    /// The maximum number of nodes in `NodeMask`.
+    ///
+    /// This is the largest number of nodes Linux supports.
+    pub const MAX_NODE: usize = 1024;
+
+    /// Create a new and empty `NodeMask`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bits: [0; NODE_MASK_WORDS],
+        }
+    }
+
+    /// Test to see if a node is in the `NodeMask`.
+    ///
+    /// `field` is the node id to test.
+    #[inline]
+    pub fn is_set(&self, field: usize) -> bool {
+        let (idx, offset) = Self::position(field);
+        self.bits[idx] & (1 << offset) != 0
+    }
+
+    /// Add a node to `NodeMask`.
+    ///
+    /// `field` is the node id to add.
+    #[inline]
+    pub fn set(&mut self, field: usize) {
+        let (idx, offset) = Self::position(field);
+        self.bits[idx] |= 1 << offset;
+    }
+
+    /// Remove a node from `NodeMask`.
+    ///
+    /// `field` is the node id to remove.
+    #[inline]
+    pub fn unset(&mut self, field: usize) {
+        let (idx, offset) = Self::position(field);
+        self.bits[idx] &= !(1 << offset);
+    }
+
+    /// Count the number of nodes set in the `NodeMask`.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.bits.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Zeroes the `NodeMask`.
+    #[inline]
+    pub fn clear(&mut self) {
+        for word in &mut self.bits {
+            *word = 0;
+        }
+    }
+
+    #[inline]
+    fn position(field: usize) -> (usize, usize) {
+        let size_in_bits = 8 * size_of::<usize>();
+        (field / size_in_bits, field % size_in_bits)
+    }
+}
+
+impl Default for NodeMask {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `MPOL_*` memory policy modes for use with [`mbind`] and
+/// [`set_mempolicy`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum MemPolicyMode {
+    /// `MPOL_DEFAULT`—Use the thread's policy for a range of memory, or the
+    /// system default policy for a thread. The node mask must be empty.
+    Default = 0,
+    /// `MPOL_PREFERRED`—Allocate from the node in the node mask if
+    /// possible, or from the local node if the node mask is empty.
+    Preferred = 1,
+    /// `MPOL_BIND`—Allocate only from the nodes in the node mask.
+    Bind = 2,
+    /// `MPOL_INTERLEAVE`—Interleave allocations across the nodes in the
+    /// node mask.
+    Interleave = 3,
+    /// `MPOL_LOCAL` (since Linux 3.8)—Allocate from the local node. The node
+    /// mask must be empty.
+    Local = 4,
+    /// `MPOL_PREFERRED_MANY` (since Linux 5.15)—Allocate from the nodes in
+    /// the node mask if possible.
+    PreferredMany = 5,
+    /// `MPOL_WEIGHTED_INTERLEAVE` (since Linux 6.9)—Interleave allocations
+    /// across the nodes in the node mask, according to their weights.
+    WeightedInterleave = 6,
+}
+
+impl MemPolicyMode {
+    fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            0 => Self::Default,
+            1 => Self::Preferred,
+            2 => Self::Bind,
+            3 => Self::Interleave,
+            4 => Self::Local,
+            5 => Self::PreferredMany,
+            6 => Self::WeightedInterleave,
+            _ => return None,
+        })
+    }
+}
+
+bitflags! {
+    /// `MPOL_F_*` mode flags for use with [`mbind`] and [`set_mempolicy`].
+    pub struct MemPolicyFlags: u32 {
+        /// `MPOL_F_STATIC_NODES` (since Linux 2.6.26)—Don't remap the node
+        /// mask when the set of allowed nodes changes.
+        const STATIC_NODES = 1 << 15;
+        /// `MPOL_F_RELATIVE_NODES` (since Linux 2.6.26)—Interpret the node
+        /// mask relative to the set of allowed nodes.
+        const RELATIVE_NODES = 1 << 14;
+        /// `MPOL_F_NUMA_BALANCING` (since Linux 5.12)—Enable NUMA balancing
+        /// with [`MemPolicyMode::Bind`].
+        const NUMA_BALANCING = 1 << 13;
+    }
+}
+
+bitflags! {
+    /// `MPOL_MF_*` flags for use with [`mbind`], and, with `MOVE` and
+    /// `MOVE_ALL`, [`move_pages`].
+    pub struct MbindFlags: u32 {
+        /// `MPOL_MF_STRICT`—Fail with `EIO` if existing pages don't follow
+        /// the policy.
+        const STRICT = 1 << 0;
+        /// `MPOL_MF_MOVE`—Move existing pages used only by this process to
+        /// follow the policy.
+        const MOVE = 1 << 1;
+        /// `MPOL_MF_MOVE_ALL`—Move all existing pages to follow the
+        /// policy. This requires `CAP_SYS_NICE`.
+        const MOVE_ALL = 1 << 2;
+    }
+}
+
+/// `MPOL_F_NODE`
+const MPOL_F_NODE: u32 = 1 << 0;
+/// `MPOL_F_ADDR`
+const MPOL_F_ADDR: u32 = 1 << 1;
+/// `MPOL_F_MEMS_ALLOWED`
+const MPOL_F_MEMS_ALLOWED: u32 = 1 << 2;
+
+/// A memory policy, as returned by [`get_mempolicy`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MemPolicy {
+    /// The policy's mode.
+    pub mode: MemPolicyMode,
+    /// The mode flags the policy was set with.
+    pub flags: MemPolicyFlags,
+    /// The policy's nodes.
+    pub nodes: NodeMask,
+}
+
+/// `mbind(addr, len, mode | mode_flags, nodemask, maxnode, flags)`—Sets the
+/// NUMA memory policy for a range of memory.
+///
+/// # Safety
+///
+/// `addr` must be page-aligned, and `addr` and `len` must describe memory
+/// the caller owns.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/mbind.2.html
+#[inline]
+pub unsafe fn mbind(
+    addr: *mut c_void,
+    len: usize,
+    mode: MemPolicyMode,
+    mode_flags: MemPolicyFlags,
+    nodemask: &NodeMask,
+    flags: MbindFlags,
+) -> io::Result<()> {
+    backend::mm::syscalls::mbind(
+        addr,
+        len,
+        mode as u32 | mode_flags.bits(),
+        &nodemask.bits,
+        flags.bits(),
+    )
+}
+
+/// `set_mempolicy(mode | mode_flags, nodemask, maxnode)`—Sets the calling
+/// thread's NUMA memory policy.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/set_mempolicy.2.html
+#[inline]
+pub fn set_mempolicy(
+    mode: MemPolicyMode,
+    mode_flags: MemPolicyFlags,
+    nodemask: &NodeMask,
+) -> io::Result<()> {
+    backend::mm::syscalls::set_mempolicy(mode as u32 | mode_flags.bits(), &nodemask.bits)
+}
+
+/// `get_mempolicy(&mode, nodemask, maxnode, NULL, 0)`—Returns the calling
+/// thread's NUMA memory policy.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/get_mempolicy.2.html
+#[inline]
+pub fn get_mempolicy() -> io::Result<MemPolicy> {
+    get_mempolicy_with(null_mut(), 0)
+}
+
+/// `get_mempolicy(&mode, nodemask, maxnode, addr, MPOL_F_ADDR)`—Returns the
+/// NUMA memory policy for the memory containing `addr`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/get_mempolicy.2.html
+#[inline]
+pub fn get_mempolicy_addr(addr: *mut c_void) -> io::Result<MemPolicy> {
+    get_mempolicy_with(addr, MPOL_F_ADDR)
+}
+
+fn get_mempolicy_with(addr: *mut c_void, flags: u32) -> io::Result<MemPolicy> {
+    let mut mode = 0;
+    let mut nodes = NodeMask::new();
+    backend::mm::syscalls::get_mempolicy(&mut mode, &mut nodes.bits, addr, flags)?;
+
+    let mode = mode as u32;
+    let mode_flags = MemPolicyFlags::from_bits_truncate(mode);
+    let mode =
+        MemPolicyMode::from_raw(mode & !MemPolicyFlags::all().bits()).ok_or(io::Errno::PROTO)?;
+    Ok(MemPolicy {
+        mode,
+        flags: mode_flags,
+        nodes,
+    })
+}
+
+/// `get_mempolicy(&node, NULL, 0, addr, MPOL_F_NODE | MPOL_F_ADDR)`—Returns
+/// the NUMA node the page containing `addr` is allocated on.
+///
+/// The page is faulted in if it isn't already, as if it were read.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/get_mempolicy.2.html
+#[inline]
+pub fn get_mempolicy_node(addr: *mut c_void) -> io::Result<u32> {
+    let mut node = 0;
+    let mut nodes = NodeMask::new();
+    backend::mm::syscalls::get_mempolicy(
+        &mut node,
+        &mut nodes.bits,
+        addr,
+        MPOL_F_NODE | MPOL_F_ADDR,
+    )?;
+    Ok(node as u32)
+}
+
+/// `get_mempolicy(NULL, nodemask, maxnode, NULL, MPOL_F_MEMS_ALLOWED)`—Returns
+/// the set of nodes the calling thread is allowed to use.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/get_mempolicy.2.html
+#[inline]
+pub fn get_mems_allowed() -> io::Result<NodeMask> {
+    let mut mode = 0;
+    let mut nodes = NodeMask::new();
+    backend::mm::syscalls::get_mempolicy(
+        &mut mode,
+        &mut nodes.bits,
+        null_mut(),
+        MPOL_F_MEMS_ALLOWED,
+    )?;
+    Ok(nodes)
+}
+
+/// `migrate_pages(pid, maxnode, old_nodes, new_nodes)`—Moves a process'
+/// pages from one set of nodes to another.
+///
+/// `pid` is the process to update. If pid is `None`, then the current
+/// process is updated.
+///
+/// Returns the number of pages which couldn't be moved.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/migrate_pages.2.html
+#[inline]
+pub fn migrate_pages(
+    pid: Option<Pid>,
+    old_nodes: &NodeMask,
+    new_nodes: &NodeMask,
+) -> io::Result<usize> {
+    backend::mm::syscalls::migrate_pages(pid, &old_nodes.bits, &new_nodes.bits)
+}
+
+/// `move_pages(pid, pages.len(), pages, nodes, status, flags)`—Moves pages
+/// of a process to other nodes, or queries which nodes they're on.
+///
+/// `pid` is the process to update. If pid is `None`, then the current
+/// process is updated.
+///
+/// If `nodes` is `Some`, each page in `pages` is moved to the corresponding
+/// node. Otherwise, the pages are only queried. Afterwards, each element of
+/// `status` holds the node its page is on, or a negated `errno` value for the
+/// page. `nodes` and `status` must have the same length as `pages`, or this
+/// fails with [`io::Errno::INVAL`]. `flags` may contain
+/// [`MbindFlags::MOVE`] or [`MbindFlags::MOVE_ALL`].
+///
+/// Returns the number of pages which couldn't be moved.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/move_pages.2.html
+#[inline]
+pub fn move_pages(
+    pid: Option<Pid>,
+    pages: &[*mut c_void],
+    nodes: Option<&[i32]>,
+    status: &mut [i32],
+    flags: MbindFlags,
+) -> io::Result<usize> {
+    if status.len() != pages.len() || nodes.map_or(false, |nodes| nodes.len() != pages.len()) {
+        return Err(io::Errno::INVAL);
+    }
+    let nodes = nodes.map_or(core::ptr::null(), <[i32]>::as_ptr);
+    // Safety: We checked that the slices have the same length.
+    unsafe {
+        backend::mm::syscalls::move_pages(
+            pid,
+            pages.len(),
+            pages.as_ptr(),
+            nodes,
+            status.as_mut_ptr(),
+            flags.bits(),
+        )
+    }
+}
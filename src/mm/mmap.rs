@@ -10,11 +10,11 @@ use crate::{backend, io};
 use backend::fd::AsFd;
 use core::ffi::c_void;
 
-#[cfg(any(target_os = "android", target_os = "linux"))]
-pub use backend::mm::types::MlockFlags;
 #[cfg(any(linux_raw, all(libc, target_os = "linux")))]
 pub use backend::mm::types::MremapFlags;
 pub use backend::mm::types::{MapFlags, MprotectFlags, ProtFlags};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use backend::mm::types::{MlockFlags, MlockallFlags};
 
 /// `mmap(ptr, len, prot, flags, fd, offset)`—Create a file-backed memory
 /// mapping.
@@ -229,3 +229,68 @@ pub unsafe fn mlock_with(ptr: *mut c_void, len: usize, flags: MlockFlags) -> io:
 pub unsafe fn munlock(ptr: *mut c_void, len: usize) -> io::Result<()> {
     backend::mm::syscalls::munlock(ptr, len)
 }
+
+/// `mlockall(flags)`—Lock all of the process' memory into RAM.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mlockall.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/mlockall.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn mlockall(flags: MlockallFlags) -> io::Result<()> {
+    backend::mm::syscalls::mlockall(flags)
+}
+
+/// `munlockall()`—Unlock all of the process' memory.
+///
+/// # Safety
+///
+/// This unlocks memory which other unrelated code in the process may have
+/// locked with `mlock`, compromising its invariants, so it should only be
+/// used by code which controls all of the process' memory locking.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/munlockall.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/munlockall.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub unsafe fn munlockall() -> io::Result<()> {
+    backend::mm::syscalls::munlockall()
+}
+
+/// `mincore(ptr, len, vec)`—Determine whether pages are resident in RAM.
+///
+/// `ptr` must be page-aligned. On success, `vec` holds one byte for each
+/// page in the `len` bytes starting at `ptr`, with the least significant
+/// bit set if the page is resident. The other bits are reserved.
+///
+/// `vec` must have room for one byte for each page in the range, which is
+/// `(len + page_size - 1) / page_size` bytes, where `page_size` is the
+/// value returned by [`page_size`]. Otherwise, this fails with
+/// [`io::Errno::INVAL`].
+///
+/// # Safety
+///
+/// This function operates on raw pointers, but it should only be used on
+/// memory which the caller owns.
+///
+/// # References
+///  - [Linux]
+///
+/// [`page_size`]: crate::param::page_size
+/// [Linux]: https://man7.org/linux/man-pages/man2/mincore.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub unsafe fn mincore(ptr: *mut c_void, len: usize, vec: &mut [u8]) -> io::Result<()> {
+    let page_size = crate::param::page_size();
+    if vec.len() < len / page_size + usize::from(len % page_size != 0) {
+        return Err(io::Errno::INVAL);
+    }
+    backend::mm::syscalls::mincore(ptr, len, vec.as_mut_ptr())
+}
@@ -2,20 +2,43 @@
 
 #[cfg(not(target_os = "redox"))]
 mod madvise;
+mod mapping;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod mempolicy;
 mod mmap;
 mod msync;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod pkey;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod userfaultfd;
 
+#[cfg(target_os = "linux")]
+pub use madvise::process_madvise;
 #[cfg(not(target_os = "redox"))]
 pub use madvise::{madvise, Advice};
+pub use mapping::Mapping;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use mempolicy::{
+    get_mempolicy, get_mempolicy_addr, get_mempolicy_node, get_mems_allowed, mbind, migrate_pages,
+    move_pages, set_mempolicy, MbindFlags, MemPolicy, MemPolicyFlags, MemPolicyMode, NodeMask,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use mmap::{mincore, mlock_with, mlockall, munlockall, MlockFlags, MlockallFlags};
 pub use mmap::{
     mlock, mmap, mmap_anonymous, mprotect, munlock, munmap, MapFlags, MprotectFlags, ProtFlags,
 };
-#[cfg(any(target_os = "android", target_os = "linux"))]
-pub use mmap::{mlock_with, MlockFlags};
 #[cfg(any(linux_raw, all(libc, target_os = "linux")))]
 pub use mmap::{mremap, mremap_fixed, MremapFlags};
 pub use msync::{msync, MsyncFlags};
 #[cfg(any(target_os = "android", target_os = "linux"))]
-pub use userfaultfd::{userfaultfd, UserfaultfdFlags};
+pub use pkey::{pkey_alloc, pkey_free, pkey_mprotect, PkeyAccessRights};
+#[cfg(all(asm, target_arch = "x86_64"))]
+pub use pkey::{pkey_get, pkey_set};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use userfaultfd::{
+    ioctl_uffdio_api, ioctl_uffdio_continue, ioctl_uffdio_copy, ioctl_uffdio_register,
+    ioctl_uffdio_unregister, ioctl_uffdio_wake, ioctl_uffdio_writeprotect, ioctl_uffdio_zeropage,
+    read_uffd_event, userfaultfd, UffdApi, UffdContinueMode, UffdCopyMode, UffdEvent,
+    UffdFeatureFlags, UffdIoctlFlags, UffdPagefaultFlags, UffdRegisterMode, UffdWriteprotectMode,
+    UffdZeropageMode, UserfaultfdFlags,
+};
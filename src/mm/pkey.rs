@@ -0,0 +1,138 @@
+//! Memory protection keys.
+//!
+//! # Safety
+//!
+//! Protection keys change the accessibility of memory, and the functions
+//! here operate on raw pointers.
+#![allow(unsafe_code)]
+
+use crate::mm::MprotectFlags;
+use crate::{backend, io};
+use bitflags::bitflags;
+use core::ffi::c_void;
+
+bitflags! {
+    /// `PKEY_*` access rights for use with [`pkey_alloc`].
+    pub struct PkeyAccessRights: u32 {
+        /// `PKEY_DISABLE_ACCESS`—Disallow all access to memory with the key.
+        const DISABLE_ACCESS = linux_raw_sys::general::PKEY_DISABLE_ACCESS;
+        /// `PKEY_DISABLE_WRITE`—Disallow writes to memory with the key.
+        const DISABLE_WRITE = linux_raw_sys::general::PKEY_DISABLE_WRITE;
+    }
+}
+
+/// `pkey_alloc(0, access_rights)`—Allocates a memory protection key.
+///
+/// The key's access rights apply to the calling thread, and threads it
+/// creates afterwards inherit them. Other threads have the access rights
+/// they had for the key before it was allocated, which are usually
+/// unrestricted.
+///
+/// This fails with [`io::Errno::NOSPC`] if all keys are in use, and also if
+/// the CPU or kernel doesn't support protection keys.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/pkey_alloc.2.html
+#[inline]
+pub fn pkey_alloc(access_rights: PkeyAccessRights) -> io::Result<u32> {
+    backend::mm::syscalls::pkey_alloc(access_rights.bits())
+}
+
+/// `pkey_free(pkey)`—Frees a memory protection key.
+///
+/// # Safety
+///
+/// Memory which is still tagged with `pkey` will be governed by whatever
+/// access rights it has if it's allocated again, so it should be retagged
+/// with [`pkey_mprotect`] first.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/pkey_free.2.html
+#[inline]
+pub unsafe fn pkey_free(pkey: u32) -> io::Result<()> {
+    backend::mm::syscalls::pkey_free(pkey)
+}
+
+/// `pkey_mprotect(ptr, len, flags, pkey)`—Changes the protection of a range
+/// of memory, and tags it with a memory protection key.
+///
+/// # Safety
+///
+/// This has the same requirements as [`mprotect`], and `pkey`'s access
+/// rights must also permit any accesses other code makes to the memory.
+///
+/// # References
+///  - [Linux]
+///
+/// [`mprotect`]: crate::mm::mprotect
+/// [Linux]: https://man7.org/linux/man-pages/man2/pkey_mprotect.2.html
+#[inline]
+pub unsafe fn pkey_mprotect(
+    ptr: *mut c_void,
+    len: usize,
+    flags: MprotectFlags,
+    pkey: u32,
+) -> io::Result<()> {
+    backend::mm::syscalls::pkey_mprotect(ptr, len, flags, pkey)
+}
+
+/// The number of protection keys the `PKRU` register has bits for.
+#[cfg(all(asm, target_arch = "x86_64"))]
+const PKRU_KEYS: u32 = 16;
+
+/// `pkey_get(pkey)`—Returns the calling thread's access rights for a memory
+/// protection key.
+///
+/// This reads the `PKRU` register with `rdpkru`.
+///
+/// # Safety
+///
+/// The CPU and kernel must support protection keys, as they do if
+/// [`pkey_alloc`] has succeeded. Otherwise, `rdpkru` raises `SIGILL`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man3/pkey_get.3.html
+#[cfg(all(asm, target_arch = "x86_64"))]
+#[inline]
+pub unsafe fn pkey_get(pkey: u32) -> io::Result<PkeyAccessRights> {
+    if pkey >= PKRU_KEYS {
+        return Err(io::Errno::INVAL);
+    }
+    let pkru = backend::mm::syscalls::rdpkru();
+    Ok(PkeyAccessRights::from_bits_truncate(pkru >> (2 * pkey)))
+}
+
+/// `pkey_set(pkey, access_rights)`—Sets the calling thread's access rights
+/// for a memory protection key.
+///
+/// This updates the `PKRU` register with `rdpkru` and `wrpkru`.
+///
+/// # Safety
+///
+/// As with [`pkey_get`], the CPU and kernel must support protection keys.
+/// Other code running on this thread must not rely on accessing memory
+/// tagged with `pkey` in ways that `access_rights` disallow.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man3/pkey_set.3.html
+#[cfg(all(asm, target_arch = "x86_64"))]
+#[inline]
+pub unsafe fn pkey_set(pkey: u32, access_rights: PkeyAccessRights) -> io::Result<()> {
+    if pkey >= PKRU_KEYS {
+        return Err(io::Errno::INVAL);
+    }
+    let shift = 2 * pkey;
+    let pkru = backend::mm::syscalls::rdpkru();
+    let pkru =
+        (pkru & !(PkeyAccessRights::all().bits() << shift)) | (access_rights.bits() << shift);
+    backend::mm::syscalls::wrpkru(pkru);
+    Ok(())
+}
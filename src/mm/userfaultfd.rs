@@ -6,11 +6,28 @@
 //! observe and manipulate process memory in magical ways.
 #![allow(unsafe_code)]
 
-use crate::fd::OwnedFd;
+use crate::fd::{AsFd, FromRawFd, OwnedFd, RawFd};
+use crate::process::Pid;
 use crate::{backend, io};
+use bitflags::bitflags;
+use core::ffi::c_void;
+use core::mem::size_of;
+use linux_raw_sys::general::{
+    uffd_msg, uffdio_api, uffdio_continue, uffdio_copy, uffdio_range, uffdio_register,
+    uffdio_writeprotect, uffdio_zeropage,
+};
 
 pub use backend::mm::types::UserfaultfdFlags;
 
+// These are from `<linux/userfaultfd.h>`; linux-raw-sys doesn't provide them
+// yet.
+/// `UFFDIO_WRITEPROTECT_MODE_WP`
+const UFFDIO_WRITEPROTECT_MODE_WP: u64 = 1 << 0;
+/// `UFFDIO_WRITEPROTECT_MODE_DONTWAKE`
+const UFFDIO_WRITEPROTECT_MODE_DONTWAKE: u64 = 1 << 1;
+/// `UFFDIO_CONTINUE_MODE_DONTWAKE`
+const UFFDIO_CONTINUE_MODE_DONTWAKE: u64 = 1 << 0;
+
 /// `userfaultfd(flags)`
 ///
 /// # Safety
@@ -28,3 +45,506 @@ pub use backend::mm::types::UserfaultfdFlags;
 pub unsafe fn userfaultfd(flags: UserfaultfdFlags) -> io::Result<OwnedFd> {
     backend::mm::syscalls::userfaultfd(flags)
 }
+
+bitflags! {
+    /// `UFFD_FEATURE_*` flags for use with [`ioctl_uffdio_api`].
+    pub struct UffdFeatureFlags: u64 {
+        /// `UFFD_FEATURE_PAGEFAULT_FLAG_WP`
+        const PAGEFAULT_FLAG_WP = linux_raw_sys::general::UFFD_FEATURE_PAGEFAULT_FLAG_WP as u64;
+        /// `UFFD_FEATURE_EVENT_FORK`
+        const EVENT_FORK = linux_raw_sys::general::UFFD_FEATURE_EVENT_FORK as u64;
+        /// `UFFD_FEATURE_EVENT_REMAP`
+        const EVENT_REMAP = linux_raw_sys::general::UFFD_FEATURE_EVENT_REMAP as u64;
+        /// `UFFD_FEATURE_EVENT_REMOVE`
+        const EVENT_REMOVE = linux_raw_sys::general::UFFD_FEATURE_EVENT_REMOVE as u64;
+        /// `UFFD_FEATURE_MISSING_HUGETLBFS`
+        const MISSING_HUGETLBFS = linux_raw_sys::general::UFFD_FEATURE_MISSING_HUGETLBFS as u64;
+        /// `UFFD_FEATURE_MISSING_SHMEM`
+        const MISSING_SHMEM = linux_raw_sys::general::UFFD_FEATURE_MISSING_SHMEM as u64;
+        /// `UFFD_FEATURE_EVENT_UNMAP`
+        const EVENT_UNMAP = linux_raw_sys::general::UFFD_FEATURE_EVENT_UNMAP as u64;
+        /// `UFFD_FEATURE_SIGBUS`
+        const SIGBUS = linux_raw_sys::general::UFFD_FEATURE_SIGBUS as u64;
+        /// `UFFD_FEATURE_THREAD_ID`
+        const THREAD_ID = linux_raw_sys::general::UFFD_FEATURE_THREAD_ID as u64;
+        /// `UFFD_FEATURE_MINOR_HUGETLBFS`
+        const MINOR_HUGETLBFS = linux_raw_sys::general::UFFD_FEATURE_MINOR_HUGETLBFS as u64;
+        /// `UFFD_FEATURE_MINOR_SHMEM`
+        const MINOR_SHMEM = linux_raw_sys::general::UFFD_FEATURE_MINOR_SHMEM as u64;
+    }
+}
+
+bitflags! {
+    /// The `ioctl`s supported by a `userfaultfd`, or by a registered range,
+    /// as reported by [`ioctl_uffdio_api`] and [`ioctl_uffdio_register`].
+    pub struct UffdIoctlFlags: u64 {
+        /// `UFFDIO_REGISTER`
+        const REGISTER = 1 << linux_raw_sys::general::_UFFDIO_REGISTER;
+        /// `UFFDIO_UNREGISTER`
+        const UNREGISTER = 1 << linux_raw_sys::general::_UFFDIO_UNREGISTER;
+        /// `UFFDIO_WAKE`
+        const WAKE = 1 << linux_raw_sys::general::_UFFDIO_WAKE;
+        /// `UFFDIO_COPY`
+        const COPY = 1 << linux_raw_sys::general::_UFFDIO_COPY;
+        /// `UFFDIO_ZEROPAGE`
+        const ZEROPAGE = 1 << linux_raw_sys::general::_UFFDIO_ZEROPAGE;
+        /// `UFFDIO_WRITEPROTECT`
+        const WRITEPROTECT = 1 << linux_raw_sys::general::_UFFDIO_WRITEPROTECT;
+        /// `UFFDIO_CONTINUE`
+        const CONTINUE = 1 << linux_raw_sys::general::_UFFDIO_CONTINUE;
+        /// `UFFDIO_API`
+        const API = 1 << linux_raw_sys::general::_UFFDIO_API;
+    }
+}
+
+bitflags! {
+    /// `UFFDIO_REGISTER_MODE_*` flags for use with [`ioctl_uffdio_register`].
+    pub struct UffdRegisterMode: u64 {
+        /// `UFFDIO_REGISTER_MODE_MISSING`—Report faults on missing pages.
+        const MISSING = linux_raw_sys::general::UFFDIO_REGISTER_MODE_MISSING as u64;
+        /// `UFFDIO_REGISTER_MODE_WP`—Report writes to write-protected
+        /// pages.
+        const WP = linux_raw_sys::general::UFFDIO_REGISTER_MODE_WP as u64;
+        /// `UFFDIO_REGISTER_MODE_MINOR`—Report faults on pages which are in
+        /// the page cache but not mapped.
+        const MINOR = linux_raw_sys::general::UFFDIO_REGISTER_MODE_MINOR as u64;
+    }
+}
+
+bitflags! {
+    /// `UFFDIO_COPY_MODE_*` flags for use with [`ioctl_uffdio_copy`].
+    pub struct UffdCopyMode: u64 {
+        /// `UFFDIO_COPY_MODE_DONTWAKE`—Don't wake threads waiting on the
+        /// range.
+        const DONTWAKE = linux_raw_sys::general::UFFDIO_COPY_MODE_DONTWAKE as u64;
+        /// `UFFDIO_COPY_MODE_WP`—Write-protect the copied pages.
+        const WP = linux_raw_sys::general::UFFDIO_COPY_MODE_WP as u64;
+    }
+}
+
+bitflags! {
+    /// `UFFDIO_ZEROPAGE_MODE_*` flags for use with [`ioctl_uffdio_zeropage`].
+    pub struct UffdZeropageMode: u64 {
+        /// `UFFDIO_ZEROPAGE_MODE_DONTWAKE`—Don't wake threads waiting on the
+        /// range.
+        const DONTWAKE = linux_raw_sys::general::UFFDIO_ZEROPAGE_MODE_DONTWAKE as u64;
+    }
+}
+
+bitflags! {
+    /// `UFFDIO_WRITEPROTECT_MODE_*` flags for use with
+    /// [`ioctl_uffdio_writeprotect`].
+    pub struct UffdWriteprotectMode: u64 {
+        /// `UFFDIO_WRITEPROTECT_MODE_WP`—Write-protect the range. Without
+        /// this, the range is made writable again.
+        const WP = UFFDIO_WRITEPROTECT_MODE_WP;
+        /// `UFFDIO_WRITEPROTECT_MODE_DONTWAKE`—Don't wake threads waiting
+        /// on the range.
+        const DONTWAKE = UFFDIO_WRITEPROTECT_MODE_DONTWAKE;
+    }
+}
+
+bitflags! {
+    /// `UFFDIO_CONTINUE_MODE_*` flags for use with [`ioctl_uffdio_continue`].
+    pub struct UffdContinueMode: u64 {
+        /// `UFFDIO_CONTINUE_MODE_DONTWAKE`—Don't wake threads waiting on the
+        /// range.
+        const DONTWAKE = UFFDIO_CONTINUE_MODE_DONTWAKE;
+    }
+}
+
+bitflags! {
+    /// `UFFD_PAGEFAULT_FLAG_*` flags reported in [`UffdEvent::Pagefault`].
+    pub struct UffdPagefaultFlags: u64 {
+        /// `UFFD_PAGEFAULT_FLAG_WRITE`—The fault was a write.
+        const WRITE = linux_raw_sys::general::UFFD_PAGEFAULT_FLAG_WRITE as u64;
+        /// `UFFD_PAGEFAULT_FLAG_WP`—The fault was a write to a
+        /// write-protected page.
+        const WP = linux_raw_sys::general::UFFD_PAGEFAULT_FLAG_WP as u64;
+        /// `UFFD_PAGEFAULT_FLAG_MINOR`—The fault was a minor fault.
+        const MINOR = linux_raw_sys::general::UFFD_PAGEFAULT_FLAG_MINOR as u64;
+    }
+}
+
+/// The result of [`ioctl_uffdio_api`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct UffdApi {
+    /// The features enabled for the `userfaultfd`.
+    pub features: UffdFeatureFlags,
+    /// The `ioctl`s the `userfaultfd` supports.
+    pub ioctls: UffdIoctlFlags,
+}
+
+/// `ioctl(fd, UFFDIO_API, &uffdio_api { .. })`—Negotiates the API version
+/// and enables `features` on a new `userfaultfd`.
+///
+/// This must be called once, before any other `ioctl`s. It fails with
+/// [`io::Errno::INVAL`] if any of `features` aren't supported; passing an
+/// empty set and inspecting [`UffdApi::features`] in the result reports
+/// everything the kernel supports, but then a new `userfaultfd` is needed to
+/// enable them.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_API")]
+pub fn ioctl_uffdio_api<Fd: AsFd>(fd: Fd, features: UffdFeatureFlags) -> io::Result<UffdApi> {
+    let mut api = uffdio_api {
+        api: linux_raw_sys::general::UFFD_API.into(),
+        features: features.bits(),
+        ioctls: 0,
+    };
+    backend::mm::syscalls::ioctl_uffdio_api(fd.as_fd(), &mut api)?;
+    Ok(UffdApi {
+        features: UffdFeatureFlags::from_bits_truncate(api.features),
+        ioctls: UffdIoctlFlags::from_bits_truncate(api.ioctls),
+    })
+}
+
+/// `ioctl(fd, UFFDIO_REGISTER, &uffdio_register { .. })`—Registers a range
+/// of memory with a `userfaultfd`.
+///
+/// Returns the `ioctl`s which may be used on the range.
+///
+/// # Safety
+///
+/// Once registered, accesses to the range described by `mode` block until
+/// they're resolved through `fd`, so the caller must ensure something
+/// handles them. `ptr` and `len` must be page-aligned.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_REGISTER")]
+pub unsafe fn ioctl_uffdio_register<Fd: AsFd>(
+    fd: Fd,
+    ptr: *mut c_void,
+    len: usize,
+    mode: UffdRegisterMode,
+) -> io::Result<UffdIoctlFlags> {
+    let mut register = uffdio_register {
+        range: range(ptr, len),
+        mode: mode.bits(),
+        ioctls: 0,
+    };
+    backend::mm::syscalls::ioctl_uffdio_register(fd.as_fd(), &mut register)?;
+    Ok(UffdIoctlFlags::from_bits_truncate(register.ioctls))
+}
+
+/// `ioctl(fd, UFFDIO_UNREGISTER, &uffdio_range { .. })`—Unregisters a range
+/// of memory from a `userfaultfd`.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be page-aligned, and describe memory the caller
+/// registered.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_UNREGISTER")]
+pub unsafe fn ioctl_uffdio_unregister<Fd: AsFd>(
+    fd: Fd,
+    ptr: *mut c_void,
+    len: usize,
+) -> io::Result<()> {
+    backend::mm::syscalls::ioctl_uffdio_unregister(fd.as_fd(), &range(ptr, len))
+}
+
+/// `ioctl(fd, UFFDIO_WAKE, &uffdio_range { .. })`—Wakes threads waiting on
+/// faults in a range of memory.
+///
+/// This is used after resolving faults with a `DONTWAKE` mode.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be page-aligned, and the faults in the range must
+/// have been resolved.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_WAKE")]
+pub unsafe fn ioctl_uffdio_wake<Fd: AsFd>(fd: Fd, ptr: *mut c_void, len: usize) -> io::Result<()> {
+    backend::mm::syscalls::ioctl_uffdio_wake(fd.as_fd(), &range(ptr, len))
+}
+
+/// `ioctl(fd, UFFDIO_COPY, &uffdio_copy { .. })`—Resolves missing-page
+/// faults by copying `len` bytes from `src` into the registered range at
+/// `dst`.
+///
+/// Returns the number of bytes copied, which may be less than `len` if the
+/// copy was interrupted.
+///
+/// # Safety
+///
+/// `dst` and `len` must be page-aligned, `dst` must be in a range registered
+/// with [`UffdRegisterMode::MISSING`], and `src` must be valid for reading
+/// `len` bytes.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_COPY")]
+pub unsafe fn ioctl_uffdio_copy<Fd: AsFd>(
+    fd: Fd,
+    dst: *mut c_void,
+    src: *const c_void,
+    len: usize,
+    mode: UffdCopyMode,
+) -> io::Result<usize> {
+    let mut copy = uffdio_copy {
+        dst: dst as usize as u64,
+        src: src as usize as u64,
+        len: len as u64,
+        mode: mode.bits(),
+        copy: 0,
+    };
+    let result = backend::mm::syscalls::ioctl_uffdio_copy(fd.as_fd(), &mut copy);
+    partial(result, copy.copy)
+}
+
+/// `ioctl(fd, UFFDIO_ZEROPAGE, &uffdio_zeropage { .. })`—Resolves
+/// missing-page faults by mapping zeroed pages into a registered range.
+///
+/// Returns the number of bytes zeroed, which may be less than `len` if the
+/// operation was interrupted.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be page-aligned, and `ptr` must be in a range
+/// registered with [`UffdRegisterMode::MISSING`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_ZEROPAGE")]
+pub unsafe fn ioctl_uffdio_zeropage<Fd: AsFd>(
+    fd: Fd,
+    ptr: *mut c_void,
+    len: usize,
+    mode: UffdZeropageMode,
+) -> io::Result<usize> {
+    let mut zeropage = uffdio_zeropage {
+        range: range(ptr, len),
+        mode: mode.bits(),
+        zeropage: 0,
+    };
+    let result = backend::mm::syscalls::ioctl_uffdio_zeropage(fd.as_fd(), &mut zeropage);
+    partial(result, zeropage.zeropage)
+}
+
+/// `ioctl(fd, UFFDIO_WRITEPROTECT, &uffdio_writeprotect { .. })`—Write
+/// protects, or removes write protection from, a registered range.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be page-aligned, and `ptr` must be in a range
+/// registered with [`UffdRegisterMode::WP`]. Once write-protected, writes to
+/// the range block until they're resolved through `fd`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_WRITEPROTECT")]
+pub unsafe fn ioctl_uffdio_writeprotect<Fd: AsFd>(
+    fd: Fd,
+    ptr: *mut c_void,
+    len: usize,
+    mode: UffdWriteprotectMode,
+) -> io::Result<()> {
+    let writeprotect = uffdio_writeprotect {
+        range: range(ptr, len),
+        mode: mode.bits(),
+    };
+    backend::mm::syscalls::ioctl_uffdio_writeprotect(fd.as_fd(), &writeprotect)
+}
+
+/// `ioctl(fd, UFFDIO_CONTINUE, &uffdio_continue { .. })`—Resolves minor
+/// faults by mapping the pages already in the page cache into a registered
+/// range.
+///
+/// Returns the number of bytes mapped, which may be less than `len` if the
+/// operation was interrupted.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be page-aligned, and `ptr` must be in a range
+/// registered with [`UffdRegisterMode::MINOR`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_userfaultfd.2.html
+#[inline]
+#[doc(alias = "UFFDIO_CONTINUE")]
+pub unsafe fn ioctl_uffdio_continue<Fd: AsFd>(
+    fd: Fd,
+    ptr: *mut c_void,
+    len: usize,
+    mode: UffdContinueMode,
+) -> io::Result<usize> {
+    let mut cont = uffdio_continue {
+        range: range(ptr, len),
+        mode: mode.bits(),
+        mapped: 0,
+    };
+    let result = backend::mm::syscalls::ioctl_uffdio_continue(fd.as_fd(), &mut cont);
+    partial(result, cont.mapped)
+}
+
+fn range(ptr: *mut c_void, len: usize) -> uffdio_range {
+    uffdio_range {
+        start: ptr as usize as u64,
+        len: len as u64,
+    }
+}
+
+/// Interprets the result of an `ioctl` which reports how many bytes it
+/// processed before failing, such as with `EAGAIN`, as a partial success.
+fn partial(result: io::Result<()>, count: i64) -> io::Result<usize> {
+    match result {
+        Ok(()) => Ok(count as usize),
+        Err(_) if count > 0 => Ok(count as usize),
+        Err(err) => Err(err),
+    }
+}
+
+/// An event read from a `userfaultfd` by [`read_uffd_event`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UffdEvent {
+    /// `UFFD_EVENT_PAGEFAULT`—A thread faulted on a registered range, and is
+    /// waiting for the fault to be resolved.
+    Pagefault {
+        /// The kind of fault.
+        flags: UffdPagefaultFlags,
+        /// The faulting address.
+        address: *mut c_void,
+        /// The faulting thread, if [`UffdFeatureFlags::THREAD_ID`] is
+        /// enabled.
+        thread_id: Option<Pid>,
+    },
+    /// `UFFD_EVENT_FORK`—The process forked, and the child's registered
+    /// ranges are reported through a new `userfaultfd`.
+    Fork {
+        /// The child's `userfaultfd`.
+        uffd: OwnedFd,
+    },
+    /// `UFFD_EVENT_REMAP`—A registered range was moved with `mremap`.
+    Remap {
+        /// The old address of the range.
+        from: *mut c_void,
+        /// The new address of the range.
+        to: *mut c_void,
+        /// The length of the range.
+        len: usize,
+    },
+    /// `UFFD_EVENT_REMOVE`—Pages in a registered range were discarded, such
+    /// as with `madvise(MADV_DONTNEED)`.
+    Remove {
+        /// The start of the discarded memory.
+        start: *mut c_void,
+        /// The end of the discarded memory.
+        end: *mut c_void,
+    },
+    /// `UFFD_EVENT_UNMAP`—A registered range was unmapped.
+    Unmap {
+        /// The start of the unmapped memory.
+        start: *mut c_void,
+        /// The end of the unmapped memory.
+        end: *mut c_void,
+    },
+}
+
+// Safety: The pointers in `UffdEvent` are addresses, which are never
+// dereferenced.
+unsafe impl Send for UffdEvent {}
+unsafe impl Sync for UffdEvent {}
+
+/// `read(fd, &mut uffd_msg, sizeof(uffd_msg))`—Reads an event from a
+/// `userfaultfd`.
+///
+/// If `fd` is non-blocking and there are no events, this fails with
+/// [`io::Errno::AGAIN`].
+///
+/// # Safety
+///
+/// `fd` must be a `userfaultfd`. Anything else could produce data which
+/// parses as an [`UffdEvent::Fork`], whose file descriptor this function
+/// would then take ownership of.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/userfaultfd.2.html
+pub unsafe fn read_uffd_event<Fd: AsFd>(fd: Fd) -> io::Result<UffdEvent> {
+    use linux_raw_sys::general::{
+        UFFD_EVENT_FORK, UFFD_EVENT_PAGEFAULT, UFFD_EVENT_REMAP, UFFD_EVENT_REMOVE,
+        UFFD_EVENT_UNMAP,
+    };
+
+    let mut buf = [0_u8; size_of::<uffd_msg>()];
+    let n = io::read(fd, &mut buf)?;
+    if n != buf.len() {
+        return Err(io::Errno::IO);
+    }
+
+    // Safety: `uffd_msg` is plain data, and `buf` is fully initialized.
+    let msg = unsafe { buf.as_ptr().cast::<uffd_msg>().read_unaligned() };
+    // Safety: The kernel initializes the union member that `event` selects.
+    unsafe {
+        Ok(match u32::from(msg.event) {
+            UFFD_EVENT_PAGEFAULT => {
+                let pagefault = msg.arg.pagefault;
+                UffdEvent::Pagefault {
+                    flags: UffdPagefaultFlags::from_bits_truncate(pagefault.flags),
+                    address: pagefault.address as usize as *mut c_void,
+                    thread_id: Pid::from_raw(pagefault.feat.ptid as _),
+                }
+            }
+            UFFD_EVENT_FORK => UffdEvent::Fork {
+                uffd: OwnedFd::from_raw_fd(msg.arg.fork.ufd as RawFd),
+            },
+            UFFD_EVENT_REMAP => {
+                let remap = msg.arg.remap;
+                UffdEvent::Remap {
+                    from: remap.from as usize as *mut c_void,
+                    to: remap.to as usize as *mut c_void,
+                    len: remap.len as usize,
+                }
+            }
+            UFFD_EVENT_REMOVE => {
+                let remove = msg.arg.remove;
+                UffdEvent::Remove {
+                    start: remove.start as usize as *mut c_void,
+                    end: remove.end as usize as *mut c_void,
+                }
+            }
+            UFFD_EVENT_UNMAP => {
+                let remove = msg.arg.remove;
+                UffdEvent::Unmap {
+                    start: remove.start as usize as *mut c_void,
+                    end: remove.end as usize as *mut c_void,
+                }
+            }
+            // The kernel only reports the events for features which were
+            // enabled, all of which are handled above.
+            _ => return Err(io::Errno::PROTO),
+        })
+    }
+}
@@ -8,6 +8,8 @@
 #![allow(unsafe_code)]
 
 use crate::{backend, io};
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 use backend::process::types::RawCpuid;
 
@@ -307,6 +309,217 @@ pub fn setsid() -> io::Result<Pid> {
     backend::process::syscalls::setsid()
 }
 
+/// Whether the credential-changing functions in this module affect only the
+/// calling thread or the whole process.
+///
+/// Linux tracks user and group IDs per thread. POSIX requires functions like
+/// `setuid` to change the IDs of every thread in the process, so libc
+/// implementations on Linux emulate this by signaling every thread and having
+/// each one perform the system call itself (the "setxid broadcast"). The
+/// `linux_raw` backend performs the system call directly, so it only changes
+/// the credentials of the calling thread. [`CREDENTIAL_SCOPE`] reports which
+/// behavior is in effect.
+///
+/// Programs that drop privileges with the `linux_raw` backend should do so
+/// before spawning any other threads.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CredentialScope {
+    /// Credential changes apply only to the calling thread.
+    Thread,
+    /// Credential changes apply to all threads in the process.
+    Process,
+}
+
+/// The scope of the credential-changing functions in this module, such as
+/// [`setuid`] and [`setgroups`].
+///
+/// See [`CredentialScope`] for details.
+#[cfg(linux_raw)]
+pub const CREDENTIAL_SCOPE: CredentialScope = CredentialScope::Thread;
+
+/// The scope of the credential-changing functions in this module, such as
+/// [`setuid`] and [`setgroups`].
+///
+/// See [`CredentialScope`] for details.
+#[cfg(not(linux_raw))]
+pub const CREDENTIAL_SCOPE: CredentialScope = CredentialScope::Process;
+
+/// `setuid(uid)`—Sets the user ID.
+///
+/// With the `linux_raw` backend, this only affects the calling thread; see
+/// [`CredentialScope`].
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/setuid.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/setuid.2.html
+#[inline]
+pub fn setuid(uid: Uid) -> io::Result<()> {
+    backend::process::syscalls::setuid(uid)
+}
+
+/// `setgid(gid)`—Sets the group ID.
+///
+/// With the `linux_raw` backend, this only affects the calling thread; see
+/// [`CredentialScope`].
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/setgid.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/setgid.2.html
+#[inline]
+pub fn setgid(gid: Gid) -> io::Result<()> {
+    backend::process::syscalls::setgid(gid)
+}
+
+/// The real, effective, and saved user IDs, as returned by [`getresuid`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ResUid {
+    /// The real user ID.
+    pub real: Uid,
+    /// The effective user ID.
+    pub effective: Uid,
+    /// The saved set-user-ID.
+    pub saved: Uid,
+}
+
+/// The real, effective, and saved group IDs, as returned by [`getresgid`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ResGid {
+    /// The real group ID.
+    pub real: Gid,
+    /// The effective group ID.
+    pub effective: Gid,
+    /// The saved set-group-ID.
+    pub saved: Gid,
+}
+
+/// `setresuid(ruid, euid, suid)`—Sets the real, effective, and saved user
+/// IDs.
+///
+/// A `None` argument leaves the corresponding ID unchanged.
+///
+/// With the `linux_raw` backend, this only affects the calling thread; see
+/// [`CredentialScope`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/setresuid.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn setresuid(ruid: Option<Uid>, euid: Option<Uid>, suid: Option<Uid>) -> io::Result<()> {
+    // `-1` (`u32::MAX`) leaves the ID unchanged.
+    let ruid = ruid.map_or(u32::MAX, Uid::as_raw);
+    let euid = euid.map_or(u32::MAX, Uid::as_raw);
+    let suid = suid.map_or(u32::MAX, Uid::as_raw);
+    backend::process::syscalls::setresuid(ruid, euid, suid)
+}
+
+/// `setresgid(rgid, egid, sgid)`—Sets the real, effective, and saved group
+/// IDs.
+///
+/// A `None` argument leaves the corresponding ID unchanged.
+///
+/// With the `linux_raw` backend, this only affects the calling thread; see
+/// [`CredentialScope`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/setresgid.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn setresgid(rgid: Option<Gid>, egid: Option<Gid>, sgid: Option<Gid>) -> io::Result<()> {
+    // `-1` (`u32::MAX`) leaves the ID unchanged.
+    let rgid = rgid.map_or(u32::MAX, Gid::as_raw);
+    let egid = egid.map_or(u32::MAX, Gid::as_raw);
+    let sgid = sgid.map_or(u32::MAX, Gid::as_raw);
+    backend::process::syscalls::setresgid(rgid, egid, sgid)
+}
+
+/// `getresuid()`—Returns the real, effective, and saved user IDs.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/getresuid.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[must_use]
+pub fn getresuid() -> ResUid {
+    let (real, effective, saved) = backend::process::syscalls::getresuid();
+    ResUid {
+        real,
+        effective,
+        saved,
+    }
+}
+
+/// `getresgid()`—Returns the real, effective, and saved group IDs.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/getresgid.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+#[must_use]
+pub fn getresgid() -> ResGid {
+    let (real, effective, saved) = backend::process::syscalls::getresgid();
+    ResGid {
+        real,
+        effective,
+        saved,
+    }
+}
+
+/// `setgroups(groups)`—Sets the supplementary group IDs.
+///
+/// With the `linux_raw` backend, this only affects the calling thread; see
+/// [`CredentialScope`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/setgroups.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn setgroups(groups: &[Gid]) -> io::Result<()> {
+    backend::process::syscalls::setgroups(groups)
+}
+
+/// `getgroups()`—Returns the supplementary group IDs.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/getgroups.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/getgroups.2.html
+pub fn getgroups() -> io::Result<Vec<Gid>> {
+    loop {
+        // Query the number of groups, then retry if the list grew between
+        // the two calls.
+        let len = backend::process::syscalls::getgroups(&mut [])?;
+        let mut groups = vec![Gid::ROOT; len];
+        match backend::process::syscalls::getgroups(&mut groups) {
+            Ok(n) => {
+                groups.truncate(n);
+                return Ok(groups);
+            }
+            Err(io::Errno::INVAL) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
 // translate_fchown_args returns the raw value of the IDs. In case of `None`
 // it returns `u32::MAX` since it has the same bit pattern as `-1` indicating
 // no change to the owner/group ID.
@@ -10,6 +10,8 @@ mod kill;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod membarrier;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod personality;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod prctl;
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))] // WASI doesn't have [gs]etpriority.
 mod priority;
@@ -25,6 +27,8 @@ mod rlimit;
 ))]
 mod sched;
 mod sched_yield;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod sysinfo;
 #[cfg(not(target_os = "wasi"))] // WASI doesn't have uname.
 mod uname;
 #[cfg(not(target_os = "wasi"))]
@@ -43,9 +47,12 @@ pub use exit::{EXIT_FAILURE, EXIT_SUCCESS};
 pub use id::Cpuid;
 #[cfg(not(target_os = "wasi"))]
 pub use id::{
-    getegid, geteuid, getgid, getpgid, getpgrp, getpid, getppid, getuid, setsid, Gid, Pid, RawGid,
-    RawNonZeroPid, RawPid, RawUid, Uid,
+    getegid, geteuid, getgid, getgroups, getpgid, getpgrp, getpid, getppid, getuid, setgid, setsid,
+    setuid, CredentialScope, Gid, Pid, RawGid, RawNonZeroPid, RawPid, RawUid, Uid,
+    CREDENTIAL_SCOPE,
 };
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use id::{getresgid, getresuid, setgroups, setresgid, setresuid, ResGid, ResUid};
 #[cfg(not(target_os = "wasi"))]
 pub use kill::{kill_current_process_group, kill_process, kill_process_group, Signal};
 #[cfg(any(target_os = "android", target_os = "linux"))]
@@ -53,6 +60,10 @@ pub use membarrier::{
     membarrier, membarrier_cpu, membarrier_query, MembarrierCommand, MembarrierQuery,
 };
 #[cfg(any(target_os = "android", target_os = "linux"))]
+pub use personality::{
+    personality, set_personality, ExecutionDomain, Personality, PersonalityFlags,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
 pub use prctl::*;
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
 pub use priority::nice;
@@ -75,6 +86,10 @@ pub use rlimit::{getrlimit, setrlimit, Resource, Rlimit};
 ))]
 pub use sched::{sched_getaffinity, sched_setaffinity, CpuSet};
 pub use sched_yield::sched_yield;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use sysinfo::{sysinfo, Sysinfo};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use uname::{setdomainname, sethostname};
 #[cfg(not(target_os = "wasi"))]
 pub use uname::{uname, Uname};
 #[cfg(not(target_os = "wasi"))]
@@ -0,0 +1,126 @@
+#![allow(unsafe_code)]
+
+use crate::{backend, io};
+use bitflags::bitflags;
+
+/// The value passed to `personality` to query the persona without changing
+/// it.
+const PERSONALITY_QUERY: u32 = 0xffff_ffff;
+
+/// `PER_MASK`—The bits of a persona which hold its execution domain.
+const PER_MASK: u32 = 0x0000_00ff;
+
+/// `PER_*` constants for the execution domain of a [`Personality`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct ExecutionDomain(u8);
+
+impl ExecutionDomain {
+    /// `PER_LINUX`
+    pub const LINUX: Self = Self(0x00);
+
+    /// `PER_LINUX32`
+    pub const LINUX32: Self = Self(0x08);
+
+    /// Constructs an `ExecutionDomain` from a raw integer.
+    #[inline]
+    pub const fn from_raw(raw: u8) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw integer for this `ExecutionDomain`.
+    #[inline]
+    pub const fn as_raw(self) -> u8 {
+        self.0
+    }
+}
+
+bitflags! {
+    /// Persona flags, the bits of a [`Personality`] above its execution
+    /// domain.
+    pub struct PersonalityFlags: u32 {
+        /// `UNAME26`
+        const UNAME26 = 0x0002_0000;
+        /// `ADDR_NO_RANDOMIZE`
+        const ADDR_NO_RANDOMIZE = 0x0004_0000;
+        /// `FDPIC_FUNCPTRS`
+        const FDPIC_FUNCPTRS = 0x0008_0000;
+        /// `MMAP_PAGE_ZERO`
+        const MMAP_PAGE_ZERO = 0x0010_0000;
+        /// `ADDR_COMPAT_LAYOUT`
+        const ADDR_COMPAT_LAYOUT = 0x0020_0000;
+        /// `READ_IMPLIES_EXEC`
+        const READ_IMPLIES_EXEC = 0x0040_0000;
+        /// `ADDR_LIMIT_32BIT`
+        const ADDR_LIMIT_32BIT = 0x0080_0000;
+        /// `SHORT_INODE`
+        const SHORT_INODE = 0x0100_0000;
+        /// `WHOLE_SECONDS`
+        const WHOLE_SECONDS = 0x0200_0000;
+        /// `STICKY_TIMEOUTS`
+        const STICKY_TIMEOUTS = 0x0400_0000;
+        /// `ADDR_LIMIT_3GB`
+        const ADDR_LIMIT_3GB = 0x0800_0000;
+    }
+}
+
+/// A persona, as used with [`personality`] and [`set_personality`]—an
+/// execution domain and a set of flags.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Personality {
+    /// The execution domain, in the low byte of the persona.
+    pub domain: ExecutionDomain,
+    /// The persona flags.
+    pub flags: PersonalityFlags,
+}
+
+impl Personality {
+    /// Split a raw persona into its execution domain and flags.
+    #[inline]
+    fn from_raw(raw: u32) -> Self {
+        Self {
+            domain: ExecutionDomain((raw & PER_MASK) as u8),
+            // Keep flags rustix doesn't know about, so that the persona
+            // round-trips through `set_personality`.
+            flags: unsafe { PersonalityFlags::from_bits_unchecked(raw & !PER_MASK) },
+        }
+    }
+
+    /// Combine the execution domain and flags into a raw persona.
+    #[inline]
+    fn as_raw(self) -> u32 {
+        u32::from(self.domain.0) | (self.flags.bits() & !PER_MASK)
+    }
+}
+
+/// `personality(0xffffffff)`—Returns the execution domain and flags of the
+/// current process.
+///
+/// Execution domains and flags not known to rustix are preserved.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/personality.2.html
+#[inline]
+pub fn personality() -> io::Result<Personality> {
+    let persona = backend::process::syscalls::personality(PERSONALITY_QUERY)?;
+    Ok(Personality::from_raw(persona))
+}
+
+/// `personality(persona)`—Sets the execution domain and flags of the current
+/// process, returning the previous persona.
+///
+/// To set a flag such as `ADDR_NO_RANDOMIZE` for a subsequent `execve`, pass
+/// the current persona from [`personality`] with the flag added to its
+/// `flags`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/personality.2.html
+#[inline]
+pub fn set_personality(persona: Personality) -> io::Result<Personality> {
+    let prev = backend::process::syscalls::personality(persona.as_raw())?;
+    Ok(Personality::from_raw(prev))
+}
@@ -0,0 +1,65 @@
+use crate::backend;
+use core::time::Duration;
+
+/// `SI_LOAD_SHIFT`—The fixed-point scale of the kernel's load averages.
+const SI_LOAD_SHIFT: u32 = 16;
+
+/// `sysinfo()`—Returns overall system statistics.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/sysinfo.2.html
+#[inline]
+pub fn sysinfo() -> Sysinfo {
+    let raw = backend::process::syscalls::sysinfo();
+
+    // Memory sizes are reported in units of `mem_unit` bytes.
+    let unit = u64::from(raw.mem_unit.max(1));
+    let bytes = |n| n as u64 * unit;
+    let load = |n| n as f64 / f64::from(1_u32 << SI_LOAD_SHIFT);
+
+    Sysinfo {
+        uptime: Duration::from_secs(raw.uptime as u64),
+        loads: [load(raw.loads[0]), load(raw.loads[1]), load(raw.loads[2])],
+        total_ram: bytes(raw.totalram),
+        free_ram: bytes(raw.freeram),
+        shared_ram: bytes(raw.sharedram),
+        buffer_ram: bytes(raw.bufferram),
+        total_swap: bytes(raw.totalswap),
+        free_swap: bytes(raw.freeswap),
+        total_high: bytes(raw.totalhigh),
+        free_high: bytes(raw.freehigh),
+        procs: raw.procs,
+    }
+}
+
+/// `struct sysinfo`—Return type for [`sysinfo`].
+///
+/// Memory sizes are in bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[doc(alias = "sysinfo")]
+pub struct Sysinfo {
+    /// `uptime`—Time since boot.
+    pub uptime: Duration,
+    /// `loads`—1, 5, and 15 minute load averages.
+    pub loads: [f64; 3],
+    /// `totalram`—Total usable main memory size.
+    pub total_ram: u64,
+    /// `freeram`—Available memory size.
+    pub free_ram: u64,
+    /// `sharedram`—Amount of shared memory.
+    pub shared_ram: u64,
+    /// `bufferram`—Memory used by buffers.
+    pub buffer_ram: u64,
+    /// `totalswap`—Total swap space size.
+    pub total_swap: u64,
+    /// `freeswap`—Swap space still available.
+    pub free_swap: u64,
+    /// `totalhigh`—Total high memory size.
+    pub total_high: u64,
+    /// `freehigh`—Available high memory size.
+    pub free_high: u64,
+    /// `procs`—Number of current processes.
+    pub procs: u16,
+}
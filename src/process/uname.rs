@@ -8,6 +8,8 @@
 
 use crate::backend;
 use crate::ffi::CStr;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::io;
 use core::fmt;
 
 /// `uname()`—Returns high-level information about the runtime OS and
@@ -17,6 +19,39 @@ pub fn uname() -> Uname {
     Uname(backend::process::syscalls::uname())
 }
 
+/// `sethostname(name)`—Sets the hostname of the current UTS namespace.
+///
+/// This is the `nodename` reported by [`uname`]. Setting it requires
+/// `CAP_SYS_ADMIN` in the user namespace that owns the UTS namespace, such as
+/// one created with `unshare(UnshareFlags::NEWUTS)`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/sethostname.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn sethostname(name: &[u8]) -> io::Result<()> {
+    backend::process::syscalls::sethostname(name)
+}
+
+/// `setdomainname(name)`—Sets the NIS domain name of the current UTS
+/// namespace.
+///
+/// This is the `domainname` reported by [`uname`]. As with [`sethostname`],
+/// setting it requires `CAP_SYS_ADMIN` in the user namespace that owns the
+/// UTS namespace.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/setdomainname.2.html
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[inline]
+pub fn setdomainname(name: &[u8]) -> io::Result<()> {
+    backend::process::syscalls::setdomainname(name)
+}
+
 /// `struct utsname`—Return type for [`uname`].
 #[doc(alias = "utsname")]
 pub struct Uname(backend::process::types::RawUname);
@@ -34,6 +34,15 @@ use crate::process::Pid;
 use backend::fd::AsFd;
 #[cfg(linux_raw)]
 use core::ffi::c_void;
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+use {
+    crate::backend::c::c_int,
+    crate::fd::{BorrowedFd, FromRawFd, OwnedFd},
+    crate::process::Signal,
+    crate::thread::UnshareFlags,
+    linux_raw_sys::general::{CLONE_INTO_CGROUP, CLONE_PIDFD},
+};
 
 #[cfg(linux_raw)]
 #[cfg(target_arch = "x86")]
@@ -224,6 +233,217 @@ pub unsafe fn fork() -> io::Result<Option<Pid>> {
     backend::runtime::syscalls::fork()
 }
 
+/// Arguments for [`clone3`].
+///
+/// The defaults describe a plain `fork`: no new namespaces, no pidfd, and
+/// `SIGCHLD` delivered to the parent when the child exits.
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "thread")))]
+#[derive(Debug, Clone)]
+pub struct CloneArgs<'a> {
+    flags: u64,
+    exit_signal: Option<Signal>,
+    set_tid: &'a [Pid],
+    cgroup: Option<BorrowedFd<'a>>,
+}
+
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+impl<'a> CloneArgs<'a> {
+    /// Construct a `CloneArgs` describing a plain `fork`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            flags: 0,
+            exit_signal: Some(Signal::Child),
+            set_tid: &[],
+            cgroup: None,
+        }
+    }
+
+    /// Set the `CLONE_*` flags, such as `CLONE_NEWNS` or `CLONE_NEWPID`, to
+    /// create the child in new namespaces.
+    ///
+    /// Note that `UnshareFlags::FILES`, `UnshareFlags::FS`, and
+    /// `UnshareFlags::SYSVSEM` have the opposite meaning here than they do
+    /// with `unshare`: they cause the child to *share* the corresponding
+    /// state with the parent.
+    #[inline]
+    pub fn flags(mut self, flags: UnshareFlags) -> Self {
+        self.flags =
+            (self.flags & !u64::from(UnshareFlags::all().bits())) | u64::from(flags.bits());
+        self
+    }
+
+    /// Request a pidfd referring to the child, with `CLONE_PIDFD`.
+    ///
+    /// The pidfd is returned in [`CloneChild::pidfd`].
+    #[inline]
+    pub fn pidfd(mut self, pidfd: bool) -> Self {
+        if pidfd {
+            self.flags |= u64::from(CLONE_PIDFD);
+        } else {
+            self.flags &= !u64::from(CLONE_PIDFD);
+        }
+        self
+    }
+
+    /// Set the signal sent to the parent when the child exits, or `None` to
+    /// send no signal.
+    #[inline]
+    pub fn exit_signal(mut self, exit_signal: Option<Signal>) -> Self {
+        self.exit_signal = exit_signal;
+        self
+    }
+
+    /// Request specific pids for the child, one per nested pid namespace,
+    /// starting with the innermost.
+    ///
+    /// This requires `CAP_CHECKPOINT_RESTORE` or `CAP_SYS_ADMIN` in the
+    /// owning user namespaces.
+    #[inline]
+    pub fn set_tid(mut self, set_tid: &'a [Pid]) -> Self {
+        self.set_tid = set_tid;
+        self
+    }
+
+    /// Place the child in the cgroup referred to by `cgroup`, a directory
+    /// file descriptor in a cgroup2 hierarchy, with `CLONE_INTO_CGROUP`.
+    #[inline]
+    pub fn cgroup(mut self, cgroup: BorrowedFd<'a>) -> Self {
+        self.flags |= CLONE_INTO_CGROUP;
+        self.cgroup = Some(cgroup);
+        self
+    }
+}
+
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+impl<'a> Default for CloneArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The parent's view of a child created by [`clone3`].
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "thread")))]
+#[derive(Debug)]
+pub struct CloneChild {
+    /// The pid of the child, in the parent's pid namespace.
+    pub pid: Pid,
+
+    /// A pidfd referring to the child, if [`CloneArgs::pidfd`] was requested.
+    pub pidfd: Option<OwnedFd>,
+}
+
+/// `clone3(args, sizeof(struct clone_args))`—Creates a new process, like
+/// [`fork`], with extended options.
+///
+/// On success, a [`CloneChild`] is returned in the parent, and `None` is
+/// returned in the child.
+///
+/// This is always fork-like: the child gets a copy of the parent's address
+/// space and continues on the calling thread's stack, so flags such as
+/// `CLONE_VM` and `CLONE_THREAD` are not supported.
+///
+/// On kernels without `clone3`, this fails with `Errno::NOSYS`. If `args`
+/// uses a field that the kernel doesn't know about, such as
+/// [`CloneArgs::cgroup`] before Linux 5.7, this fails with `Errno::TOOBIG`.
+///
+/// # Safety
+///
+/// The same considerations as for [`fork`] apply to the child.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/clone3.2.html
+#[cfg(linux_raw)]
+#[cfg(feature = "thread")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "thread")))]
+pub unsafe fn clone3(args: &CloneArgs<'_>) -> io::Result<Option<CloneChild>> {
+    let mut pidfd: c_int = -1;
+    let exit_signal = args.exit_signal.map_or(0, |sig| sig as u64);
+    match backend::runtime::syscalls::clone3(
+        args.flags,
+        &mut pidfd,
+        exit_signal,
+        args.set_tid,
+        args.cgroup,
+    )? {
+        None => Ok(None),
+        Some(pid) => Ok(Some(CloneChild {
+            pid,
+            pidfd: if args.flags & u64::from(CLONE_PIDFD) != 0 {
+                Some(OwnedFd::from_raw_fd(pidfd))
+            } else {
+                None
+            },
+        })),
+    }
+}
+
+/// `clone(CLONE_VM | CLONE_VFORK)` and `execve(path, argv, envp)`—Creates a
+/// new process running a new program, like `posix_spawn`.
+///
+/// The child shares the parent's address space, running on a dedicated
+/// stack, until it calls `execve`, so this avoids the cost of copying the
+/// parent's page tables that [`fork`] incurs. The calling thread is suspended
+/// until the child calls `execve` or fails.
+///
+/// Before calling `execve`, the child resets all signal handlers to their
+/// default dispositions and then performs `actions` in order. If any action
+/// or the `execve` fails, the child exits and the error is returned here.
+/// Otherwise, the child's pid is returned.
+///
+/// This is currently only available on x86-64, aarch64, and riscv64.
+///
+/// # Safety
+///
+/// The `argv` and `envp` pointers must point to NUL-terminated arrays, and
+/// their contents must be pointers to NUL-terminated byte arrays.
+///
+/// # References
+///  - [POSIX `posix_spawn`]
+///  - [Linux `clone`]
+///
+/// [POSIX `posix_spawn`]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/posix_spawn.html
+/// [Linux `clone`]: https://man7.org/linux/man-pages/man2/clone.2.html
+#[cfg(linux_raw)]
+#[cfg(all(
+    asm,
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+#[doc(alias = "posix_spawn")]
+#[doc(alias = "vfork")]
+pub unsafe fn spawn(
+    path: &CStr,
+    argv: *const *const u8,
+    envp: *const *const u8,
+    actions: &[SpawnAction<'_>],
+) -> io::Result<Pid> {
+    backend::runtime::spawn::spawn(path, argv, envp, actions)
+}
+
+#[cfg(linux_raw)]
+#[cfg(all(
+    asm,
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+pub use backend::runtime::spawn::SpawnAction;
+
 /// `execveat(dirfd, path.as_c_str(), argv, envp, flags)`—Execute a new
 /// command using the current process.
 ///
@@ -0,0 +1,250 @@
+#![allow(unsafe_code)]
+
+use core::mem::MaybeUninit;
+
+use bitflags::bitflags;
+
+use crate::backend::c::c_int;
+use crate::backend::thread::syscalls;
+use crate::io;
+use crate::process::Pid;
+use crate::thread::Capability;
+
+/// `_LINUX_CAPABILITY_VERSION_3`
+const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+/// `_LINUX_CAPABILITY_U32S_3`
+const LINUX_CAPABILITY_U32S_3: usize = 2;
+
+/// `struct __user_cap_header_struct`
+#[repr(C)]
+pub(crate) struct CapUserHeader {
+    pub(crate) version: u32,
+    pub(crate) pid: c_int,
+}
+
+/// `struct __user_cap_data_struct`
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub(crate) struct CapUserData {
+    pub(crate) effective: u32,
+    pub(crate) permitted: u32,
+    pub(crate) inheritable: u32,
+}
+
+bitflags! {
+    /// A set of [`Capability`] values, as used in [`CapabilitySets`].
+    pub struct CapabilityFlags: u64 {
+        /// `CAP_CHOWN`
+        const CHOWN = 1 << Capability::ChangeOwnership as u32;
+        /// `CAP_DAC_OVERRIDE`
+        const DAC_OVERRIDE = 1 << Capability::DACOverride as u32;
+        /// `CAP_DAC_READ_SEARCH`
+        const DAC_READ_SEARCH = 1 << Capability::DACReadSearch as u32;
+        /// `CAP_FOWNER`
+        const FOWNER = 1 << Capability::FileOwner as u32;
+        /// `CAP_FSETID`
+        const FSETID = 1 << Capability::FileSetID as u32;
+        /// `CAP_KILL`
+        const KILL = 1 << Capability::Kill as u32;
+        /// `CAP_SETGID`
+        const SETGID = 1 << Capability::SetGroupID as u32;
+        /// `CAP_SETUID`
+        const SETUID = 1 << Capability::SetUserID as u32;
+        /// `CAP_SETPCAP`
+        const SETPCAP = 1 << Capability::SetPermittedCapabilities as u32;
+        /// `CAP_LINUX_IMMUTABLE`
+        const LINUX_IMMUTABLE = 1 << Capability::LinuxImmutable as u32;
+        /// `CAP_NET_BIND_SERVICE`
+        const NET_BIND_SERVICE = 1 << Capability::NetBindService as u32;
+        /// `CAP_NET_BROADCAST`
+        const NET_BROADCAST = 1 << Capability::NetBroadcast as u32;
+        /// `CAP_NET_ADMIN`
+        const NET_ADMIN = 1 << Capability::NetAdmin as u32;
+        /// `CAP_NET_RAW`
+        const NET_RAW = 1 << Capability::NetRaw as u32;
+        /// `CAP_IPC_LOCK`
+        const IPC_LOCK = 1 << Capability::IPCLock as u32;
+        /// `CAP_IPC_OWNER`
+        const IPC_OWNER = 1 << Capability::IPCOwner as u32;
+        /// `CAP_SYS_MODULE`
+        const SYS_MODULE = 1 << Capability::SystemModule as u32;
+        /// `CAP_SYS_RAWIO`
+        const SYS_RAWIO = 1 << Capability::SystemRawIO as u32;
+        /// `CAP_SYS_CHROOT`
+        const SYS_CHROOT = 1 << Capability::SystemChangeRoot as u32;
+        /// `CAP_SYS_PTRACE`
+        const SYS_PTRACE = 1 << Capability::SystemProcessTrace as u32;
+        /// `CAP_SYS_PACCT`
+        const SYS_PACCT = 1 << Capability::SystemProcessAccounting as u32;
+        /// `CAP_SYS_ADMIN`
+        const SYS_ADMIN = 1 << Capability::SystemAdmin as u32;
+        /// `CAP_SYS_BOOT`
+        const SYS_BOOT = 1 << Capability::SystemBoot as u32;
+        /// `CAP_SYS_NICE`
+        const SYS_NICE = 1 << Capability::SystemNice as u32;
+        /// `CAP_SYS_RESOURCE`
+        const SYS_RESOURCE = 1 << Capability::SystemResource as u32;
+        /// `CAP_SYS_TIME`
+        const SYS_TIME = 1 << Capability::SystemTime as u32;
+        /// `CAP_SYS_TTY_CONFIG`
+        const SYS_TTY_CONFIG = 1 << Capability::SystemTTYConfig as u32;
+        /// `CAP_MKNOD`
+        const MKNOD = 1 << Capability::MakeNode as u32;
+        /// `CAP_LEASE`
+        const LEASE = 1 << Capability::Lease as u32;
+        /// `CAP_AUDIT_WRITE`
+        const AUDIT_WRITE = 1 << Capability::AuditWrite as u32;
+        /// `CAP_AUDIT_CONTROL`
+        const AUDIT_CONTROL = 1 << Capability::AuditControl as u32;
+        /// `CAP_SETFCAP`
+        const SETFCAP = 1 << Capability::SetFileCapabilities as u32;
+        /// `CAP_MAC_OVERRIDE`
+        const MAC_OVERRIDE = 1 << Capability::MACOverride as u32;
+        /// `CAP_MAC_ADMIN`
+        const MAC_ADMIN = 1 << Capability::MACAdmin as u32;
+        /// `CAP_SYSLOG`
+        const SYSLOG = 1 << Capability::SystemLog as u32;
+        /// `CAP_WAKE_ALARM`
+        const WAKE_ALARM = 1 << Capability::WakeAlarm as u32;
+        /// `CAP_BLOCK_SUSPEND`
+        const BLOCK_SUSPEND = 1 << Capability::BlockSuspend as u32;
+        /// `CAP_AUDIT_READ`
+        const AUDIT_READ = 1 << Capability::AuditRead as u32;
+        /// `CAP_PERFMON`
+        const PERFMON = 1 << Capability::PerformanceMonitoring as u32;
+        /// `CAP_BPF`
+        const BPF = 1 << Capability::BerkeleyPacketFilters as u32;
+        /// `CAP_CHECKPOINT_RESTORE`
+        const CHECKPOINT_RESTORE = 1 << Capability::CheckpointRestore as u32;
+    }
+}
+
+impl From<Capability> for CapabilityFlags {
+    #[inline]
+    fn from(capability: Capability) -> Self {
+        Self::from_bits_truncate(1 << capability as u32)
+    }
+}
+
+/// The effective, permitted, and inheritable capability sets of a thread.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CapabilitySets {
+    /// `__user_cap_data_struct.effective`
+    pub effective: CapabilityFlags,
+    /// `__user_cap_data_struct.permitted`
+    pub permitted: CapabilityFlags,
+    /// `__user_cap_data_struct.inheritable`
+    pub inheritable: CapabilityFlags,
+}
+
+impl CapabilitySets {
+    /// Parse the `CapEff`, `CapPrm`, and `CapInh` fields from the contents of
+    /// a `/proc/<pid>/status` file, such as one opened with
+    /// [`proc_self_status`].
+    ///
+    /// Returns `None` if any of the fields are missing or malformed.
+    ///
+    /// [`proc_self_status`]: crate::io::proc_self_status
+    pub fn from_proc_status(status: &[u8]) -> Option<Self> {
+        Some(Self {
+            effective: capability_flags_from_proc_status(status, "CapEff")?,
+            permitted: capability_flags_from_proc_status(status, "CapPrm")?,
+            inheritable: capability_flags_from_proc_status(status, "CapInh")?,
+        })
+    }
+}
+
+/// Parse a capability set field, such as `CapEff`, `CapBnd`, or `CapAmb`,
+/// from the contents of a `/proc/<pid>/status` file.
+///
+/// Returns `None` if the field is missing or malformed. Bits for capabilities
+/// not known to rustix are preserved.
+///
+/// # References
+///  - [Linux `proc`]
+///
+/// [Linux `proc`]: https://man7.org/linux/man-pages/man5/proc.5.html
+pub fn capability_flags_from_proc_status(status: &[u8], field: &str) -> Option<CapabilityFlags> {
+    let field = field.as_bytes();
+    for line in status.split(|b| *b == b'\n') {
+        if line.len() <= field.len() || !line.starts_with(field) || line[field.len()] != b':' {
+            continue;
+        }
+        let value = core::str::from_utf8(&line[field.len() + 1..]).ok()?.trim();
+        let bits = u64::from_str_radix(value, 16).ok()?;
+        // Safety: Newer kernels may have capabilities not known to rustix,
+        // and their bits must survive a round trip.
+        return Some(unsafe { CapabilityFlags::from_bits_unchecked(bits) });
+    }
+    None
+}
+
+/// `capget(_LINUX_CAPABILITY_VERSION_3, pid)`—Get the capability sets of
+/// a thread.
+///
+/// If `pid` is `None`, this returns the capability sets of the calling
+/// thread.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/capget.2.html
+#[inline]
+#[doc(alias = "capget")]
+pub fn capabilities(pid: Option<Pid>) -> io::Result<CapabilitySets> {
+    let mut header = CapUserHeader {
+        version: LINUX_CAPABILITY_VERSION_3,
+        pid: Pid::as_raw(pid) as c_int,
+    };
+    let mut data = [MaybeUninit::<CapUserData>::uninit(); LINUX_CAPABILITY_U32S_3];
+
+    // Safety: `_LINUX_CAPABILITY_VERSION_3` uses two data structs, and the
+    // kernel fills in both of them on success.
+    let data = unsafe {
+        syscalls::capget(&mut header, data.as_mut_ptr().cast())?;
+        [data[0].assume_init(), data[1].assume_init()]
+    };
+
+    // The kernel splits each 64-bit set into two 32-bit halves. Keep bits
+    // for capabilities not known to rustix, so that passing the result to
+    // `set_capabilities` doesn't drop them.
+    let combine = |lo: u32, hi: u32| unsafe {
+        CapabilityFlags::from_bits_unchecked(u64::from(lo) | (u64::from(hi) << 32))
+    };
+    Ok(CapabilitySets {
+        effective: combine(data[0].effective, data[1].effective),
+        permitted: combine(data[0].permitted, data[1].permitted),
+        inheritable: combine(data[0].inheritable, data[1].inheritable),
+    })
+}
+
+/// `capset(_LINUX_CAPABILITY_VERSION_3, pid, ...)`—Set the capability sets
+/// of a thread.
+///
+/// If `pid` is `None`, this sets the capability sets of the calling thread.
+/// Linux only permits setting the capability sets of the calling thread, so
+/// `pid` should be `None` or the calling thread's id.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/capset.2.html
+#[inline]
+#[doc(alias = "capset")]
+pub fn set_capabilities(pid: Option<Pid>, sets: CapabilitySets) -> io::Result<()> {
+    let mut header = CapUserHeader {
+        version: LINUX_CAPABILITY_VERSION_3,
+        pid: Pid::as_raw(pid) as c_int,
+    };
+
+    // Split each 64-bit set into the two 32-bit halves the kernel expects.
+    let split = |shift: u32| CapUserData {
+        effective: (sets.effective.bits() >> shift) as u32,
+        permitted: (sets.permitted.bits() >> shift) as u32,
+        inheritable: (sets.inheritable.bits() >> shift) as u32,
+    };
+    let data: [CapUserData; LINUX_CAPABILITY_U32S_3] = [split(0), split(32)];
+
+    unsafe { syscalls::capset(&mut header, data.as_ptr()) }
+}
@@ -6,8 +6,12 @@
 //! primitives.
 #![allow(unsafe_code)]
 
-use crate::thread::Timespec;
+use crate::thread::{ClockId, Timespec};
 use crate::{backend, io};
+use core::marker::PhantomData;
+use core::ptr::{null, null_mut};
+use core::sync::atomic::AtomicU32;
+use linux_raw_sys::general::{futex_waitv, FUTEX_32, FUTEX_PRIVATE_FLAG};
 
 pub use backend::thread::{FutexFlags, FutexOperation};
 
@@ -36,3 +40,390 @@ pub unsafe fn futex(
 ) -> io::Result<usize> {
     backend::thread::syscalls::futex(uaddr, op, flags, val, utime, uaddr2, val3)
 }
+
+/// Return the address of `futex`, for passing to the kernel.
+#[inline]
+fn futex_addr(futex: &AtomicU32) -> *mut u32 {
+    (futex as *const AtomicU32).cast::<u32>() as *mut u32
+}
+
+/// Return a pointer to `timeout`, or null if it's `None`.
+#[inline]
+fn timeout_ptr(timeout: Option<&Timespec>) -> *const Timespec {
+    timeout.map_or(null(), |timeout| timeout)
+}
+
+/// Pass `val2` in the `utime` argument, as the requeue operations expect.
+#[inline]
+fn val2_ptr(val2: u32) -> *const Timespec {
+    val2 as usize as *const Timespec
+}
+
+/// `futex(futex, FUTEX_WAIT, val, timeout, NULL, 0)`—Waits on a futex,
+/// if it still contains `val`.
+///
+/// `timeout` is relative, and is measured against `CLOCK_MONOTONIC`. If
+/// `timeout` is `None`, this waits indefinitely. To wait until an absolute
+/// time on a selected clock, use [`futex_wait_bitset`] with a `bitset` of
+/// `!0`.
+///
+/// This fails with [`io::Errno::AGAIN`] if `futex` doesn't contain `val`,
+/// and [`io::Errno::TIMEDOUT`] if the timeout expires.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_wait(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    val: u32,
+    timeout: Option<&Timespec>,
+) -> io::Result<()> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::Wait,
+            flags,
+            val,
+            timeout_ptr(timeout),
+            null_mut(),
+            0,
+        )
+        .map(drop)
+    }
+}
+
+/// `futex(futex, FUTEX_WAKE, n, NULL, NULL, 0)`—Wakes up to `n` waiters on
+/// a futex.
+///
+/// To wake all waiters, pass `i32::MAX as u32` for `n`.
+///
+/// Returns the number of waiters woken.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_wake(futex: &AtomicU32, flags: FutexFlags, n: u32) -> io::Result<usize> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::Wake,
+            flags,
+            n,
+            null(),
+            null_mut(),
+            0,
+        )
+    }
+}
+
+/// `futex(futex, FUTEX_WAIT_BITSET, val, timeout, NULL, bitset)`—Waits on
+/// a futex, if it still contains `val`, to be woken by a wake with a bitset
+/// that intersects `bitset`.
+///
+/// Unlike [`futex_wait`], `timeout` is an absolute time, measured against
+/// `CLOCK_MONOTONIC`, or `CLOCK_REALTIME` if `flags` contains
+/// [`FutexFlags::CLOCK_REALTIME`]. `bitset` must not be zero.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_wait_bitset(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    val: u32,
+    timeout: Option<&Timespec>,
+    bitset: u32,
+) -> io::Result<()> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::WaitBitset,
+            flags,
+            val,
+            timeout_ptr(timeout),
+            null_mut(),
+            bitset,
+        )
+        .map(drop)
+    }
+}
+
+/// `futex(futex, FUTEX_WAKE_BITSET, n, NULL, NULL, bitset)`—Wakes up to `n`
+/// waiters on a futex which are waiting with a bitset that intersects
+/// `bitset`.
+///
+/// Returns the number of waiters woken.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_wake_bitset(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    n: u32,
+    bitset: u32,
+) -> io::Result<usize> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::WakeBitset,
+            flags,
+            n,
+            null(),
+            null_mut(),
+            bitset,
+        )
+    }
+}
+
+/// `futex(futex, FUTEX_REQUEUE, n_wake, n_requeue, futex2, 0)`—Wakes up to
+/// `n_wake` waiters on a futex, and moves up to `n_requeue` of the remaining
+/// waiters to wait on `futex2`.
+///
+/// Returns the number of waiters woken.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_requeue(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    n_wake: u32,
+    n_requeue: u32,
+    futex2: &AtomicU32,
+) -> io::Result<usize> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::Requeue,
+            flags,
+            n_wake,
+            val2_ptr(n_requeue),
+            futex_addr(futex2),
+            0,
+        )
+    }
+}
+
+/// `futex(futex, FUTEX_CMP_REQUEUE, n_wake, n_requeue, futex2, val)`—Like
+/// [`futex_requeue`], but only if `futex` still contains `val`.
+///
+/// This fails with [`io::Errno::AGAIN`] if `futex` doesn't contain `val`.
+///
+/// Returns the number of waiters woken or requeued.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_cmp_requeue(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    n_wake: u32,
+    n_requeue: u32,
+    futex2: &AtomicU32,
+    val: u32,
+) -> io::Result<usize> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::CmpRequeue,
+            flags,
+            n_wake,
+            val2_ptr(n_requeue),
+            futex_addr(futex2),
+            val,
+        )
+    }
+}
+
+/// `futex(futex, FUTEX_LOCK_PI, 0, timeout, NULL, 0)`—Acquires a
+/// priority-inheritance futex, which holds the owner's thread ID.
+///
+/// This is used after an attempt to acquire the lock in userspace fails.
+/// `timeout` is an absolute time, measured against `CLOCK_REALTIME`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_lock_pi(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    timeout: Option<&Timespec>,
+) -> io::Result<()> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::LockPi,
+            flags,
+            0,
+            timeout_ptr(timeout),
+            null_mut(),
+            0,
+        )
+        .map(drop)
+    }
+}
+
+/// `futex(futex, FUTEX_UNLOCK_PI, 0, NULL, NULL, 0)`—Releases a
+/// priority-inheritance futex, and wakes its highest-priority waiter.
+///
+/// This is used when the futex's `FUTEX_WAITERS` bit is set, so it can't be
+/// released in userspace.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_unlock_pi(futex: &AtomicU32, flags: FutexFlags) -> io::Result<()> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::UnlockPi,
+            flags,
+            0,
+            null(),
+            null_mut(),
+            0,
+        )
+        .map(drop)
+    }
+}
+
+/// `futex(futex, FUTEX_WAIT_REQUEUE_PI, val, timeout, pi_futex, 0)`—Waits
+/// on a non-PI futex, if it still contains `val`, to be requeued onto the
+/// priority-inheritance futex `pi_futex` by [`futex_cmp_requeue_pi`].
+///
+/// On success, the calling thread owns `pi_futex`. `timeout` is an absolute
+/// time, measured against `CLOCK_MONOTONIC`, or `CLOCK_REALTIME` if `flags`
+/// contains [`FutexFlags::CLOCK_REALTIME`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_wait_requeue_pi(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    val: u32,
+    timeout: Option<&Timespec>,
+    pi_futex: &AtomicU32,
+) -> io::Result<()> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::WaitRequeuePi,
+            flags,
+            val,
+            timeout_ptr(timeout),
+            futex_addr(pi_futex),
+            0,
+        )
+        .map(drop)
+    }
+}
+
+/// `futex(futex, FUTEX_CMP_REQUEUE_PI, 1, n_requeue, pi_futex, val)`—Wakes
+/// or requeues one waiter from [`futex_wait_requeue_pi`], and requeues up to
+/// `n_requeue` others onto the priority-inheritance futex `pi_futex`, if
+/// `futex` still contains `val`.
+///
+/// Returns the number of waiters woken or requeued.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/futex.2.html
+#[inline]
+pub fn futex_cmp_requeue_pi(
+    futex: &AtomicU32,
+    flags: FutexFlags,
+    n_requeue: u32,
+    pi_futex: &AtomicU32,
+    val: u32,
+) -> io::Result<usize> {
+    unsafe {
+        backend::thread::syscalls::futex(
+            futex_addr(futex),
+            FutexOperation::CmpRequeuePi,
+            flags,
+            1,
+            val2_ptr(n_requeue),
+            futex_addr(pi_futex),
+            val,
+        )
+    }
+}
+
+/// A futex to wait on with [`futex_waitv`], along with the value it's
+/// expected to contain.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug)]
+pub struct FutexWaitv<'a> {
+    waitv: futex_waitv,
+    _futex: PhantomData<&'a AtomicU32>,
+}
+
+impl<'a> FutexWaitv<'a> {
+    /// Construct a new `FutexWaitv` for waiting on `futex`, if it contains
+    /// `val`.
+    ///
+    /// Of `flags`, only [`FutexFlags::PRIVATE`] is meaningful here; the
+    /// clock is selected by [`futex_waitv`].
+    #[inline]
+    pub fn new(futex: &'a AtomicU32, val: u32, flags: FutexFlags) -> Self {
+        let mut waitv_flags = FUTEX_32;
+        if flags.contains(FutexFlags::PRIVATE) {
+            // `FUTEX2_PRIVATE` has the same value as `FUTEX_PRIVATE_FLAG`.
+            waitv_flags |= FUTEX_PRIVATE_FLAG;
+        }
+        Self {
+            waitv: futex_waitv {
+                val: val.into(),
+                uaddr: futex_addr(futex) as usize as u64,
+                flags: waitv_flags,
+                __reserved: 0,
+            },
+            _futex: PhantomData,
+        }
+    }
+}
+
+/// `futex_waitv(waiters, waiters.len(), 0, timeout, clockid)`—Waits on
+/// several futexes at once, until any of them is woken.
+///
+/// `timeout` is an absolute time, measured against `clockid`, which must be
+/// [`ClockId::Monotonic`] or [`ClockId::Realtime`]. There may be at most 128
+/// waiters. This fails with [`io::Errno::AGAIN`] if any futex doesn't
+/// contain its expected value.
+///
+/// Returns the index in `waiters` of a futex that was woken.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/userspace-api/futex2.html
+#[inline]
+pub fn futex_waitv(
+    waiters: &[FutexWaitv<'_>],
+    timeout: Option<&Timespec>,
+    clockid: ClockId,
+) -> io::Result<usize> {
+    backend::thread::syscalls::futex_waitv(waiters, timeout, clockid)
+}
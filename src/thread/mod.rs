@@ -1,5 +1,7 @@
 //! Thread-associated operations.
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod capabilities;
 #[cfg(not(target_os = "redox"))]
 mod clock;
 #[cfg(linux_raw)]
@@ -9,8 +11,17 @@ mod id;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod prctl;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod rseq;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod setns;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use capabilities::{
+    capabilities, capability_flags_from_proc_status, set_capabilities, CapabilityFlags,
+    CapabilitySets,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) use capabilities::{CapUserData, CapUserHeader};
 #[cfg(not(any(
     target_os = "dragonfly",
     target_os = "emscripten",
@@ -26,10 +37,25 @@ pub use clock::{clock_nanosleep_absolute, clock_nanosleep_relative, ClockId};
 #[cfg(not(target_os = "redox"))]
 pub use clock::{nanosleep, NanosleepRelativeResult, Timespec};
 #[cfg(linux_raw)]
-pub use futex::{futex, FutexFlags, FutexOperation};
+pub use futex::{
+    futex, futex_cmp_requeue, futex_cmp_requeue_pi, futex_lock_pi, futex_requeue, futex_unlock_pi,
+    futex_wait, futex_wait_bitset, futex_wait_requeue_pi, futex_waitv, futex_wake,
+    futex_wake_bitset, FutexFlags, FutexOperation, FutexWaitv,
+};
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use id::gettid;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use prctl::*;
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_endian = "little"),
+    target_arch = "riscv64",
+    target_arch = "powerpc64",
+))]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use rseq::RSEQ_SIG;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use rseq::{rseq_register, rseq_unregister, sched_getcpu, Rseq};
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use setns::*;
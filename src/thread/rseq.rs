@@ -0,0 +1,214 @@
+//! Linux restartable sequences.
+//!
+//! # Safety
+//!
+//! The kernel writes to a registered `Rseq` area until it's unregistered or
+//! the thread exits, so it must stay valid for that long.
+#![allow(unsafe_code)]
+
+use crate::{backend, io};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// `RSEQ_CPU_ID_UNINITIALIZED`
+const RSEQ_CPU_ID_UNINITIALIZED: u32 = -1_i32 as u32;
+
+/// `RSEQ_FLAG_UNREGISTER`
+const RSEQ_FLAG_UNREGISTER: u32 = 1 << 0;
+
+/// `RSEQ_SIG` for x86 and x86-64, which encodes as `ud1`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const SIG: u32 = 0x5305_3053;
+
+/// `RSEQ_SIG` for little-endian aarch64, which encodes as `brk #0x45e0`.
+#[cfg(all(target_arch = "aarch64", target_endian = "little"))]
+const SIG: u32 = 0xd428_bc00;
+
+/// `RSEQ_SIG` for riscv64, which encodes as `csrw mhartid, x0`.
+#[cfg(target_arch = "riscv64")]
+const SIG: u32 = 0xf140_1073;
+
+/// `RSEQ_SIG` for powerpc64, which encodes as `twui r5, 11`.
+#[cfg(target_arch = "powerpc64")]
+const SIG: u32 = 0x0fe5_000b;
+
+/// The signature glibc uses for restartable sequences on this platform.
+///
+/// The signature must immediately precede the abort handler of each
+/// critical section, so it's encoded as an instruction which traps.
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_endian = "little"),
+    target_arch = "riscv64",
+    target_arch = "powerpc64",
+))]
+pub const RSEQ_SIG: u32 = SIG;
+
+/// `struct rseq`—A thread's restartable sequences area.
+///
+/// Once registered with [`rseq_register`], the kernel keeps the CPU fields
+/// up to date whenever the thread returns to userspace.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/userspace-api/rseq.html
+#[repr(C, align(32))]
+#[derive(Debug)]
+pub struct Rseq {
+    cpu_id_start: AtomicU32,
+    cpu_id: AtomicU32,
+    rseq_cs: UnsafeCell<u64>,
+    flags: AtomicU32,
+    node_id: AtomicU32,
+    mm_cid: AtomicU32,
+}
+
+// Safety: `rseq_cs` is only accessed through raw pointers, by the owning
+// thread and the kernel.
+unsafe impl Sync for Rseq {}
+
+impl Rseq {
+    /// Create a new unregistered `Rseq` area.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            cpu_id_start: AtomicU32::new(0),
+            cpu_id: AtomicU32::new(RSEQ_CPU_ID_UNINITIALIZED),
+            rseq_cs: UnsafeCell::new(0),
+            flags: AtomicU32::new(0),
+            node_id: AtomicU32::new(0),
+            mm_cid: AtomicU32::new(0),
+        }
+    }
+
+    /// Return the `cpu_id` field—the CPU the thread is running on, or `None`
+    /// if this area isn't registered.
+    #[inline]
+    pub fn cpu_id(&self) -> Option<u32> {
+        let cpu_id = self.cpu_id.load(Ordering::Relaxed);
+        if (cpu_id as i32) < 0 {
+            None
+        } else {
+            Some(cpu_id)
+        }
+    }
+
+    /// Return the `cpu_id_start` field—the CPU the thread is running on,
+    /// which is always a valid CPU number, even before registration.
+    ///
+    /// Critical sections compare this against the CPU they started on.
+    #[inline]
+    pub fn cpu_id_start(&self) -> u32 {
+        self.cpu_id_start.load(Ordering::Relaxed)
+    }
+
+    /// Return the `node_id` field—the NUMA node the thread is running on.
+    ///
+    /// This is only updated on Linux 6.3 and later.
+    #[inline]
+    pub fn node_id(&self) -> u32 {
+        self.node_id.load(Ordering::Relaxed)
+    }
+
+    /// Return the `mm_cid` field—the thread's concurrency ID, which is
+    /// unique among the threads of the process currently running, and is
+    /// less than the number of CPUs and the number of threads. It's useful
+    /// as an index into per-CPU data which stays compact.
+    ///
+    /// This is only updated on Linux 6.3 and later.
+    #[inline]
+    pub fn mm_cid(&self) -> u32 {
+        self.mm_cid.load(Ordering::Relaxed)
+    }
+
+    /// Return the CPU the thread is running on, from `cpu_id` if this area
+    /// is registered, or otherwise from [`sched_getcpu`].
+    #[inline]
+    pub fn current_cpu(&self) -> io::Result<usize> {
+        match self.cpu_id() {
+            Some(cpu_id) => Ok(cpu_id as usize),
+            None => sched_getcpu(),
+        }
+    }
+
+    /// Return a pointer to the `rseq_cs` field, which points to the
+    /// `struct rseq_cs` describing the critical section in progress, or is
+    /// zero.
+    #[inline]
+    pub fn rseq_cs(&self) -> *mut u64 {
+        self.rseq_cs.get()
+    }
+}
+
+impl Default for Rseq {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `rseq(rseq, sizeof(struct rseq), 0, sig)`—Registers a restartable
+/// sequences area for the calling thread.
+///
+/// `sig` is the signature which must precede each abort handler, such as
+/// [`RSEQ_SIG`]. A thread can only have one area registered at a time, and
+/// glibc 2.35 and later register one for every thread, in which case this
+/// fails with [`io::Errno::INVAL`], or [`io::Errno::BUSY`] if `rseq` is the
+/// same area.
+///
+/// # Safety
+///
+/// `rseq` must stay valid, and must not be moved or used for anything else,
+/// until it's unregistered with [`rseq_unregister`] or the thread exits.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/userspace-api/rseq.html
+#[inline]
+pub unsafe fn rseq_register(rseq: &Rseq, sig: u32) -> io::Result<()> {
+    backend::thread::syscalls::rseq(rseq, 0, sig)
+}
+
+/// `rseq(rseq, sizeof(struct rseq), RSEQ_FLAG_UNREGISTER, sig)`—Unregisters
+/// the calling thread's restartable sequences area.
+///
+/// `rseq` and `sig` must be the area and signature that were registered.
+/// Afterwards, [`Rseq::cpu_id`] returns `None` again.
+///
+/// # Safety
+///
+/// No critical section may be in progress on `rseq`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/userspace-api/rseq.html
+#[inline]
+pub unsafe fn rseq_unregister(rseq: &Rseq, sig: u32) -> io::Result<()> {
+    backend::thread::syscalls::rseq(rseq, RSEQ_FLAG_UNREGISTER, sig)
+}
+
+/// `sched_getcpu()`—Returns the CPU the calling thread is running on.
+///
+/// This always asks the kernel, and doesn't look at any [`Rseq`] area the
+/// thread has registered, since rustix doesn't track which one that is. With
+/// the linux_raw backend, it uses the vDSO's `getcpu` where it's available.
+/// To read the CPU from a registered area instead, use
+/// [`Rseq::current_cpu`].
+///
+/// Unlike glibc's `sched_getcpu`, this doesn't read the area glibc 2.35 and
+/// later register for each thread either. Finding it takes the
+/// `__rseq_offset` and `__rseq_size` symbols, which older glibc versions
+/// and other libcs don't define, and Rust can't link to them weakly.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man3/sched_getcpu.3.html
+#[inline]
+pub fn sched_getcpu() -> io::Result<usize> {
+    backend::thread::syscalls::sched_getcpu()
+}
@@ -76,6 +76,8 @@ bitflags! {
         const NEWTIME = CLONE_NEWTIME;
         /// `CLONE_NEWUSER`.
         const NEWUSER = CLONE_NEWUSER;
+        /// `CLONE_NEWUTS`.
+        const NEWUTS = CLONE_NEWUTS;
         /// `CLONE_SYSVSEM`.
         const SYSVSEM = CLONE_SYSVSEM;
     }
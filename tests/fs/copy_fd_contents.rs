@@ -0,0 +1,117 @@
+use rustix::fs::{copy_fd_contents, cwd, openat, seek, tell, CopyMethod, Mode, OFlags};
+use rustix::io::{pipe, read, write, SeekFrom};
+use std::fs;
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 % 251) as u8).collect()
+}
+
+fn open_rw(path: &std::path::Path) -> rustix::fd::OwnedFd {
+    openat(
+        cwd(),
+        path,
+        OFlags::RDWR | OFlags::CREATE | OFlags::CLOEXEC,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_copy_fd_contents_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let contents = data(1 << 20);
+    fs::write(dir.path().join("from"), &contents).unwrap();
+    let from = open_rw(&dir.path().join("from"));
+    let to = open_rw(&dir.path().join("to"));
+
+    // Copy from the middle of `from`, to the middle of `to`.
+    seek(&from, SeekFrom::Start(100)).unwrap();
+    write(&to, b"header").unwrap();
+    let (copied, _method) = copy_fd_contents(&from, &to, u64::MAX).unwrap();
+    assert_eq!(copied, contents.len() as u64 - 100);
+    assert_eq!(tell(&from).unwrap(), contents.len() as u64);
+    assert_eq!(tell(&to).unwrap(), copied + 6);
+
+    let copy = fs::read(dir.path().join("to")).unwrap();
+    assert_eq!(&copy[..6], b"header");
+    assert_eq!(&copy[6..], &contents[100..]);
+
+    // At the end of `from`, there's nothing left to copy.
+    assert_eq!(copy_fd_contents(&from, &to, u64::MAX).unwrap().0, 0);
+}
+
+#[test]
+fn test_copy_fd_contents_len() {
+    let dir = tempfile::tempdir().unwrap();
+    let contents = data(100_000);
+    fs::write(dir.path().join("from"), &contents).unwrap();
+    let from = open_rw(&dir.path().join("from"));
+    let to = open_rw(&dir.path().join("to"));
+
+    assert_eq!(copy_fd_contents(&from, &to, 12345).unwrap().0, 12345);
+    assert_eq!(tell(&from).unwrap(), 12345);
+    assert_eq!(tell(&to).unwrap(), 12345);
+    assert_eq!(copy_fd_contents(&from, &to, 0).unwrap().0, 0);
+    assert_eq!(fs::read(dir.path().join("to")).unwrap(), &contents[..12345]);
+}
+
+#[test]
+fn test_copy_fd_contents_from_pipe() {
+    let dir = tempfile::tempdir().unwrap();
+    let contents = data(10_000);
+    let (reader, writer) = pipe().unwrap();
+    write(&writer, &contents).unwrap();
+    drop(writer);
+    let to = open_rw(&dir.path().join("to"));
+
+    let (copied, method) = copy_fd_contents(&reader, &to, u64::MAX).unwrap();
+    assert_eq!(copied, contents.len() as u64);
+    assert_ne!(method, CopyMethod::Reflink);
+    assert_eq!(fs::read(dir.path().join("to")).unwrap(), contents);
+}
+
+#[test]
+fn test_copy_fd_contents_to_pipe() {
+    let dir = tempfile::tempdir().unwrap();
+    let contents = data(10_000);
+    fs::write(dir.path().join("from"), &contents).unwrap();
+    let from = open_rw(&dir.path().join("from"));
+    let (reader, writer) = pipe().unwrap();
+
+    let (copied, method) = copy_fd_contents(&from, &writer, u64::MAX).unwrap();
+    assert_eq!(copied, contents.len() as u64);
+    assert_ne!(method, CopyMethod::Reflink);
+    drop(writer);
+
+    let mut buf = vec![0; contents.len() + 1];
+    let mut n = 0;
+    loop {
+        match read(&reader, &mut buf[n..]).unwrap() {
+            0 => break,
+            m => n += m,
+        }
+    }
+    assert_eq!(&buf[..n], &contents[..]);
+}
+
+#[test]
+fn test_copy_fd_contents_procfs() {
+    let dir = tempfile::tempdir().unwrap();
+    // Files in procfs report a size of 0, and `copy_file_range` copies
+    // nothing from them.
+    let from = openat(
+        cwd(),
+        "/proc/self/mountinfo",
+        OFlags::RDONLY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap();
+    let to = open_rw(&dir.path().join("to"));
+
+    let (copied, _method) = copy_fd_contents(&from, &to, u64::MAX).unwrap();
+    assert!(copied > 0);
+    assert_eq!(
+        fs::read(dir.path().join("to")).unwrap().len() as u64,
+        copied
+    );
+}
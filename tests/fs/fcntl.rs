@@ -15,3 +15,150 @@ fn test_fcntl_dupfd_cloexec() {
     let new = rustix::fs::fcntl_dupfd_cloexec(&file, 700).unwrap();
     assert_eq!(new.as_fd().as_raw_fd(), 700);
 }
+
+#[cfg(not(any(target_os = "redox", target_os = "wasi")))]
+#[test]
+fn test_fcntl_lock() {
+    use rustix::fs::{fcntl_getlk, fcntl_lock, FileLock, FileLockKind};
+
+    let file = tempfile::tempfile().unwrap();
+
+    fcntl_lock(&file, &FileLock::new(FileLockKind::Write, 0, 10)).unwrap();
+
+    // Process-associated locks never conflict with the process's own locks.
+    let probe = FileLock::new(FileLockKind::Write, 5, 10);
+    assert_eq!(fcntl_getlk(&file, &probe).unwrap(), None);
+
+    fcntl_lock(&file, &FileLock::new(FileLockKind::Unlock, 0, 0)).unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_fcntl_ofd_lock() {
+    use rustix::fs::{
+        fcntl_ofd_getlk, fcntl_ofd_lock, FileLock, FileLockConflict, FileLockKind, FileLockWhence,
+    };
+    use rustix::io::Errno;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    let a = std::fs::File::create(&path).unwrap();
+    let b = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .unwrap();
+
+    let lock = FileLock::new(FileLockKind::Write, 0, 10);
+    fcntl_ofd_lock(&a, &lock).unwrap();
+
+    // Open file description locks conflict between separate opens, even
+    // within one process.
+    let probe = FileLock::new(FileLockKind::Read, 5, 10);
+    assert_eq!(
+        fcntl_ofd_getlk(&b, &probe).unwrap(),
+        Some(FileLockConflict {
+            lock: FileLock {
+                kind: FileLockKind::Write,
+                whence: FileLockWhence::Start,
+                start: 0,
+                len: 10,
+            },
+            pid: None,
+        })
+    );
+    assert_eq!(fcntl_ofd_lock(&b, &probe), Err(Errno::AGAIN));
+
+    // Ranges that don't overlap don't conflict.
+    let other = FileLock::new(FileLockKind::Write, 10, 10);
+    assert_eq!(fcntl_ofd_getlk(&b, &other).unwrap(), None);
+    fcntl_ofd_lock(&b, &other).unwrap();
+
+    fcntl_ofd_lock(&a, &FileLock::new(FileLockKind::Unlock, 0, 0)).unwrap();
+    fcntl_ofd_lock(&b, &probe).unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_fcntl_lease() {
+    use rustix::fs::{fcntl_getlease, fcntl_setlease, LeaseType};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    drop(std::fs::File::create(&path).unwrap());
+    let file = std::fs::File::open(&path).unwrap();
+
+    assert_eq!(fcntl_getlease(&file).unwrap(), LeaseType::Unlock);
+    match fcntl_setlease(&file, LeaseType::Read) {
+        Ok(()) => {
+            assert_eq!(fcntl_getlease(&file).unwrap(), LeaseType::Read);
+            fcntl_setlease(&file, LeaseType::Unlock).unwrap();
+        }
+        // Some filesystems, such as overlayfs, don't support leases.
+        Err(rustix::io::Errno::INVAL) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(fcntl_getlease(&file).unwrap(), LeaseType::Unlock);
+}
+
+#[cfg(feature = "process")]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_fcntl_owner_and_signal() {
+    use rustix::fs::{fcntl_getown_ex, fcntl_getsig, fcntl_setown_ex, fcntl_setsig, FileOwner};
+    use rustix::process::{getpid, Signal};
+
+    let (reader, _writer) = rustix::io::pipe().unwrap();
+
+    assert_eq!(fcntl_getown_ex(&reader).unwrap(), None);
+    fcntl_setown_ex(&reader, Some(FileOwner::Process(getpid()))).unwrap();
+    assert_eq!(
+        fcntl_getown_ex(&reader).unwrap(),
+        Some(FileOwner::Process(getpid()))
+    );
+    fcntl_setown_ex(&reader, None).unwrap();
+    assert_eq!(fcntl_getown_ex(&reader).unwrap(), None);
+
+    assert_eq!(fcntl_getsig(&reader).unwrap(), 0);
+    fcntl_setsig(&reader, Signal::Usr1 as _).unwrap();
+    assert_eq!(
+        Signal::from_raw(fcntl_getsig(&reader).unwrap()),
+        Some(Signal::Usr1)
+    );
+
+    // Real-time signals round-trip too.
+    let sigrt = 40;
+    fcntl_setsig(&reader, sigrt).unwrap();
+    let sig = fcntl_getsig(&reader).unwrap();
+    assert_eq!(sig, sigrt);
+    fcntl_setsig(&reader, 0).unwrap();
+    assert_eq!(fcntl_getsig(&reader).unwrap(), 0);
+    fcntl_setsig(&reader, sig).unwrap();
+    assert_eq!(fcntl_getsig(&reader).unwrap(), sigrt);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_fcntl_notify() {
+    use rustix::fs::{fcntl_notify, openat, DnotifyFlags, Mode, OFlags};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(
+        rustix::fs::cwd(),
+        tmp.path(),
+        OFlags::RDONLY | OFlags::DIRECTORY,
+        Mode::empty(),
+    )
+    .unwrap();
+
+    // Register and then cancel, without changing the directory, so that no
+    // `SIGIO` is delivered.
+    fcntl_notify(&dir, DnotifyFlags::CREATE | DnotifyFlags::MULTISHOT).unwrap();
+    fcntl_notify(&dir, DnotifyFlags::empty()).unwrap();
+
+    let (reader, _writer) = rustix::io::pipe().unwrap();
+    assert_eq!(
+        fcntl_notify(&reader, DnotifyFlags::MODIFY),
+        Err(rustix::io::Errno::NOTDIR)
+    );
+}
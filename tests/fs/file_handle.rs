@@ -0,0 +1,46 @@
+use rustix::fs::{
+    cwd, name_to_handle_at, open_by_handle_at, openat, AtFlags, FileHandle, Mode, OFlags,
+};
+use rustix::io::Errno;
+use std::io::Read;
+
+#[test]
+fn test_name_to_handle_at() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("file"), b"contents").unwrap();
+    let dirfd = openat(cwd(), dir.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    let handle = match name_to_handle_at(&dirfd, "file", AtFlags::empty()) {
+        Ok(handle) => handle,
+        // Some filesystems, such as overlayfs in some configurations, don't
+        // support file handles.
+        Err(Errno::OPNOTSUPP) | Err(Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(!handle.as_bytes().is_empty());
+
+    // A handle is stable across renames.
+    std::fs::rename(dir.path().join("file"), dir.path().join("renamed")).unwrap();
+    let renamed = name_to_handle_at(&dirfd, "renamed", AtFlags::empty()).unwrap();
+    assert_eq!(renamed, handle);
+
+    let rebuilt = FileHandle::new(handle.handle_type(), handle.as_bytes(), handle.mount_id());
+    assert_eq!(rebuilt, handle);
+
+    // `AT_EMPTY_PATH` names `dirfd` itself.
+    let dir_handle = name_to_handle_at(&dirfd, "", AtFlags::EMPTY_PATH).unwrap();
+    assert_ne!(dir_handle, handle);
+
+    match open_by_handle_at(&dirfd, &handle, OFlags::RDONLY | OFlags::CLOEXEC) {
+        Ok(fd) => {
+            let mut contents = String::new();
+            std::fs::File::from(fd)
+                .read_to_string(&mut contents)
+                .unwrap();
+            assert_eq!(contents, "contents");
+        }
+        // Opening by handle requires `CAP_DAC_READ_SEARCH`.
+        Err(Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
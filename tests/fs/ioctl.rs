@@ -0,0 +1,158 @@
+use rustix::fs::{
+    fiemap_extents, ioctl_ficlone, ioctl_fideduperange, ioctl_fiemap, ioctl_fsgetxattr,
+    ioctl_fssetxattr, ioctl_getflags, ioctl_setflags, Fiemap, FiemapExtent, FiemapExtentFlags,
+    FiemapFlags, FileDedupeRange, FileDedupeResult, InodeFlags,
+};
+use rustix::io::Errno;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Errors meaning the filesystem can't share extents.
+fn unsupported(err: Errno) -> bool {
+    err == Errno::OPNOTSUPP || err == Errno::XDEV || err == Errno::INVAL || err == Errno::NOTTY
+}
+
+#[test]
+fn test_ioctl_ficlone() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut src = std::fs::File::create(dir.path().join("src")).unwrap();
+    src.write_all(&[7; 8192]).unwrap();
+    let src = std::fs::File::open(dir.path().join("src")).unwrap();
+    let mut dest = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .open(dir.path().join("dest"))
+        .unwrap();
+
+    match ioctl_ficlone(&dest, &src) {
+        Ok(()) => {
+            let mut contents = Vec::new();
+            dest.seek(SeekFrom::Start(0)).unwrap();
+            dest.read_to_end(&mut contents).unwrap();
+            assert_eq!(contents, vec![7; 8192]);
+        }
+        Err(err) if unsupported(err) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn test_ioctl_fideduperange() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut src = std::fs::File::create(dir.path().join("src")).unwrap();
+    src.write_all(&[7; 8192]).unwrap();
+    let src = std::fs::File::open(dir.path().join("src")).unwrap();
+    let mut same = std::fs::File::create(dir.path().join("same")).unwrap();
+    same.write_all(&[7; 8192]).unwrap();
+    let mut differs = std::fs::File::create(dir.path().join("differs")).unwrap();
+    differs.write_all(&[8; 8192]).unwrap();
+
+    let dests = [
+        FileDedupeRange {
+            dest_fd: rustix::fd::AsFd::as_fd(&same),
+            dest_offset: 0,
+        },
+        FileDedupeRange {
+            dest_fd: rustix::fd::AsFd::as_fd(&differs),
+            dest_offset: 0,
+        },
+    ];
+    match ioctl_fideduperange(&src, 0, 8192, &dests) {
+        Ok(results) => {
+            assert_eq!(results.len(), 2);
+            assert_eq!(results[0], FileDedupeResult::Deduped(8192));
+            assert_eq!(results[1], FileDedupeResult::Differs);
+        }
+        Err(err) if unsupported(err) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn test_ioctl_getflags_setflags() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = std::fs::File::create(dir.path().join("file")).unwrap();
+
+    let flags = match ioctl_getflags(&file) {
+        Ok(flags) => flags,
+        // tmpfs and some other filesystems don't support inode flags.
+        Err(Errno::NOTTY) | Err(Errno::OPNOTSUPP) | Err(Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(!flags.contains(InodeFlags::IMMUTABLE));
+
+    // `NOATIME` may be set by the file's owner without extra privileges.
+    match ioctl_setflags(&file, flags | InodeFlags::NOATIME) {
+        Ok(()) => {
+            assert!(ioctl_getflags(&file).unwrap().contains(InodeFlags::NOATIME));
+            ioctl_setflags(&file, flags).unwrap();
+        }
+        Err(Errno::OPNOTSUPP) | Err(Errno::INVAL) | Err(Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(ioctl_getflags(&file).unwrap(), flags);
+}
+
+#[test]
+fn test_ioctl_fsgetxattr_fssetxattr() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = std::fs::File::create(dir.path().join("file")).unwrap();
+
+    let attr = match ioctl_fsgetxattr(&file) {
+        Ok(attr) => attr,
+        Err(Errno::NOTTY) | Err(Errno::OPNOTSUPP) | Err(Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+
+    // Writing back the current attributes is always permitted for the owner.
+    match ioctl_fssetxattr(&file, &attr) {
+        Ok(()) => assert_eq!(ioctl_fsgetxattr(&file).unwrap(), attr),
+        Err(Errno::OPNOTSUPP) | Err(Errno::INVAL) | Err(Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn test_ioctl_fiemap() {
+    assert_eq!(std::mem::size_of::<FiemapExtent>(), 56);
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut file = std::fs::File::create(dir.path().join("file")).unwrap();
+
+    // Write enough separate chunks of data, with holes between them, that
+    // `fiemap_extents` needs more than one `ioctl` to map them.
+    const CHUNKS: u64 = 40;
+    for i in 0..CHUNKS {
+        file.seek(SeekFrom::Start(i << 20)).unwrap();
+        file.write_all(&[1; 1 << 16]).unwrap();
+    }
+
+    let mut count = Fiemap::new(0, u64::MAX, FiemapFlags::SYNC, []);
+    let count = match ioctl_fiemap(&file, &mut count) {
+        Ok(count) => count,
+        // tmpfs and some other filesystems don't support `FIEMAP`.
+        Err(Errno::OPNOTSUPP) | Err(Errno::NOTTY) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(count >= CHUNKS as usize);
+
+    // Map the first few extents into a buffer owned by the caller.
+    let mut fiemap = Fiemap::new(
+        0,
+        u64::MAX,
+        FiemapFlags::empty(),
+        [FiemapExtent::default(); 8],
+    );
+    assert_eq!(ioctl_fiemap(&file, &mut fiemap), Ok(8));
+    assert_eq!(fiemap.extents().len(), 8);
+
+    let all = fiemap_extents(&file, FiemapFlags::empty())
+        .collect::<rustix::io::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(all.len(), count);
+    assert_eq!(&all[..8], fiemap.extents());
+    assert!(all.last().unwrap().flags.contains(FiemapExtentFlags::LAST));
+    assert_eq!(all[0].logical, 0);
+    let mapped: u64 = all.iter().map(|e| e.length).sum();
+    assert_eq!(mapped, CHUNKS << 16);
+}
@@ -6,14 +6,20 @@
 #![cfg_attr(io_lifetimes_use_std, feature(io_safety))]
 #![cfg_attr(core_c_str, feature(core_c_str))]
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod copy_fd_contents;
 mod cwd;
 mod dir;
 mod fcntl;
 mod file;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod file_handle;
 #[cfg(not(target_os = "wasi"))]
 mod flock;
 mod futimens;
 mod invalid_offset;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod ioctl;
 mod long_paths;
 #[cfg(not(any(
     target_os = "dragonfly",
@@ -29,6 +35,8 @@ mod long_paths;
     target_os = "wasi",
 )))]
 mod makedev;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod memfd_secret;
 mod mkdirat;
 mod mknodat;
 #[cfg(any(target_os = "android", target_os = "linux"))]
@@ -37,6 +45,17 @@ mod openat;
 mod openat2;
 mod readdir;
 mod renameat;
+#[cfg(target_os = "linux")]
+mod shm;
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris",
+))]
+mod sparse;
 #[cfg(not(any(
     target_os = "haiku",
     target_os = "illumos",
@@ -47,5 +66,11 @@ mod renameat;
 mod statfs;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod statx;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod sync;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod tmpfile;
 mod utimensat;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod walk;
 mod y2038;
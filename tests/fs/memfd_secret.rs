@@ -0,0 +1,14 @@
+use rustix::fs::{fstat, ftruncate, memfd_secret, MemfdSecretFlags};
+use rustix::io::Errno;
+
+#[test]
+fn test_memfd_secret() {
+    // `memfd_secret` isn't available everywhere, and may be disabled.
+    let fd = match memfd_secret(MemfdSecretFlags::CLOEXEC) {
+        Ok(fd) => fd,
+        Err(Errno::NOSYS) => return,
+        Err(err) => Err(err).unwrap(),
+    };
+    ftruncate(&fd, 4096).unwrap();
+    assert_eq!(fstat(&fd).unwrap().st_size, 4096);
+}
@@ -0,0 +1,65 @@
+use rustix::fs::{fstat, ftruncate, shm_open, shm_unlink, Mode, OFlags};
+use rustix::io::{pread, pwrite, Errno};
+
+#[test]
+fn test_shm_open() {
+    if !std::path::Path::new("/dev/shm").is_dir() {
+        return;
+    }
+
+    let name = format!("/rustix-test-shm-{}", std::process::id());
+    let fd = shm_open(
+        &name,
+        OFlags::CREATE | OFlags::EXCL | OFlags::RDWR,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+    ftruncate(&fd, 4096).unwrap();
+    pwrite(&fd, b"shared", 0).unwrap();
+
+    assert_eq!(
+        shm_open(
+            &name,
+            OFlags::CREATE | OFlags::EXCL | OFlags::RDWR,
+            Mode::empty()
+        )
+        .unwrap_err(),
+        Errno::EXIST
+    );
+
+    // Leading slashes are ignored, and the object is an ordinary file in
+    // `/dev/shm`, as it is with glibc.
+    let other = shm_open(format!("//{}", &name[1..]), OFlags::RDONLY, Mode::empty()).unwrap();
+    assert_eq!(fstat(&other).unwrap().st_size, 4096);
+    let mut buf = [0_u8; 6];
+    pread(&other, &mut buf, 0).unwrap();
+    assert_eq!(&buf, b"shared");
+    assert_eq!(
+        std::fs::read(format!("/dev/shm{}", name)).unwrap()[..6],
+        *b"shared"
+    );
+
+    shm_unlink(&name).unwrap();
+    assert_eq!(
+        shm_open(&name, OFlags::RDONLY, Mode::empty()).unwrap_err(),
+        Errno::NOENT
+    );
+    assert_eq!(shm_unlink(&name).unwrap_err(), Errno::NOENT);
+}
+
+#[test]
+fn test_shm_names() {
+    for name in &["", "/", "//", "a/b", "/a/", ".", "..", "/.", "/.."] {
+        assert_eq!(
+            shm_open(*name, OFlags::RDONLY, Mode::empty()).unwrap_err(),
+            Errno::INVAL
+        );
+        assert_eq!(shm_unlink(*name).unwrap_err(), Errno::INVAL);
+    }
+
+    let long = "x".repeat(256);
+    assert_eq!(
+        shm_open(&long, OFlags::RDONLY, Mode::empty()).unwrap_err(),
+        Errno::NAMETOOLONG
+    );
+}
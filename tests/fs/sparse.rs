@@ -0,0 +1,41 @@
+use rustix::fs::{seek_data, seek_hole, sparse_regions};
+use rustix::io::{pwrite, Errno};
+
+#[test]
+fn test_seek_data_hole() {
+    let file = tempfile::tempfile().unwrap();
+    assert_eq!(seek_data(&file, 0), Err(Errno::NXIO));
+    assert_eq!(seek_hole(&file, 0), Err(Errno::NXIO));
+
+    pwrite(&file, b"data", 0).unwrap();
+    assert_eq!(seek_data(&file, 0), Ok(0));
+    assert_eq!(seek_hole(&file, 0), Ok(4));
+    assert_eq!(seek_data(&file, 4), Err(Errno::NXIO));
+}
+
+#[test]
+fn test_sparse_regions() {
+    const LEN: u64 = 4 << 20;
+    const DATA: u64 = 1 << 20;
+
+    let file = tempfile::tempfile().unwrap();
+    assert_eq!(sparse_regions(&file).count(), 0);
+
+    file.set_len(LEN).unwrap();
+    pwrite(&file, &[1; 4096], DATA).unwrap();
+
+    let regions = sparse_regions(&file)
+        .collect::<rustix::io::Result<Vec<_>>>()
+        .unwrap();
+
+    // Filesystems may report data at a coarser granularity, or not track
+    // holes at all, but the written bytes must be covered.
+    assert!(!regions.is_empty());
+    assert!(regions
+        .iter()
+        .any(|r| r.start <= DATA && r.end >= DATA + 4096));
+    for pair in regions.windows(2) {
+        assert!(pair[0].end < pair[1].start);
+    }
+    assert!(regions.last().unwrap().end <= LEN);
+}
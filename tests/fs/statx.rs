@@ -8,7 +8,7 @@ fn test_statx_unknown_flags() {
     // unknown bits. Exclude `STATX__RESERVED` here as that evokes an explicit
     // failure; that's tested separately below.
     let too_many_flags =
-        unsafe { StatxFlags::from_bits_retain(!0 & !linux_raw_sys::general::STATX__RESERVED) };
+        unsafe { StatxFlags::from_bits_unchecked(!0 & !linux_raw_sys::general::STATX__RESERVED) };
 
     // It's also ok to pass such flags to `statx`.
     let result = rustix::fs::statx(&f, "Cargo.toml", AtFlags::empty(), too_many_flags).unwrap();
@@ -29,9 +29,78 @@ fn test_statx_reserved() {
     // It's ok (though still unwise) to construct a `STATX__RESERVED` flag
     // value but `statx` should reliably fail with `INVAL`.
     let reserved =
-        unsafe { StatxFlags::from_bits_retain(linux_raw_sys::general::STATX__RESERVED) };
+        unsafe { StatxFlags::from_bits_unchecked(linux_raw_sys::general::STATX__RESERVED) };
     match rustix::fs::statx(&f, "Cargo.toml", AtFlags::empty(), reserved) {
         Ok(_) => panic!("statx succeeded with `STATX__RESERVED`"),
         Err(err) => assert_eq!(err, rustix::io::Errno::INVAL),
     }
 }
+
+#[test]
+fn test_statx_accessors() {
+    use rustix::fs::{fstat, statx, AtFlags, StatxAttributes, StatxFlags};
+
+    let file = tempfile::tempfile().unwrap();
+    rustix::io::write(&file, b"hello").unwrap();
+
+    let mask = StatxFlags::BASIC_STATS | StatxFlags::MNT_ID | StatxFlags::DIOALIGN;
+    let x = match statx(&file, "", AtFlags::EMPTY_PATH, mask) {
+        Ok(x) => x,
+        Err(rustix::io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(x.mask().contains(StatxFlags::BASIC_STATS));
+    assert_eq!(
+        x.mount_id().is_some(),
+        x.mask().contains(StatxFlags::MNT_ID)
+    );
+    assert_eq!(
+        x.dio_mem_align().is_some(),
+        x.mask().contains(StatxFlags::DIOALIGN)
+    );
+    assert_eq!(
+        x.dio_offset_align().is_some(),
+        x.mask().contains(StatxFlags::DIOALIGN)
+    );
+    assert!(!x.attributes().contains(StatxAttributes::MOUNT_ROOT));
+
+    let stat = fstat(&file).unwrap();
+    let converted = x.to_stat().unwrap();
+    assert_eq!(converted.st_dev, stat.st_dev);
+    assert_eq!(converted.st_ino, stat.st_ino);
+    assert_eq!(converted.st_mode, stat.st_mode);
+    assert_eq!(converted.st_nlink, stat.st_nlink);
+    assert_eq!(converted.st_size, 5);
+    assert_eq!(converted.st_mtime, stat.st_mtime);
+    assert_eq!(converted.st_mtime_nsec, stat.st_mtime_nsec);
+}
+
+#[test]
+fn test_statx_mount_root() {
+    use rustix::fs::{cwd, statx, AtFlags, StatxAttributes, StatxFlags};
+
+    let x = match statx(cwd(), "/", AtFlags::empty(), StatxFlags::BASIC_STATS) {
+        Ok(x) => x,
+        Err(rustix::io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    // `STATX_ATTR_MOUNT_ROOT` is reported since Linux 5.8.
+    if x.attributes_mask().contains(StatxAttributes::MOUNT_ROOT) {
+        assert!(x.attributes().contains(StatxAttributes::MOUNT_ROOT));
+    }
+}
+
+#[test]
+fn test_statx_with_fallback() {
+    use rustix::fs::{statat, statx_with_fallback, AtFlags, StatxFlags};
+
+    let f = std::fs::File::open(".").unwrap();
+    let x =
+        statx_with_fallback(&f, "Cargo.toml", AtFlags::empty(), StatxFlags::BASIC_STATS).unwrap();
+    assert!(x.mask().contains(StatxFlags::BASIC_STATS));
+
+    let stat = statat(&f, "Cargo.toml", AtFlags::empty()).unwrap();
+    assert_eq!(x.stx_ino, stat.st_ino as u64);
+    assert_eq!(x.stx_size, stat.st_size as u64);
+    assert_eq!(u32::from(x.stx_mode), stat.st_mode as u32);
+}
@@ -0,0 +1,40 @@
+use rustix::fs::{readahead, sync, sync_file_range, syncfs, SyncFileRangeFlags};
+use std::io::Write;
+
+#[test]
+fn test_syncfs() {
+    let file = tempfile::tempfile().unwrap();
+    syncfs(&file).unwrap();
+    sync();
+}
+
+#[test]
+fn test_sync_file_range() {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&[0; 8192]).unwrap();
+
+    // Start writeback of the first page, then wait for all of it.
+    sync_file_range(&file, 0, 4096, SyncFileRangeFlags::WRITE).unwrap();
+    sync_file_range(
+        &file,
+        0,
+        0,
+        SyncFileRangeFlags::WAIT_BEFORE
+            | SyncFileRangeFlags::WRITE
+            | SyncFileRangeFlags::WAIT_AFTER,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_readahead() {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&[0; 8192]).unwrap();
+
+    readahead(&file, 0, 8192).unwrap();
+    // Reading ahead past the end of the file is harmless.
+    readahead(&file, 1 << 20, 4096).unwrap();
+
+    let (reader, _writer) = rustix::io::pipe().unwrap();
+    assert_eq!(readahead(&reader, 0, 4096), Err(rustix::io::Errno::INVAL));
+}
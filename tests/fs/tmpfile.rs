@@ -0,0 +1,77 @@
+use rustix::fs::{cwd, fstat, openat, Mode, OFlags, TmpFile};
+use rustix::io::{write, Errno};
+use std::fs;
+
+fn open_dir(path: &std::path::Path) -> rustix::fd::OwnedFd {
+    openat(
+        cwd(),
+        path,
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap()
+}
+
+fn names(path: &std::path::Path) -> Vec<String> {
+    let mut names = fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_tmpfile_persist() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = open_dir(tmp.path());
+
+    let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR).unwrap();
+    write(&file, b"first").unwrap();
+    if file.is_unnamed() {
+        assert!(names(tmp.path()).is_empty());
+    }
+    file.persist("config").unwrap();
+    assert_eq!(fs::read(tmp.path().join("config")).unwrap(), b"first");
+
+    // Replace the file, checking that the old contents stay visible until
+    // the new file is persisted.
+    let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR).unwrap();
+    write(&file, b"second").unwrap();
+    assert_eq!(fs::read(tmp.path().join("config")).unwrap(), b"first");
+    let stat = fstat(&file).unwrap();
+    assert_eq!(stat.st_mode & 0o777, 0o600);
+    file.persist("config").unwrap();
+    assert_eq!(fs::read(tmp.path().join("config")).unwrap(), b"second");
+
+    assert_eq!(names(tmp.path()), ["config"]);
+}
+
+#[test]
+fn test_tmpfile_persist_noclobber() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = open_dir(tmp.path());
+
+    let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR).unwrap();
+    write(&file, b"first").unwrap();
+    file.persist_noclobber("config").unwrap();
+
+    let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR).unwrap();
+    write(&file, b"second").unwrap();
+    assert_eq!(file.persist_noclobber("config"), Err(Errno::EXIST));
+    assert_eq!(fs::read(tmp.path().join("config")).unwrap(), b"first");
+
+    // The failed attempt leaves nothing behind.
+    assert_eq!(names(tmp.path()), ["config"]);
+}
+
+#[test]
+fn test_tmpfile_drop() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = open_dir(tmp.path());
+
+    let file = TmpFile::new(&dir, Mode::RUSR | Mode::WUSR).unwrap();
+    write(&file, b"discarded").unwrap();
+    drop(file);
+    assert!(names(tmp.path()).is_empty());
+}
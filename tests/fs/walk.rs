@@ -0,0 +1,227 @@
+use rustix::fs::{
+    cwd, openat, unlinkat, AtFlags, FileType, Mode, OFlags, StatxFlags, Walk, WalkOrder,
+};
+use rustix::io::Errno;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Creates `a/b/c`, `a/d`, `e`, `f/`, and a symlink `a/link` pointing to the
+/// root.
+fn make_tree(root: &Path) {
+    fs::create_dir_all(root.join("a/b")).unwrap();
+    fs::create_dir(root.join("f")).unwrap();
+    fs::write(root.join("a/b/c"), b"c").unwrap();
+    fs::write(root.join("a/d"), b"d").unwrap();
+    fs::write(root.join("e"), b"e").unwrap();
+    std::os::unix::fs::symlink("../..", root.join("a/link")).unwrap();
+}
+
+fn open_dir(path: &Path) -> rustix::fd::OwnedFd {
+    openat(
+        cwd(),
+        path,
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap()
+}
+
+/// Returns the paths visited, in order, with their types and depths.
+fn collect<Fd: rustix::fd::AsFd>(walk: &mut Walk<Fd>) -> Vec<(String, FileType, usize)> {
+    let mut entries = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = entry.unwrap();
+        let path = entry.path().into_string().unwrap();
+        assert_eq!(
+            entry.file_name().to_bytes(),
+            path.rsplit('/').next().unwrap().as_bytes()
+        );
+        entries.push((path, entry.file_type(), entry.depth()));
+    }
+    entries
+}
+
+fn position(entries: &[(String, FileType, usize)], path: &str) -> usize {
+    entries.iter().position(|(p, _, _)| p == path).unwrap()
+}
+
+#[test]
+fn test_walk_depth_first() {
+    let dir = tempfile::tempdir().unwrap();
+    make_tree(dir.path());
+    let root = open_dir(dir.path());
+
+    let entries = collect(&mut Walk::new(&root).unwrap());
+    let types = entries
+        .iter()
+        .map(|(path, file_type, depth)| (path.as_str(), (*file_type, *depth)))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(types.len(), 7);
+    assert_eq!(types["a"], (FileType::Directory, 1));
+    assert_eq!(types["a/b"], (FileType::Directory, 2));
+    assert_eq!(types["a/b/c"], (FileType::RegularFile, 3));
+    assert_eq!(types["a/d"], (FileType::RegularFile, 2));
+    assert_eq!(types["a/link"], (FileType::Symlink, 2));
+    assert_eq!(types["e"], (FileType::RegularFile, 1));
+    assert_eq!(types["f"], (FileType::Directory, 1));
+
+    // Directories come before their contents, which come before any of the
+    // directory's following siblings.
+    assert!(position(&entries, "a") < position(&entries, "a/b"));
+    assert!(position(&entries, "a/b") + 1 == position(&entries, "a/b/c"));
+    let a_end = ["a/b/c", "a/d", "a/link"]
+        .iter()
+        .map(|path| position(&entries, path))
+        .max()
+        .unwrap();
+    assert!(position(&entries, "e") < position(&entries, "a") || position(&entries, "e") > a_end);
+}
+
+#[test]
+fn test_walk_post_order_remove() {
+    let dir = tempfile::tempdir().unwrap();
+    make_tree(dir.path());
+    let root = open_dir(dir.path());
+
+    let mut walk = Walk::new(&root)
+        .unwrap()
+        .order(WalkOrder::DepthFirstPostOrder);
+    let mut count = 0;
+    while let Some(entry) = walk.next() {
+        let entry = entry.unwrap();
+        let flags = if entry.file_type() == FileType::Directory {
+            AtFlags::REMOVEDIR
+        } else {
+            AtFlags::empty()
+        };
+        // Removing a directory only succeeds if its contents were visited
+        // first.
+        unlinkat(entry.parent_fd(), entry.file_name(), flags).unwrap();
+        count += 1;
+    }
+    assert_eq!(count, 7);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_walk_breadth_first() {
+    let dir = tempfile::tempdir().unwrap();
+    make_tree(dir.path());
+    let root = open_dir(dir.path());
+
+    let entries = collect(&mut Walk::new(&root).unwrap().order(WalkOrder::BreadthFirst));
+    assert_eq!(entries.len(), 7);
+    assert!(entries.windows(2).all(|w| w[0].2 <= w[1].2));
+}
+
+#[test]
+fn test_walk_max_depth_and_skip() {
+    let dir = tempfile::tempdir().unwrap();
+    make_tree(dir.path());
+    let root = open_dir(dir.path());
+
+    let entries = collect(&mut Walk::new(&root).unwrap().max_depth(1));
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().all(|(_, _, depth)| *depth == 1));
+
+    let mut walk = Walk::new(&root).unwrap();
+    let mut paths = Vec::new();
+    loop {
+        let path = match walk.next() {
+            Some(entry) => entry.unwrap().path().into_string().unwrap(),
+            None => break,
+        };
+        if path == "a" {
+            walk.skip_current_dir();
+        }
+        paths.push(path);
+    }
+    paths.sort();
+    assert_eq!(paths, ["a", "e", "f"]);
+}
+
+#[test]
+fn test_walk_statx() {
+    let dir = tempfile::tempdir().unwrap();
+    make_tree(dir.path());
+    let root = open_dir(dir.path());
+
+    let mut walk = Walk::new(&root).unwrap();
+    while let Some(entry) = walk.next() {
+        let entry = entry.unwrap();
+        match entry.statx(StatxFlags::TYPE | StatxFlags::INO | StatxFlags::SIZE) {
+            Ok(statx) => {
+                assert_eq!(u64::from(statx.stx_ino), entry.ino());
+                if entry.file_type() == FileType::RegularFile {
+                    assert_eq!(statx.stx_size, 1);
+                }
+            }
+            // Older kernels and some sandboxes lack `statx`.
+            Err(Errno::NOSYS) | Err(Errno::PERM) => return,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+}
+
+#[test]
+fn test_walk_fd_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut path = dir.path().to_owned();
+    for i in 0..20 {
+        path.push(format!("d{}", i));
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("file"), b"").unwrap();
+    }
+    let root = open_dir(dir.path());
+
+    for order in [
+        WalkOrder::DepthFirst,
+        WalkOrder::DepthFirstPostOrder,
+        WalkOrder::BreadthFirst,
+    ]
+    .iter()
+    {
+        let entries = collect(&mut Walk::new(&root).unwrap().order(*order).max_open_fds(2));
+        assert_eq!(entries.len(), 40);
+        assert_eq!(entries.iter().map(|(_, _, depth)| *depth).max(), Some(21));
+    }
+}
+
+#[test]
+fn test_walk_replaced_ancestor() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("a/b/c/d/e")).unwrap();
+    fs::write(dir.path().join("a/b/c/d/e/f"), b"").unwrap();
+    let root = open_dir(dir.path());
+
+    let mut walk = Walk::new(&root)
+        .unwrap()
+        .order(WalkOrder::DepthFirstPostOrder)
+        .max_open_fds(2);
+    assert_eq!(
+        walk.next().unwrap().unwrap().path().to_bytes(),
+        b"a/b/c/d/e/f"
+    );
+
+    // Replace `a` while its file descriptor is closed. The walker must not
+    // reopen the replacement in its place.
+    fs::rename(dir.path().join("a"), dir.path().join("a2")).unwrap();
+    fs::create_dir_all(dir.path().join("a/b/c")).unwrap();
+
+    let mut errors = 0;
+    while let Some(entry) = walk.next() {
+        match entry {
+            Ok(_) => {}
+            Err(Errno::NOENT) => errors += 1,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+    assert!(errors > 0);
+}
+
+#[test]
+fn test_walk_not_dir() {
+    let file = tempfile::tempfile().unwrap();
+    assert_eq!(Walk::new(&file).unwrap_err(), Errno::NOTDIR);
+}
@@ -123,3 +123,18 @@ fn test_vmsplice_read() {
     assert_eq!(&outputs.1, b" ");
     assert_eq!(&outputs.2, b"world");
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_pipe_size() {
+    use rustix::io::{fcntl_getpipe_sz, fcntl_setpipe_sz, pipe};
+
+    let (read_p, write_p) = pipe().unwrap();
+    let size = fcntl_getpipe_sz(&read_p).unwrap();
+    assert!(size > 0);
+
+    // The kernel rounds the size up to a page or more.
+    let new_size = fcntl_setpipe_sz(&write_p, size * 2 - 1).unwrap();
+    assert!(new_size >= size * 2 - 1);
+    assert_eq!(fcntl_getpipe_sz(&read_p).unwrap(), new_size);
+}
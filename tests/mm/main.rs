@@ -4,10 +4,18 @@
 #![cfg_attr(target_os = "wasi", feature(wasi_ext))]
 #![cfg_attr(io_lifetimes_use_std, feature(io_safety))]
 
+#[cfg(not(windows))]
+mod mapping;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod mempolicy;
 #[cfg(not(windows))]
 #[cfg(not(target_os = "wasi"))]
 mod mlock;
 #[cfg(not(windows))]
 mod mmap;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod pkey;
 #[cfg(not(windows))]
 mod prot;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod userfaultfd;
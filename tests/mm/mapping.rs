@@ -0,0 +1,134 @@
+#![cfg(not(target_os = "wasi"))]
+
+use rustix::mm::{MapFlags, Mapping, MprotectFlags, MsyncFlags, ProtFlags};
+
+#[test]
+fn test_mapping_anonymous() {
+    let mut mapping =
+        Mapping::anonymous(8192, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    assert_eq!(mapping.len(), 8192);
+    assert!(mapping.as_slice().unwrap().iter().all(|b| *b == 0));
+    mapping.as_mut_slice().unwrap()[4096] = 7;
+    assert_eq!(mapping.as_slice().unwrap()[4096], 7);
+
+    mapping.protect(MprotectFlags::READ).unwrap();
+    assert_eq!(mapping.prot(), ProtFlags::READ);
+    assert!(mapping.as_mut_slice().is_none());
+    assert_eq!(mapping.as_slice().unwrap()[4096], 7);
+
+    mapping.protect(MprotectFlags::empty()).unwrap();
+    assert!(mapping.as_slice().is_none());
+}
+
+#[test]
+fn test_mapping_fixed() {
+    assert_eq!(
+        Mapping::anonymous(4096, ProtFlags::READ, MapFlags::PRIVATE | MapFlags::FIXED).unwrap_err(),
+        rustix::io::Errno::INVAL
+    );
+}
+
+#[test]
+fn test_mapping_raw_parts() {
+    let mapping = Mapping::anonymous(4096, ProtFlags::READ, MapFlags::PRIVATE).unwrap();
+    let (ptr, len) = mapping.into_raw_parts();
+    let mapping = unsafe { Mapping::from_raw_parts(ptr, len, ProtFlags::READ) };
+    assert_eq!(mapping.as_ptr(), ptr);
+    assert_eq!(mapping.as_slice().unwrap().len(), 4096);
+}
+
+#[test]
+fn test_mapping_lock() {
+    let mapping = Mapping::anonymous(4096, ProtFlags::READ, MapFlags::PRIVATE).unwrap();
+    match mapping.lock() {
+        Ok(()) => mapping.unlock().unwrap(),
+        // Locking may be denied by `RLIMIT_MEMLOCK`.
+        Err(rustix::io::Errno::NOMEM) | Err(rustix::io::Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[cfg(not(target_os = "redox"))]
+#[test]
+fn test_mapping_advise() {
+    use rustix::mm::Advice;
+
+    let mut mapping =
+        Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    mapping.advise(Advice::WillNeed).unwrap();
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        use rustix::io;
+
+        mapping.as_mut_slice().unwrap()[0] = 1;
+        mapping.advise(Advice::LinuxDontNeed).unwrap();
+        assert_eq!(mapping.as_slice().unwrap()[0], 0);
+
+        assert_eq!(mapping.advise(Advice::LinuxFree), Err(io::Errno::INVAL));
+        assert_eq!(mapping.advise(Advice::LinuxRemove), Err(io::Errno::INVAL));
+        assert_eq!(mapping.advise(Advice::LinuxHwPoison), Err(io::Errno::INVAL));
+        unsafe { mapping.advise_unchecked(Advice::LinuxFree) }.unwrap();
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_mapping_remap() {
+    use rustix::mm::MremapFlags;
+
+    let mut mapping =
+        Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    mapping.as_mut_slice().unwrap()[0] = 3;
+    mapping.remap(3 * 4096, MremapFlags::MAYMOVE).unwrap();
+    assert_eq!(mapping.len(), 3 * 4096);
+    let slice = mapping.as_mut_slice().unwrap();
+    assert_eq!(slice[0], 3);
+    slice[2 * 4096] = 4;
+    mapping.remap(4096, MremapFlags::empty()).unwrap();
+    assert_eq!(
+        mapping.as_slice().unwrap(),
+        &{
+            let mut page = [0; 4096];
+            page[0] = 3;
+            page
+        }[..]
+    );
+}
+
+#[cfg(feature = "fs")]
+#[cfg(not(target_os = "redox"))]
+#[test]
+fn test_mapping_file() {
+    use rustix::fs::{cwd, ftruncate, openat, Mode, OFlags};
+    use rustix::io::pread;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let file = openat(
+        cwd(),
+        tmp.path().join("file"),
+        OFlags::CREATE | OFlags::RDWR,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+    ftruncate(&file, 8192).unwrap();
+
+    // Safety: Nothing else modifies the file while it's mapped.
+    let mut mapping = unsafe {
+        Mapping::map(
+            8192,
+            ProtFlags::READ | ProtFlags::WRITE,
+            MapFlags::SHARED,
+            &file,
+            0,
+        )
+    }
+    .unwrap();
+    mapping.as_mut_slice().unwrap()[100] = b'x';
+    mapping.sync(MsyncFlags::SYNC).unwrap();
+    drop(mapping);
+
+    let mut buf = [0_u8; 1];
+    pread(&file, &mut buf, 100).unwrap();
+    assert_eq!(buf, [b'x']);
+}
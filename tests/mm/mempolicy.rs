@@ -0,0 +1,151 @@
+//! Tests for NUMA memory policies.
+//!
+//! Kernels built without `CONFIG_NUMA` fail these calls with `ENOSYS`, so
+//! these tests return early in that case. Only node 0 is assumed to exist.
+
+use rustix::io::Errno;
+use rustix::mm::{
+    get_mempolicy, get_mempolicy_addr, get_mempolicy_node, get_mems_allowed, mbind, migrate_pages,
+    move_pages, set_mempolicy, MapFlags, Mapping, MbindFlags, MemPolicyFlags, MemPolicyMode,
+    NodeMask, ProtFlags,
+};
+
+fn numa_supported() -> bool {
+    match get_mems_allowed() {
+        Ok(_) => true,
+        Err(Errno::NOSYS) => false,
+        Err(err) => Err(err).unwrap(),
+    }
+}
+
+fn node0() -> NodeMask {
+    let mut nodes = NodeMask::new();
+    nodes.set(0);
+    nodes
+}
+
+#[test]
+fn test_node_mask() {
+    let mut nodes = NodeMask::new();
+    assert_eq!(nodes.count(), 0);
+    nodes.set(0);
+    nodes.set(NodeMask::MAX_NODE - 1);
+    assert!(nodes.is_set(0));
+    assert!(!nodes.is_set(1));
+    assert!(nodes.is_set(NodeMask::MAX_NODE - 1));
+    assert_eq!(nodes.count(), 2);
+    nodes.unset(0);
+    assert!(!nodes.is_set(0));
+    nodes.clear();
+    assert_eq!(nodes, NodeMask::default());
+}
+
+#[test]
+fn test_set_mempolicy() {
+    if !numa_supported() {
+        return;
+    }
+
+    set_mempolicy(MemPolicyMode::Preferred, MemPolicyFlags::empty(), &node0()).unwrap();
+    let policy = get_mempolicy().unwrap();
+    assert_eq!(policy.mode, MemPolicyMode::Preferred);
+    assert_eq!(policy.flags, MemPolicyFlags::empty());
+    assert_eq!(policy.nodes, node0());
+
+    set_mempolicy(
+        MemPolicyMode::Preferred,
+        MemPolicyFlags::STATIC_NODES,
+        &node0(),
+    )
+    .unwrap();
+    let policy = get_mempolicy().unwrap();
+    assert_eq!(policy.mode, MemPolicyMode::Preferred);
+    assert_eq!(policy.flags, MemPolicyFlags::STATIC_NODES);
+
+    set_mempolicy(
+        MemPolicyMode::Default,
+        MemPolicyFlags::empty(),
+        &NodeMask::new(),
+    )
+    .unwrap();
+    let policy = get_mempolicy().unwrap();
+    assert_eq!(policy.mode, MemPolicyMode::Default);
+    assert_eq!(policy.nodes.count(), 0);
+}
+
+#[test]
+fn test_mbind() {
+    if !numa_supported() {
+        return;
+    }
+
+    let mut mapping =
+        Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    unsafe {
+        mbind(
+            mapping.as_ptr(),
+            mapping.len(),
+            MemPolicyMode::Preferred,
+            MemPolicyFlags::empty(),
+            &node0(),
+            MbindFlags::empty(),
+        )
+        .unwrap();
+    }
+    let policy = get_mempolicy_addr(mapping.as_ptr()).unwrap();
+    assert_eq!(policy.mode, MemPolicyMode::Preferred);
+    assert_eq!(policy.nodes, node0());
+
+    mapping.as_mut_slice().unwrap()[0] = 1;
+    assert_eq!(get_mempolicy_node(mapping.as_ptr()).unwrap(), 0);
+}
+
+#[test]
+fn test_get_mems_allowed() {
+    if !numa_supported() {
+        return;
+    }
+
+    let nodes = get_mems_allowed().unwrap();
+    assert!(nodes.is_set(0));
+}
+
+#[test]
+fn test_move_pages() {
+    if !numa_supported() {
+        return;
+    }
+
+    let mut mapping =
+        Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    mapping.as_mut_slice().unwrap()[0] = 1;
+
+    let pages = [mapping.as_ptr()];
+    let mut status = [-1];
+    assert_eq!(
+        move_pages(None, &pages, None, &mut status, MbindFlags::empty()).unwrap(),
+        0
+    );
+    assert_eq!(status[0], 0);
+
+    let nodes = [0];
+    assert_eq!(
+        move_pages(None, &pages, Some(&nodes), &mut status, MbindFlags::MOVE).unwrap(),
+        0
+    );
+    assert_eq!(status[0], 0);
+
+    assert_eq!(
+        move_pages(None, &pages, None, &mut [], MbindFlags::empty()),
+        Err(Errno::INVAL)
+    );
+}
+
+#[test]
+fn test_migrate_pages() {
+    if !numa_supported() {
+        return;
+    }
+
+    assert_eq!(migrate_pages(None, &node0(), &node0()).unwrap(), 0);
+}
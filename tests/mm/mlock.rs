@@ -77,3 +77,21 @@ fn test_mlock_with_onfault() {
         }
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_mlockall() {
+    use rustix::mm::{mlockall, munlockall, MlockallFlags};
+
+    // Don't use `MCL_FUTURE`, which would affect the other tests running in
+    // this process, or populate everything up front.
+    match mlockall(MlockallFlags::CURRENT | MlockallFlags::ONFAULT) {
+        Ok(()) => unsafe { munlockall().unwrap() },
+        // Tests won't always have enough memory or permissions, and that's ok.
+        Err(rustix::io::Errno::PERM)
+        | Err(rustix::io::Errno::NOMEM)
+        | Err(rustix::io::Errno::INVAL) => {}
+        // But they shouldn't fail otherwise.
+        Err(other) => Err(other).unwrap(),
+    }
+}
@@ -161,3 +161,80 @@ fn test_msync() {
         munmap(addr, 8192).unwrap();
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_mincore() {
+    use rustix::mm::{mincore, mmap_anonymous, munmap, MapFlags, ProtFlags};
+    use std::ptr::null_mut;
+
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let len = 4 * page_size;
+    unsafe {
+        let addr = mmap_anonymous(
+            null_mut(),
+            len,
+            ProtFlags::READ | ProtFlags::WRITE,
+            MapFlags::PRIVATE,
+        )
+        .unwrap();
+
+        let mut vec = [0xff_u8; 4];
+        mincore(addr, len, &mut vec).unwrap();
+        assert!(vec.iter().all(|b| b & 1 == 0));
+
+        *addr.cast::<u8>() = 1;
+        *addr.cast::<u8>().add(2 * page_size) = 1;
+        mincore(addr, len, &mut vec).unwrap();
+        assert_eq!(vec.iter().map(|b| b & 1).collect::<Vec<_>>(), [1, 0, 1, 0]);
+
+        // A `vec` too short for the range is rejected.
+        assert_eq!(
+            mincore(addr, len, &mut vec[..3]),
+            Err(rustix::io::Errno::INVAL)
+        );
+        assert_eq!(
+            mincore(addr, 3 * page_size + 1, &mut vec[..3]),
+            Err(rustix::io::Errno::INVAL)
+        );
+        mincore(addr, 3 * page_size, &mut vec[..3]).unwrap();
+
+        // Unaligned addresses are rejected.
+        assert_eq!(
+            mincore(addr.cast::<u8>().add(1).cast(), 1, &mut vec),
+            Err(rustix::io::Errno::INVAL)
+        );
+
+        munmap(addr, len).unwrap();
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_madvise() {
+    use rustix::fd::{FromRawFd, OwnedFd};
+    use rustix::io::IoSlice;
+    use rustix::mm::{process_madvise, Advice, MapFlags, Mapping, ProtFlags};
+
+    let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, libc::getpid(), 0) };
+    if pidfd == -1 {
+        // `pidfd_open` is new in Linux 5.3, and `process_madvise` in 5.10.
+        assert_eq!(libc_errno::errno().0, libc::ENOSYS);
+        return;
+    }
+    let pidfd = unsafe { OwnedFd::from_raw_fd(pidfd as _) };
+
+    // Each range must be page-aligned.
+    let mapping =
+        Mapping::anonymous(8192, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    let buf = mapping.as_slice().unwrap();
+    let ranges = [IoSlice::new(buf)];
+    match unsafe { process_madvise(&pidfd, &ranges, Advice::LinuxCold) } {
+        Ok(n) => assert_eq!(n, 8192),
+        // Advising another process needs privileges, and older kernels
+        // don't have `process_madvise`.
+        Err(rustix::io::Errno::PERM) | Err(rustix::io::Errno::NOSYS) => return,
+        Err(other) => Err(other).unwrap(),
+    }
+    assert!(buf.iter().all(|b| *b == 0));
+}
@@ -0,0 +1,78 @@
+//! Tests for memory protection keys.
+//!
+//! Protection keys need CPU support, so these tests return early when
+//! `pkey_alloc` fails with `ENOSPC` or `ENOSYS`.
+
+use rustix::io::Errno;
+use rustix::mm::{
+    pkey_alloc, pkey_free, pkey_mprotect, MapFlags, Mapping, MprotectFlags, PkeyAccessRights,
+    ProtFlags,
+};
+
+fn alloc(access_rights: PkeyAccessRights) -> Option<u32> {
+    match pkey_alloc(access_rights) {
+        Ok(pkey) => Some(pkey),
+        Err(Errno::NOSPC) | Err(Errno::NOSYS) | Err(Errno::INVAL) => None,
+        Err(err) => Err(err).unwrap(),
+    }
+}
+
+#[test]
+fn test_pkey_mprotect() {
+    let pkey = match alloc(PkeyAccessRights::empty()) {
+        Some(pkey) => pkey,
+        None => return,
+    };
+    let mut mapping =
+        Mapping::anonymous(4096, ProtFlags::READ | ProtFlags::WRITE, MapFlags::PRIVATE).unwrap();
+    unsafe {
+        pkey_mprotect(
+            mapping.as_ptr(),
+            4096,
+            MprotectFlags::READ | MprotectFlags::WRITE,
+            pkey,
+        )
+        .unwrap();
+    }
+    mapping.as_mut_slice().unwrap()[0] = 1;
+
+    // Retag the memory with the default key before freeing `pkey`.
+    unsafe {
+        pkey_mprotect(
+            mapping.as_ptr(),
+            4096,
+            MprotectFlags::READ | MprotectFlags::WRITE,
+            0,
+        )
+        .unwrap();
+        pkey_free(pkey).unwrap();
+    }
+    assert_eq!(mapping.as_slice().unwrap()[0], 1);
+
+    assert_eq!(unsafe { pkey_free(pkey) }, Err(Errno::INVAL));
+}
+
+#[cfg(all(asm, target_arch = "x86_64"))]
+#[test]
+fn test_pkey_get_set() {
+    use rustix::mm::{pkey_get, pkey_set};
+
+    let pkey = match alloc(PkeyAccessRights::DISABLE_WRITE) {
+        Some(pkey) => pkey,
+        None => return,
+    };
+    unsafe {
+        assert_eq!(pkey_get(pkey).unwrap(), PkeyAccessRights::DISABLE_WRITE);
+
+        pkey_set(pkey, PkeyAccessRights::DISABLE_ACCESS).unwrap();
+        assert_eq!(pkey_get(pkey).unwrap(), PkeyAccessRights::DISABLE_ACCESS);
+        // Other keys are unaffected.
+        assert_eq!(pkey_get(0).unwrap(), PkeyAccessRights::empty());
+        pkey_set(pkey, PkeyAccessRights::empty()).unwrap();
+        assert_eq!(pkey_get(pkey).unwrap(), PkeyAccessRights::empty());
+
+        assert_eq!(pkey_get(16), Err(Errno::INVAL));
+        assert_eq!(pkey_set(16, PkeyAccessRights::empty()), Err(Errno::INVAL));
+        pkey_free(pkey).unwrap();
+    }
+}
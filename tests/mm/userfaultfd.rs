@@ -0,0 +1,201 @@
+//! Tests for the `userfaultfd` API.
+//!
+//! `userfaultfd` may be disabled for unprivileged users, so these tests
+//! return early when it fails with `EPERM`.
+
+use rustix::fd::OwnedFd;
+use rustix::io::Errno;
+use rustix::mm::{
+    ioctl_uffdio_api, ioctl_uffdio_copy, ioctl_uffdio_register, ioctl_uffdio_unregister,
+    ioctl_uffdio_writeprotect, ioctl_uffdio_zeropage, read_uffd_event, userfaultfd, MapFlags,
+    Mapping, ProtFlags, UffdCopyMode, UffdEvent, UffdFeatureFlags, UffdIoctlFlags,
+    UffdPagefaultFlags, UffdRegisterMode, UffdWriteprotectMode, UffdZeropageMode, UserfaultfdFlags,
+};
+use std::thread;
+
+const PAGE: usize = 4096;
+
+fn uffd(features: UffdFeatureFlags) -> Option<OwnedFd> {
+    let uffd = match unsafe { userfaultfd(UserfaultfdFlags::CLOEXEC) } {
+        Ok(uffd) => uffd,
+        Err(Errno::PERM) | Err(Errno::NOSYS) => return None,
+        Err(err) => Err(err).unwrap(),
+    };
+    match ioctl_uffdio_api(&uffd, features) {
+        Ok(api) => {
+            assert!(api.features.contains(features));
+            assert!(api
+                .ioctls
+                .contains(UffdIoctlFlags::API | UffdIoctlFlags::REGISTER));
+            Some(uffd)
+        }
+        Err(Errno::INVAL) => None,
+        Err(err) => Err(err).unwrap(),
+    }
+}
+
+fn mapping(pages: usize) -> Mapping {
+    Mapping::anonymous(
+        pages * PAGE,
+        ProtFlags::READ | ProtFlags::WRITE,
+        MapFlags::PRIVATE,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_userfaultfd_missing() {
+    let uffd = match uffd(UffdFeatureFlags::THREAD_ID) {
+        Some(uffd) => uffd,
+        None => return,
+    };
+    let mapping = mapping(2);
+    let addr = mapping.as_ptr();
+    let ioctls =
+        unsafe { ioctl_uffdio_register(&uffd, addr, 2 * PAGE, UffdRegisterMode::MISSING) }.unwrap();
+    assert!(ioctls.contains(UffdIoctlFlags::COPY | UffdIoctlFlags::ZEROPAGE));
+
+    // Fault on both pages from another thread, and resolve the faults here.
+    let addr_int = addr as usize;
+    let faulter = thread::spawn(move || unsafe {
+        let first = *(addr_int as *const u8);
+        *((addr_int + PAGE) as *mut u8) = 1;
+        (first, *((addr_int + PAGE) as *const u8))
+    });
+
+    match unsafe { read_uffd_event(&uffd) }.unwrap() {
+        UffdEvent::Pagefault {
+            flags,
+            address,
+            thread_id,
+        } => {
+            assert_eq!(address as usize & !(PAGE - 1), addr as usize);
+            assert!(!flags.contains(UffdPagefaultFlags::WRITE));
+            assert!(thread_id.is_some());
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+    let src = [7_u8; PAGE];
+    let n = unsafe {
+        ioctl_uffdio_copy(
+            &uffd,
+            addr,
+            src.as_ptr().cast(),
+            PAGE,
+            UffdCopyMode::empty(),
+        )
+    }
+    .unwrap();
+    assert_eq!(n, PAGE);
+
+    match unsafe { read_uffd_event(&uffd) }.unwrap() {
+        UffdEvent::Pagefault { flags, address, .. } => {
+            assert_eq!(address as usize & !(PAGE - 1), addr as usize + PAGE);
+            assert!(flags.contains(UffdPagefaultFlags::WRITE));
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+    let n = unsafe {
+        ioctl_uffdio_zeropage(
+            &uffd,
+            addr.cast::<u8>().add(PAGE).cast(),
+            PAGE,
+            UffdZeropageMode::empty(),
+        )
+    }
+    .unwrap();
+    assert_eq!(n, PAGE);
+
+    assert_eq!(faulter.join().unwrap(), (7, 1));
+
+    // Populated pages can't be filled again.
+    assert_eq!(
+        unsafe {
+            ioctl_uffdio_copy(
+                &uffd,
+                addr,
+                src.as_ptr().cast(),
+                PAGE,
+                UffdCopyMode::empty(),
+            )
+        },
+        Err(Errno::EXIST)
+    );
+
+    unsafe { ioctl_uffdio_unregister(&uffd, addr, 2 * PAGE) }.unwrap();
+}
+
+#[test]
+fn test_userfaultfd_writeprotect() {
+    let uffd = match uffd(UffdFeatureFlags::PAGEFAULT_FLAG_WP) {
+        Some(uffd) => uffd,
+        None => return,
+    };
+    let mut mapping = mapping(1);
+    mapping.as_mut_slice().unwrap()[0] = 1;
+    let addr = mapping.as_ptr();
+    let ioctls = match unsafe { ioctl_uffdio_register(&uffd, addr, PAGE, UffdRegisterMode::WP) } {
+        Ok(ioctls) => ioctls,
+        // Write protection isn't supported on all architectures.
+        Err(Errno::INVAL) => return,
+        Err(err) => Err(err).unwrap(),
+    };
+    assert!(ioctls.contains(UffdIoctlFlags::WRITEPROTECT));
+    unsafe { ioctl_uffdio_writeprotect(&uffd, addr, PAGE, UffdWriteprotectMode::WP) }.unwrap();
+
+    let addr_int = addr as usize;
+    let writer = thread::spawn(move || unsafe { *(addr_int as *mut u8) = 2 });
+
+    match unsafe { read_uffd_event(&uffd) }.unwrap() {
+        UffdEvent::Pagefault { flags, address, .. } => {
+            assert_eq!(address as usize & !(PAGE - 1), addr as usize);
+            assert!(flags.contains(UffdPagefaultFlags::WRITE | UffdPagefaultFlags::WP));
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+    // Removing the protection also wakes the writer.
+    unsafe { ioctl_uffdio_writeprotect(&uffd, addr, PAGE, UffdWriteprotectMode::empty()) }.unwrap();
+    writer.join().unwrap();
+    assert_eq!(mapping.as_slice().unwrap()[0], 2);
+}
+
+#[test]
+fn test_userfaultfd_events() {
+    use rustix::mm::Advice;
+
+    let uffd = match uffd(UffdFeatureFlags::EVENT_REMOVE | UffdFeatureFlags::EVENT_UNMAP) {
+        Some(uffd) => uffd,
+        None => return,
+    };
+    let mut mapping = mapping(2);
+    let addr = mapping.as_ptr();
+    unsafe { ioctl_uffdio_register(&uffd, addr, 2 * PAGE, UffdRegisterMode::MISSING) }.unwrap();
+
+    // Non-cooperative events block the thread causing them until they're
+    // read, so read them from another thread.
+    let handler = thread::spawn(move || {
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            events.push(unsafe { read_uffd_event(&uffd) }.unwrap());
+        }
+        events
+    });
+    mapping.advise(Advice::LinuxDontNeed).unwrap();
+    drop(mapping);
+
+    let events = handler.join().unwrap();
+    match events[0] {
+        UffdEvent::Remove { start, end } => {
+            assert_eq!(start, addr);
+            assert_eq!(end as usize, addr as usize + 2 * PAGE);
+        }
+        ref event => panic!("unexpected event {:?}", event),
+    }
+    match events[1] {
+        UffdEvent::Unmap { start, end } => {
+            assert_eq!(start, addr);
+            assert_eq!(end as usize, addr as usize + 2 * PAGE);
+        }
+        ref event => panic!("unexpected event {:?}", event),
+    }
+}
@@ -100,3 +100,59 @@ fn test_getpgrp() {
         );
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_getresuid() {
+    let res = process::getresuid();
+    assert_eq!(res.real, process::getuid());
+    assert_eq!(res.effective, process::geteuid());
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_getresgid() {
+    let res = process::getresgid();
+    assert_eq!(res.real, process::getgid());
+    assert_eq!(res.effective, process::getegid());
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_setresuid_unchanged() {
+    let before = process::getresuid();
+    process::setresuid(None, None, None).unwrap();
+    process::setresgid(None, None, None).unwrap();
+    assert_eq!(process::getresuid(), before);
+}
+
+#[test]
+fn test_setuid_same() {
+    // Setting the IDs to their current values is always permitted, as long
+    // as the real and effective IDs agree.
+    if process::getuid() == process::geteuid() {
+        process::setuid(process::getuid()).unwrap();
+    }
+    if process::getgid() == process::getegid() {
+        process::setgid(process::getgid()).unwrap();
+    }
+}
+
+#[test]
+fn test_getgroups() {
+    let groups = process::getgroups().unwrap();
+    let len = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+    assert_eq!(groups.len(), len as usize);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_setgroups_same() {
+    let groups = process::getgroups().unwrap();
+    match process::setgroups(&groups) {
+        Ok(()) => assert_eq!(process::getgroups().unwrap(), groups),
+        // Setting the supplementary groups requires `CAP_SETGID`.
+        Err(rustix::io::Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
@@ -16,6 +16,8 @@ mod id;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod membarrier;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod personality;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod prctl;
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))] // WASI doesn't have [gs]etpriority.
 mod priority;
@@ -24,6 +26,8 @@ mod procctl;
 #[cfg(not(any(target_os = "fuchsia", target_os = "redox", target_os = "wasi")))]
 mod rlimit;
 mod sched_yield;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod sysinfo;
 #[cfg(not(target_os = "wasi"))] // WASI doesn't have uname.
 mod uname;
 #[cfg(not(target_os = "wasi"))] // WASI doesn't have waitpid.
@@ -0,0 +1,27 @@
+use rustix::process::{personality, set_personality, ExecutionDomain, PersonalityFlags};
+
+#[test]
+fn test_personality() {
+    let persona = personality().unwrap();
+
+    // Setting the current persona succeeds, and reports it as the previous
+    // one.
+    assert_eq!(set_personality(persona).unwrap(), persona);
+    assert_eq!(personality().unwrap(), persona);
+
+    // Switching the execution domain leaves the flags alone.
+    let mut linux32 = persona;
+    linux32.domain = ExecutionDomain::LINUX32;
+    assert_eq!(set_personality(linux32).unwrap(), persona);
+    let current = personality().unwrap();
+    assert_eq!(current.domain, ExecutionDomain::LINUX32);
+    assert_eq!(current.flags, persona.flags);
+
+    // Flags don't spill into the execution domain.
+    let mut flagged = persona;
+    flagged.flags |= PersonalityFlags::ADDR_NO_RANDOMIZE;
+    assert_eq!(set_personality(flagged).unwrap(), linux32);
+    assert_eq!(personality().unwrap().domain, persona.domain);
+
+    set_personality(persona).unwrap();
+}
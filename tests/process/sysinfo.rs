@@ -0,0 +1,12 @@
+use rustix::process::sysinfo;
+
+#[test]
+fn test_sysinfo() {
+    let info = sysinfo();
+
+    assert!(info.total_ram > 0);
+    assert!(info.free_ram <= info.total_ram);
+    assert!(info.free_swap <= info.total_swap);
+    assert!(info.procs > 0);
+    assert!(info.loads.iter().all(|load| *load >= 0.0));
+}
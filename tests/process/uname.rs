@@ -11,3 +11,21 @@ fn test_uname() {
     #[cfg(any(target_os = "android", target_os = "linux"))]
     assert!(!name.domainname().to_bytes().is_empty());
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_sethostname_unprivileged() {
+    // Don't touch the host's real hostname when running as root.
+    if rustix::process::geteuid().is_root() {
+        return;
+    }
+
+    // Setting the hostname fails without `CAP_SYS_ADMIN`, and setting it to
+    // its current value is harmless if we have that anyway.
+    let name = rustix::process::uname();
+    match rustix::process::sethostname(name.nodename().to_bytes()) {
+        Ok(()) | Err(rustix::io::Errno::PERM) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(rustix::process::uname().nodename(), name.nodename());
+}
@@ -0,0 +1,59 @@
+use rustix::fd::AsFd;
+use rustix::io::Errno;
+use rustix::process::{waitpid, Pid, WaitOptions};
+use rustix::runtime::{clone3, exit_group, CloneArgs, CloneChild};
+
+/// Run `clone3` with `args`, have the child exit with `code`, and return the
+/// parent's view of the child, or `None` if `clone3` isn't supported.
+fn clone3_exit(args: &CloneArgs<'_>, code: i32) -> Option<CloneChild> {
+    match unsafe { clone3(args) } {
+        Ok(Some(child)) => Some(child),
+        Ok(None) => exit_group(code),
+        Err(Errno::NOSYS) => None,
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+fn wait_exit_status(pid: Pid) -> Option<u32> {
+    waitpid(Some(pid), WaitOptions::empty())
+        .unwrap()
+        .unwrap()
+        .exit_status()
+}
+
+#[test]
+fn test_clone3_fork() {
+    let child = match clone3_exit(&CloneArgs::new(), 42) {
+        Some(child) => child,
+        None => return,
+    };
+    assert!(child.pidfd.is_none());
+    assert_eq!(wait_exit_status(child.pid), Some(42));
+}
+
+#[test]
+fn test_clone3_pidfd() {
+    let child = match clone3_exit(&CloneArgs::new().pidfd(true), 7) {
+        Some(child) => child,
+        None => return,
+    };
+    assert!(child.pidfd.is_some());
+    assert_eq!(wait_exit_status(child.pid), Some(7));
+}
+
+#[test]
+fn test_clone3_cgroup_size() {
+    // `CLONE_INTO_CGROUP` needs the larger version of `struct clone_args`.
+    // Kernels that predate it reject the size with `E2BIG`, and newer ones
+    // reject a directory that isn't in a cgroup2 hierarchy with `EBADF`.
+    let root = std::fs::File::open("/").unwrap();
+    match unsafe { clone3(&CloneArgs::new().cgroup(root.as_fd())) } {
+        Ok(Some(_)) | Ok(None) => panic!("clone3 succeeded with a non-cgroup fd"),
+        Err(Errno::NOSYS) => (),
+        Err(err) => assert!(
+            err == Errno::TOOBIG || err == Errno::BADF,
+            "unexpected error: {:?}",
+            err
+        ),
+    }
+}
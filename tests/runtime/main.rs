@@ -0,0 +1,20 @@
+//! Tests for [`rustix::runtime`].
+
+#![cfg(feature = "runtime")]
+#![cfg(not(windows))]
+#![cfg_attr(io_lifetimes_use_std, feature(io_safety))]
+#![cfg_attr(core_c_str, feature(core_c_str))]
+
+#[cfg(all(linux_raw, feature = "process", feature = "thread"))]
+mod clone3;
+#[cfg(all(
+    linux_raw,
+    asm,
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    ),
+    feature = "process"
+))]
+mod spawn;
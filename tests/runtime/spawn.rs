@@ -0,0 +1,39 @@
+use rustix::cstr;
+use rustix::ffi::CStr;
+use rustix::io::Errno;
+use rustix::process::{waitpid, Pid, WaitOptions};
+use rustix::runtime::{spawn, SpawnAction};
+
+/// Spawn `path` with no arguments other than its name, and an empty
+/// environment.
+fn spawn_path(path: &CStr, actions: &[SpawnAction<'_>]) -> rustix::io::Result<Pid> {
+    let argv = [path.as_ptr().cast::<u8>(), core::ptr::null()];
+    let envp = [core::ptr::null()];
+    unsafe { spawn(path, argv.as_ptr(), envp.as_ptr(), actions) }
+}
+
+fn wait_exit_status(pid: Pid) -> Option<u32> {
+    waitpid(Some(pid), WaitOptions::empty())
+        .unwrap()
+        .unwrap()
+        .exit_status()
+}
+
+#[test]
+fn test_spawn_exit_status() {
+    let pid = spawn_path(cstr!("/bin/true"), &[]).unwrap();
+    assert_eq!(wait_exit_status(pid), Some(0));
+
+    let pid = spawn_path(cstr!("/bin/false"), &[SpawnAction::ResetSignalMask]).unwrap();
+    assert_eq!(wait_exit_status(pid), Some(1));
+}
+
+#[test]
+fn test_spawn_exec_failure() {
+    // The child's `execve` error is reported to the parent.
+    assert_eq!(
+        spawn_path(cstr!("/no/such/program"), &[]),
+        Err(Errno::NOENT)
+    );
+    assert_eq!(spawn_path(cstr!("/"), &[]), Err(Errno::ACCESS));
+}
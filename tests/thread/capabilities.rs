@@ -0,0 +1,62 @@
+use std::io::Read;
+
+use rustix::thread::*;
+
+#[test]
+fn test_capabilities() {
+    let sets = capabilities(None).unwrap();
+    assert!(sets.permitted.contains(sets.effective));
+
+    // Setting the capability sets to their current values is always
+    // permitted.
+    set_capabilities(None, sets).unwrap();
+    assert_eq!(capabilities(None).unwrap(), sets);
+}
+
+#[test]
+fn test_capability_flags_from_capability() {
+    assert_eq!(
+        CapabilityFlags::from(Capability::ChangeOwnership),
+        CapabilityFlags::CHOWN
+    );
+    assert_eq!(
+        CapabilityFlags::from(Capability::CheckpointRestore),
+        CapabilityFlags::CHECKPOINT_RESTORE
+    );
+}
+
+#[test]
+fn test_capabilities_from_proc_status() {
+    let status = b"Name:\tcat\nCapInh:\t0000000000000000\nCapPrm:\t00000000000000c0\n\
+CapEff:\t0000000000000040\nCapBnd:\t000001ffffffffff\nCapAmb:\t0000000000000000\n";
+    let sets = CapabilitySets::from_proc_status(status).unwrap();
+    assert_eq!(sets.inheritable, CapabilityFlags::empty());
+    assert_eq!(
+        sets.permitted,
+        CapabilityFlags::SETGID | CapabilityFlags::SETUID
+    );
+    assert_eq!(sets.effective, CapabilityFlags::SETGID);
+    assert_eq!(
+        capability_flags_from_proc_status(status, "CapBnd").unwrap(),
+        CapabilityFlags::all()
+    );
+    assert!(capability_flags_from_proc_status(status, "Cap").is_none());
+
+    // Bits for capabilities rustix doesn't know about are kept.
+    assert_eq!(
+        capability_flags_from_proc_status(b"CapBnd:\t000003ffffffffff\n", "CapBnd")
+            .unwrap()
+            .bits(),
+        0x3ff_ffff_ffff
+    );
+
+    let mut status = Vec::new();
+    std::fs::File::open("/proc/thread-self/status")
+        .unwrap()
+        .read_to_end(&mut status)
+        .unwrap();
+    assert_eq!(
+        CapabilitySets::from_proc_status(&status).unwrap(),
+        capabilities(None).unwrap()
+    );
+}
@@ -0,0 +1,177 @@
+use rustix::io::Errno;
+use rustix::thread::{
+    futex_cmp_requeue, futex_cmp_requeue_pi, futex_lock_pi, futex_requeue, futex_unlock_pi,
+    futex_wait, futex_wait_bitset, futex_waitv, futex_wake, futex_wake_bitset, gettid, ClockId,
+    FutexFlags, FutexWaitv, Timespec,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const FLAGS: FutexFlags = FutexFlags::PRIVATE;
+
+/// An absolute time that has already passed, on any clock.
+const PAST: Timespec = Timespec {
+    tv_sec: 0,
+    tv_nsec: 0,
+};
+
+#[test]
+fn test_futex_wait_timeout() {
+    let futex = AtomicU32::new(0);
+    assert_eq!(futex_wait(&futex, FLAGS, 1, None), Err(Errno::AGAIN));
+
+    let timeout = Timespec {
+        tv_sec: 0,
+        tv_nsec: 1_000_000,
+    };
+    assert_eq!(
+        futex_wait(&futex, FLAGS, 0, Some(&timeout)),
+        Err(Errno::TIMEDOUT)
+    );
+
+    assert_eq!(
+        futex_wait_bitset(&futex, FLAGS, 0, Some(&PAST), !0),
+        Err(Errno::TIMEDOUT)
+    );
+    assert_eq!(
+        futex_wait_bitset(
+            &futex,
+            FLAGS | FutexFlags::CLOCK_REALTIME,
+            0,
+            Some(&PAST),
+            !0
+        ),
+        Err(Errno::TIMEDOUT)
+    );
+    assert_eq!(
+        futex_wait_bitset(&futex, FLAGS, 0, Some(&PAST), 0),
+        Err(Errno::INVAL)
+    );
+    assert_eq!(futex_wake(&futex, FLAGS, 1), Ok(0));
+    assert_eq!(futex_wake_bitset(&futex, FLAGS, 1, !0), Ok(0));
+}
+
+#[test]
+fn test_futex_wait_wake() {
+    let futex = Arc::new(AtomicU32::new(0));
+    let waiter = {
+        let futex = futex.clone();
+        thread::spawn(move || {
+            while futex.load(Ordering::SeqCst) == 0 {
+                match futex_wait_bitset(&futex, FLAGS, 0, None, 0b10) {
+                    Ok(()) | Err(Errno::AGAIN) | Err(Errno::INTR) => (),
+                    Err(err) => panic!("{:?}", err),
+                }
+            }
+        })
+    };
+
+    futex.store(1, Ordering::SeqCst);
+    // A wake with a disjoint bitset must not wake the waiter.
+    assert_eq!(futex_wake_bitset(&futex, FLAGS, 1, 0b01), Ok(0));
+    futex_wake_bitset(&futex, FLAGS, 1, 0b10).unwrap();
+    waiter.join().unwrap();
+}
+
+#[test]
+fn test_futex_requeue() {
+    let futex = Arc::new(AtomicU32::new(0));
+    let futex2 = Arc::new(AtomicU32::new(0));
+
+    // With no waiters yet, nothing is woken or requeued.
+    assert_eq!(
+        futex_cmp_requeue(&futex, FLAGS, 0, 1, &futex2, 1),
+        Err(Errno::AGAIN)
+    );
+    assert_eq!(futex_requeue(&futex, FLAGS, 0, 1, &futex2), Ok(0));
+
+    let waiter = {
+        let futex = futex.clone();
+        thread::spawn(move || {
+            while futex.load(Ordering::SeqCst) == 0 {
+                match futex_wait(&futex, FLAGS, 0, None) {
+                    Ok(()) | Err(Errno::AGAIN) | Err(Errno::INTR) => (),
+                    Err(err) => panic!("{:?}", err),
+                }
+            }
+        })
+    };
+
+    // Wait until the waiter is blocked, and move it to `futex2`.
+    while futex_cmp_requeue(&futex, FLAGS, 0, 1, &futex2, 0).unwrap() == 0 {
+        thread::yield_now();
+    }
+    futex.store(1, Ordering::SeqCst);
+    assert_eq!(futex_wake(&futex, FLAGS, 1), Ok(0));
+    assert_eq!(futex_wake(&futex2, FLAGS, 1), Ok(1));
+    waiter.join().unwrap();
+}
+
+#[test]
+fn test_futex_lock_pi() {
+    let futex = AtomicU32::new(0);
+    futex_lock_pi(&futex, FLAGS, None).unwrap();
+    assert_eq!(
+        futex.load(Ordering::SeqCst),
+        gettid().as_raw_nonzero().get() as u32
+    );
+    assert_eq!(futex_lock_pi(&futex, FLAGS, None), Err(Errno::DEADLK));
+    futex_unlock_pi(&futex, FLAGS).unwrap();
+    assert_eq!(futex.load(Ordering::SeqCst), 0);
+    assert_eq!(futex_unlock_pi(&futex, FLAGS), Err(Errno::PERM));
+
+    let pi_futex = AtomicU32::new(0);
+    assert_eq!(
+        futex_cmp_requeue_pi(&futex, FLAGS, 1, &pi_futex, 1),
+        Err(Errno::AGAIN)
+    );
+}
+
+#[test]
+fn test_futex_waitv() {
+    let futexes = Arc::new([AtomicU32::new(0), AtomicU32::new(0)]);
+
+    let waiters = [
+        FutexWaitv::new(&futexes[0], 0, FLAGS),
+        FutexWaitv::new(&futexes[1], 1, FLAGS),
+    ];
+    match futex_waitv(&waiters, None, ClockId::Monotonic) {
+        Err(Errno::NOSYS) => return,
+        result => assert_eq!(result, Err(Errno::AGAIN)),
+    }
+
+    let waiters = [
+        FutexWaitv::new(&futexes[0], 0, FLAGS),
+        FutexWaitv::new(&futexes[1], 0, FLAGS),
+    ];
+    assert_eq!(
+        futex_waitv(&waiters, Some(&PAST), ClockId::Monotonic),
+        Err(Errno::TIMEDOUT)
+    );
+    assert_eq!(
+        futex_waitv(&waiters, Some(&PAST), ClockId::Realtime),
+        Err(Errno::TIMEDOUT)
+    );
+
+    let waiter = {
+        let futexes = futexes.clone();
+        thread::spawn(move || loop {
+            let waiters = [
+                FutexWaitv::new(&futexes[0], 0, FLAGS),
+                FutexWaitv::new(&futexes[1], 0, FLAGS),
+            ];
+            match futex_waitv(&waiters, None, ClockId::Monotonic) {
+                Ok(index) => return index,
+                // `futexes[1]` was changed before we started waiting.
+                Err(Errno::AGAIN) => return 1,
+                Err(Errno::INTR) => (),
+                Err(err) => panic!("{:?}", err),
+            }
+        })
+    };
+
+    futexes[1].store(1, Ordering::SeqCst);
+    futex_wake(&futexes[1], FLAGS, 1).unwrap();
+    assert_eq!(waiter.join().unwrap(), 1);
+}
@@ -3,11 +3,17 @@
 #![cfg(feature = "thread")]
 #![cfg(not(windows))]
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod capabilities;
 #[cfg(not(target_os = "redox"))]
 mod clocks;
+#[cfg(linux_raw)]
+mod futex;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod id;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod prctl;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod rseq;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod setns;
@@ -0,0 +1,69 @@
+use rustix::thread::{sched_getcpu, Rseq};
+
+#[test]
+fn test_sched_getcpu() {
+    let nprocs = num_cpus();
+    assert!(sched_getcpu().unwrap() < nprocs);
+
+    // An unregistered area falls back to `sched_getcpu`.
+    let rseq = Rseq::new();
+    assert_eq!(rseq.cpu_id(), None);
+    assert!(rseq.current_cpu().unwrap() < nprocs);
+}
+
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_endian = "little"),
+    target_arch = "riscv64",
+    target_arch = "powerpc64",
+))]
+#[test]
+fn test_rseq_register() {
+    use rustix::io::Errno;
+    use rustix::thread::{rseq_register, rseq_unregister, RSEQ_SIG};
+    use std::env;
+    use std::process::Command;
+
+    // glibc registers its own area for each thread, so run this test in a
+    // child process with that disabled.
+    if env::var_os("GLIBC_TUNABLES").is_none() {
+        let status = Command::new(env::current_exe().unwrap())
+            .args(&["--exact", "rseq::test_rseq_register"])
+            .env("GLIBC_TUNABLES", "glibc.pthread.rseq=0")
+            .status()
+            .unwrap();
+        assert!(status.success());
+        return;
+    }
+
+    let rseq = Box::new(Rseq::new());
+    match unsafe { rseq_register(&rseq, RSEQ_SIG) } {
+        Ok(()) => (),
+        // The kernel is too old, or libc registered an area anyway.
+        Err(Errno::NOSYS) | Err(Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+
+    let nprocs = num_cpus();
+    assert!((rseq.cpu_id().unwrap() as usize) < nprocs);
+    assert!((rseq.cpu_id_start() as usize) < nprocs);
+    assert!(rseq.current_cpu().unwrap() < nprocs);
+
+    unsafe {
+        assert_eq!(rseq_register(&rseq, RSEQ_SIG), Err(Errno::BUSY));
+        assert_eq!(rseq_unregister(&rseq, !RSEQ_SIG), Err(Errno::PERM));
+        rseq_unregister(&rseq, RSEQ_SIG).unwrap();
+    }
+    assert_eq!(rseq.cpu_id(), None);
+}
+
+fn num_cpus() -> usize {
+    std::fs::read_to_string("/sys/devices/system/cpu/possible")
+        .ok()
+        .and_then(|possible| {
+            let last = possible.trim().rsplit(|c| c == '-' || c == ',').next()?;
+            last.parse::<usize>().ok()
+        })
+        .map_or(usize::MAX, |last| last + 1)
+}